@@ -1,23 +1,38 @@
 use std::io;
 use std::time::{Duration, Instant};
 
-use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::event::{self, DisableFocusChange, EnableFocusChange, Event, KeyCode, KeyEventKind};
 use crossterm::terminal::{
     EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
 };
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Clear, List, ListItem, Paragraph, Wrap};
+use ratatui::widgets::{Block, Clear, List, ListItem, Paragraph, Row, Table, Tabs, Wrap};
 
 use tui_morph::backend::{MorphBackend, MorphConfig};
+use tui_morph::interpolate;
+use tui_morph::solver::MatchStrategy;
+use tui_morph::theme::ThemeMap;
+use tui_morph::transition::Transition;
+use tui_morph::weights::MorphWeights;
+
+mod soak;
 
 fn main() -> io::Result<()> {
+    if let Some(arg) = std::env::args().find(|a| a.starts_with("--soak")) {
+        let minutes = arg
+            .strip_prefix("--soak=")
+            .and_then(|m| m.parse().ok())
+            .unwrap_or(60);
+        return soak::run(minutes);
+    }
+
     enable_raw_mode()?;
-    crossterm::execute!(io::stdout(), EnterAlternateScreen)?;
+    crossterm::execute!(io::stdout(), EnterAlternateScreen, EnableFocusChange)?;
 
     let result = run();
 
     disable_raw_mode()?;
-    crossterm::execute!(io::stdout(), LeaveAlternateScreen)?;
+    crossterm::execute!(io::stdout(), DisableFocusChange, LeaveAlternateScreen)?;
 
     result
 }
@@ -26,88 +41,352 @@ fn run() -> io::Result<()> {
     let backend = CrosstermBackend::new(io::stdout());
     let config = MorphConfig {
         duration: Duration::from_millis(500),
+        weights: SCENES[0].weights,
         ..MorphConfig::default()
     };
-    let mut terminal = MorphBackend::wrap(backend, config)?;
-
-    let scenes: &[fn(&mut Frame)] = &[
-        scene_inbox,
-        scene_detail,
-        scene_article,
-        scene_article_modal,
-        scene_dashboard,
-        scene_about,
-    ];
-    let total = scenes.len() + 1;
-    let rangers_idx = scenes.len();
+    let mut terminal = Terminal::new(MorphBackend::new(backend, config)?.synchronized())?;
 
     let mut current = 0;
-    let mut ranger_count: usize = 0;
+    let mut step: usize = 0;
     let mut last_tick = Instant::now();
-    let tick_interval = Duration::from_millis(900);
 
-    terminal.draw(|f| scenes[current](f))?;
+    // The two most recent logical frames, for the scrubber.
+    let mut prev_frame: Option<Buffer> = None;
+    let mut last_frame = terminal
+        .draw(|f| draw_scene(f, current, step))?
+        .buffer
+        .clone();
+    let mut scrubber: Option<Scrubber> = None;
 
     loop {
-        let timeout = if current == rangers_idx {
+        if let Some(scrub) = scrubber.as_mut() {
+            if let Event::Key(key) = event::read()? {
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+
+                match key.code {
+                    KeyCode::Char('q') => break,
+                    KeyCode::Char('s') | KeyCode::Esc => {
+                        let saved = scrub.saved_duration;
+                        scrubber = None;
+                        terminal.backend_mut().config_mut().duration = saved;
+                        terminal.draw(|f| f.buffer_mut().clone_from(&last_frame))?;
+                        continue;
+                    }
+                    KeyCode::Right => scrub.step(0.05),
+                    KeyCode::Left => scrub.step(-0.05),
+                    KeyCode::Up => scrub.step(0.01),
+                    KeyCode::Down => scrub.step(-0.01),
+                    KeyCode::Home => scrub.t = 0.0,
+                    KeyCode::End => scrub.t = 1.0,
+                    _ => continue,
+                }
+
+                terminal.draw(|f| scrub.render(f))?;
+            }
+
+            continue;
+        }
+
+        let scene = &SCENES[current];
+        let timeout = if scene.auto.is_some() {
             Duration::from_millis(50)
         } else {
             Duration::from_secs(60)
         };
 
-        if event::poll(timeout)? {
-            if let Event::Key(key) = event::read()? {
-                if key.kind != KeyEventKind::Press {
+        let advance = if event::poll(timeout)? {
+            let key = match event::read()? {
+                Event::Key(key) => key,
+                Event::FocusGained => {
+                    terminal.backend().set_focused(true);
+                    continue;
+                }
+                Event::FocusLost => {
+                    terminal.backend().set_focused(false);
                     continue;
                 }
+                _ => continue,
+            };
 
-                match key.code {
-                    KeyCode::Char('q') | KeyCode::Esc => break,
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
 
-                    KeyCode::Right | KeyCode::Char(' ') | KeyCode::Enter => {
-                        current = (current + 1) % total;
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => break,
+
+                KeyCode::Char('s') => {
+                    if let Some(src) = &prev_frame {
+                        let backend = terminal.backend_mut();
+                        let scrub = Scrubber::new(src, &last_frame, backend.config());
+                        backend.config_mut().duration = Duration::ZERO;
+                        terminal.draw(|f| scrub.render(f))?;
+                        scrubber = Some(scrub);
                     }
 
-                    KeyCode::Left => {
-                        current = (current + total - 1) % total;
+                    continue;
+                }
+
+                KeyCode::Char('m') => {
+                    if let Some(src) = &prev_frame {
+                        preview_motion(&mut terminal, src, &last_frame)?;
                     }
 
-                    _ => continue,
+                    continue;
                 }
 
-                if current == rangers_idx {
-                    ranger_count = 0;
-                    last_tick = Instant::now();
-                    terminal.draw(|f| scene_rangers(f, ranger_count))?;
-                } else {
-                    terminal.draw(|f| scenes[current](f))?;
+                KeyCode::Char('n') => Advance::Step,
+
+                KeyCode::Right | KeyCode::Char(' ') | KeyCode::Enter => {
+                    Advance::Scene((current + 1) % SCENES.len())
                 }
+
+                KeyCode::Left => Advance::Scene((current + SCENES.len() - 1) % SCENES.len()),
+
+                _ => continue,
             }
-        } else if current == rangers_idx {
-            let pause = if ranger_count >= RANGERS.len() {
-                tick_interval * 3
-            } else {
-                tick_interval
-            };
+        } else if scene
+            .auto
+            .is_some_and(|delay| last_tick.elapsed() >= delay(step))
+        {
+            Advance::Step
+        } else {
+            continue;
+        };
 
-            if last_tick.elapsed() >= pause {
-                if ranger_count >= RANGERS.len() {
-                    ranger_count = 0;
-                } else {
-                    ranger_count += 1;
+        match advance {
+            Advance::Scene(next) => {
+                current = next;
+                step = 0;
+                terminal.backend_mut().config_mut().weights = SCENES[current].weights;
+            }
+            Advance::Step => {
+                if let Some(theme) = scene.theme {
+                    let duration = terminal.backend().config().duration;
+                    terminal
+                        .backend_mut()
+                        .theme_switch(&theme(step), duration)?;
                 }
 
-                last_tick = Instant::now();
-                terminal.draw(|f| scene_rangers(f, ranger_count))?;
+                step += 1;
             }
         }
+
+        last_tick = Instant::now();
+        let frame = terminal
+            .draw(|f| draw_scene(f, current, step))?
+            .buffer
+            .clone();
+        prev_frame = Some(std::mem::replace(&mut last_frame, frame));
+    }
+
+    Ok(())
+}
+
+enum Advance {
+    Scene(usize),
+    Step,
+}
+
+/// One demo screen. `step` starts at 0 on entry and counts `n` presses
+/// (or timer ticks, for `auto` scenes); static scenes ignore it.
+struct Scene {
+    name: &'static str,
+    draw: fn(&mut Frame, Rect, usize),
+    weights: MorphWeights,
+    /// Delay before the next automatic step.
+    auto: Option<fn(usize) -> Duration>,
+    /// Palette mapping from `step` to `step + 1`, played with the theme
+    /// API before the next draw.
+    theme: Option<fn(usize) -> ThemeMap>,
+}
+
+impl Scene {
+    const fn new(name: &'static str, draw: fn(&mut Frame, Rect, usize)) -> Self {
+        Self {
+            name,
+            draw,
+            weights: MorphWeights::CRISP,
+            auto: None,
+            theme: None,
+        }
+    }
+}
+
+const SCENES: &[Scene] = &[
+    Scene::new("Inbox", scene_inbox),
+    Scene::new("Detail", scene_detail),
+    Scene::new("Article", scene_article),
+    Scene::new("Modal", scene_article_modal),
+    Scene::new("Dashboard", scene_dashboard),
+    Scene::new("About", scene_about),
+    Scene {
+        auto: Some(rangers_delay),
+        ..Scene::new("Morphin", scene_rangers)
+    },
+    Scene {
+        weights: MorphWeights::FADE,
+        theme: Some(theme_map),
+        ..Scene::new("Theme", scene_theme)
+    },
+    Scene {
+        weights: MorphWeights::LIQUID,
+        ..Scene::new("Sort", scene_table)
+    },
+    Scene::new("Tabs", scene_tabs),
+    Scene {
+        auto: Some(|_| Duration::from_millis(700)),
+        ..Scene::new("Chat", scene_chat)
+    },
+    Scene {
+        weights: MorphWeights::LIQUID,
+        ..Scene::new("Kanban", scene_kanban)
+    },
+    Scene::new("Diff", scene_code),
+];
+
+fn draw_scene(f: &mut Frame, index: usize, step: usize) {
+    let (hdr, body) = split_header(f.area());
+    let scene = &SCENES[index];
+    let label = format!("{}/{} {}", index + 1, SCENES.len(), scene.name);
+
+    header(f, hdr, &label);
+    (scene.draw)(f, body, step);
+}
+
+/// Frame-by-frame inspection of the most recent transition.
+struct Scrubber {
+    transition: Transition,
+    easing: fn(f32) -> f32,
+    t: f32,
+    saved_duration: Duration,
+}
+
+impl Scrubber {
+    fn new(src: &Buffer, dst: &Buffer, config: &MorphConfig) -> Self {
+        Self {
+            transition: Transition::new(src, dst, config),
+            easing: config.easing,
+            t: 0.0,
+            saved_duration: config.duration,
+        }
+    }
+
+    fn step(&mut self, dt: f32) {
+        self.t = (self.t + dt).clamp(0.0, 1.0);
+    }
+
+    fn render(&self, f: &mut Frame) {
+        let eased = (self.easing)(self.t);
+        let frame = interpolate::render(self.transition.plan(), eased);
+        f.buffer_mut().clone_from(&frame);
+
+        let area = f.area();
+        let bar = Rect::new(area.x, area.bottom().saturating_sub(1), area.width, 1);
+        let status = format!(
+            " SCRUB  t={:.2}  eased={:.3}  [</> 0.05] [^/v 0.01] [home/end] [s exit] ",
+            self.t, eased
+        );
+
+        f.render_widget(
+            Paragraph::new(status).style(
+                Style::new()
+                    .fg(Color::Rgb(20, 20, 20))
+                    .bg(Color::Rgb(255, 200, 80)),
+            ),
+            bar,
+        );
+    }
+}
+
+/// Motion-reduction levels an app might offer users who opt out of motion.
+#[derive(Clone, Copy)]
+enum MotionLevel {
+    Full,
+    /// Colors and fades only; nothing travels.
+    ColorOnly,
+    /// Cut straight to the target.
+    None,
+}
+
+impl MotionLevel {
+    const ALL: [Self; 3] = [Self::Full, Self::ColorOnly, Self::None];
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Full => "Full",
+            Self::ColorOnly => "ColorOnly",
+            Self::None => "None",
+        }
     }
 
+    fn apply(self, config: &mut MorphConfig) {
+        match self {
+            Self::Full => {}
+            Self::ColorOnly => config.solver.strategy = MatchStrategy::Crossfade,
+            Self::None => config.duration = Duration::ZERO,
+        }
+    }
+}
+
+/// Replay the most recent transition once per `MotionLevel`, labelled.
+fn preview_motion(
+    terminal: &mut Terminal<MorphBackend<CrosstermBackend<io::Stdout>>>,
+    src: &Buffer,
+    dst: &Buffer,
+) -> io::Result<()> {
+    let config = terminal.backend().config();
+    let (duration, strategy) = (config.duration, config.solver.strategy);
+
+    for (i, level) in MotionLevel::ALL.into_iter().enumerate() {
+        let text = format!(" MOTION  {}  ({}/3) ", level.label(), i + 1);
+
+        let config = terminal.backend_mut().config_mut();
+        config.duration = Duration::ZERO;
+        terminal.draw(|f| {
+            f.buffer_mut().clone_from(src);
+            status_bar(f, &text);
+        })?;
+        std::thread::sleep(Duration::from_millis(400));
+
+        let config = terminal.backend_mut().config_mut();
+        config.duration = duration;
+        config.solver.strategy = strategy;
+        level.apply(config);
+        terminal.draw(|f| {
+            f.buffer_mut().clone_from(dst);
+            status_bar(f, &text);
+        })?;
+        std::thread::sleep(Duration::from_millis(800));
+    }
+
+    let config = terminal.backend_mut().config_mut();
+    config.duration = duration;
+    config.solver.strategy = strategy;
+    terminal.draw(|f| f.buffer_mut().clone_from(dst))?;
+
     Ok(())
 }
 
+fn status_bar(f: &mut Frame, text: &str) {
+    let area = f.area();
+    let bar = Rect::new(area.x, area.bottom().saturating_sub(1), area.width, 1);
+
+    f.render_widget(
+        Paragraph::new(text).style(
+            Style::new()
+                .fg(Color::Rgb(20, 20, 20))
+                .bg(Color::Rgb(255, 200, 80)),
+        ),
+        bar,
+    );
+}
+
 fn header(f: &mut Frame, area: Rect, label: &str) {
-    let text = format!("tui-morph  [</>  cycle]  [q quit]  |  {label}");
+    let text =
+        format!("tui-morph  [</>  cycle]  [n step]  [s scrub]  [m motion]  [q quit]  |  {label}");
 
     f.render_widget(
         Paragraph::new(text).style(Style::new().fg(Color::DarkGray)),
@@ -136,10 +415,7 @@ fn centered_rect(width_pct: u16, height_pct: u16, area: Rect) -> Rect {
     .split(v[1])[1]
 }
 
-fn scene_inbox(f: &mut Frame) {
-    let (hdr, body) = split_header(f.area());
-    header(f, hdr, "1/7 Inbox");
-
+fn scene_inbox(f: &mut Frame, body: Rect, _step: usize) {
     let cols = Layout::horizontal([Constraint::Percentage(35), Constraint::Min(0)]).split(body);
 
     let messages: &[(&str, &str, bool)] = &[
@@ -203,10 +479,7 @@ fn scene_inbox(f: &mut Frame) {
     );
 }
 
-fn scene_detail(f: &mut Frame) {
-    let (hdr, body) = split_header(f.area());
-    header(f, hdr, "2/7 Detail");
-
+fn scene_detail(f: &mut Frame, body: Rect, _step: usize) {
     let cols = Layout::horizontal([Constraint::Length(4), Constraint::Min(0)]).split(body);
 
     let indicators: Vec<ListItem> =
@@ -261,10 +534,7 @@ fn scene_detail(f: &mut Frame) {
     );
 }
 
-fn scene_article(f: &mut Frame) {
-    let (hdr, body) = split_header(f.area());
-    header(f, hdr, "3/7 Article");
-
+fn scene_article(f: &mut Frame, body: Rect, _step: usize) {
     let text = "Frame-Level Morphing for Terminal UIs\n\
                 =====================================\n\
                 \n\
@@ -315,10 +585,7 @@ fn scene_article(f: &mut Frame) {
     );
 }
 
-fn scene_article_modal(f: &mut Frame) {
-    let (hdr, body) = split_header(f.area());
-    header(f, hdr, "4/7 Modal");
-
+fn scene_article_modal(f: &mut Frame, body: Rect, _step: usize) {
     let bg_text = "Frame-Level Morphing for Terminal UIs\n\
                    =====================================\n\
                    \n\
@@ -366,10 +633,7 @@ fn scene_article_modal(f: &mut Frame) {
     );
 }
 
-fn scene_dashboard(f: &mut Frame) {
-    let (hdr, body) = split_header(f.area());
-    header(f, hdr, "5/7 Dashboard");
-
+fn scene_dashboard(f: &mut Frame, body: Rect, _step: usize) {
     let grid_rows =
         Layout::vertical([Constraint::Percentage(50), Constraint::Percentage(50)]).split(body);
 
@@ -442,10 +706,7 @@ fn scene_dashboard(f: &mut Frame) {
     }
 }
 
-fn scene_about(f: &mut Frame) {
-    let (hdr, body) = split_header(f.area());
-    header(f, hdr, "6/7 About");
-
+fn scene_about(f: &mut Frame, body: Rect, _step: usize) {
     f.render_widget(
         Block::new().style(Style::new().bg(Color::Rgb(20, 10, 30))),
         body,
@@ -488,6 +749,7 @@ fn scene_about(f: &mut Frame) {
     );
 }
 
+#[rustfmt::skip]
 const RANGERS: &[(&str, &str, &str, Color)] = &[
     ("Jason", "Red Ranger", "Tyrannosaurus", Color::Rgb(220, 40, 40)),
     ("Zack", "Black Ranger", "Mastodon", Color::Rgb(200, 200, 210)),
@@ -497,9 +759,18 @@ const RANGERS: &[(&str, &str, &str, Color)] = &[
     ("Tommy", "Green Ranger", "Dragonzord", Color::Rgb(40, 200, 80)),
 ];
 
-fn scene_rangers(f: &mut Frame, count: usize) {
-    let (hdr, body) = split_header(f.area());
-    header(f, hdr, "7/7 Morphin");
+fn rangers_delay(step: usize) -> Duration {
+    let tick = Duration::from_millis(900);
+
+    if step % (RANGERS.len() + 1) == RANGERS.len() {
+        tick * 3
+    } else {
+        tick
+    }
+}
+
+fn scene_rangers(f: &mut Frame, body: Rect, step: usize) {
+    let count = step % (RANGERS.len() + 1);
 
     let items: Vec<ListItem> = RANGERS[..count]
         .iter()
@@ -529,3 +800,542 @@ fn scene_rangers(f: &mut Frame, count: usize) {
         body,
     );
 }
+
+/// One UI palette; the theme scene swaps between two of these.
+struct Palette {
+    bg: Color,
+    surface: Color,
+    fg: Color,
+    muted: Color,
+    accent: Color,
+    border: Color,
+}
+
+impl Palette {
+    fn colors(&self) -> [Color; 6] {
+        [
+            self.bg,
+            self.surface,
+            self.fg,
+            self.muted,
+            self.accent,
+            self.border,
+        ]
+    }
+}
+
+const DARK: Palette = Palette {
+    bg: Color::Rgb(18, 18, 24),
+    surface: Color::Rgb(30, 30, 42),
+    fg: Color::Rgb(220, 220, 235),
+    muted: Color::Rgb(120, 120, 140),
+    accent: Color::Rgb(110, 160, 255),
+    border: Color::Rgb(70, 70, 95),
+};
+
+const LIGHT: Palette = Palette {
+    bg: Color::Rgb(245, 244, 240),
+    surface: Color::Rgb(228, 226, 218),
+    fg: Color::Rgb(35, 35, 45),
+    muted: Color::Rgb(125, 122, 115),
+    accent: Color::Rgb(30, 90, 200),
+    border: Color::Rgb(185, 180, 170),
+};
+
+fn palette(step: usize) -> &'static Palette {
+    if step.is_multiple_of(2) {
+        &DARK
+    } else {
+        &LIGHT
+    }
+}
+
+fn theme_map(step: usize) -> ThemeMap {
+    let (from, to) = (palette(step), palette(step + 1));
+    from.colors().into_iter().zip(to.colors()).collect()
+}
+
+fn scene_theme(f: &mut Frame, body: Rect, step: usize) {
+    let p = palette(step);
+    let cols = Layout::horizontal([Constraint::Length(22), Constraint::Min(0)]).split(body);
+    let rows = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).split(cols[1]);
+
+    let sections = [
+        "General",
+        "Appearance",
+        "Editor",
+        "Keybindings",
+        "Extensions",
+    ];
+    let items: Vec<ListItem> = sections
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            let style = if i == 1 {
+                Style::new().fg(p.bg).bg(p.accent)
+            } else {
+                Style::new().fg(p.fg)
+            };
+            ListItem::new(format!(" {name}")).style(style)
+        })
+        .collect();
+
+    f.render_widget(
+        List::new(items).block(
+            Block::bordered()
+                .title(" Settings ")
+                .border_style(Style::new().fg(p.border))
+                .style(Style::new().fg(p.fg).bg(p.surface)),
+        ),
+        cols[0],
+    );
+
+    let mode = if step.is_multiple_of(2) {
+        "Dark"
+    } else {
+        "Light"
+    };
+    let text = vec![
+        Line::from(Span::styled("Appearance", Style::new().fg(p.accent).bold())),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Theme        ", Style::new().fg(p.muted)),
+            Span::styled(mode, Style::new().fg(p.fg)),
+        ]),
+        Line::from(vec![
+            Span::styled("Font size    ", Style::new().fg(p.muted)),
+            Span::styled("14", Style::new().fg(p.fg)),
+        ]),
+        Line::from(vec![
+            Span::styled("Line height  ", Style::new().fg(p.muted)),
+            Span::styled("1.4", Style::new().fg(p.fg)),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Press n to switch between light and dark.",
+            Style::new().fg(p.muted),
+        )),
+    ];
+
+    f.render_widget(
+        Paragraph::new(text).block(
+            Block::bordered()
+                .border_style(Style::new().fg(p.border))
+                .style(Style::new().fg(p.fg).bg(p.bg)),
+        ),
+        rows[0],
+    );
+
+    f.render_widget(
+        Paragraph::new(" saved  |  UTF-8  |  LF").style(Style::new().fg(p.bg).bg(p.accent)),
+        rows[1],
+    );
+}
+
+#[rustfmt::skip]
+const PROCESSES: &[(&str, u32, f32, u32)] = &[
+    ("postgres", 812, 12.4, 1840),
+    ("nginx", 1203, 1.8, 96),
+    ("cargo", 4410, 88.1, 2210),
+    ("redis-server", 977, 3.2, 410),
+    ("sshd", 601, 0.1, 12),
+    ("node", 3320, 24.7, 1320),
+    ("rust-analyzer", 4102, 41.5, 3050),
+    ("dockerd", 702, 5.6, 780),
+    ("systemd", 1, 0.3, 24),
+    ("tmux", 2280, 0.9, 38),
+    ("firefox", 2901, 17.3, 4120),
+    ("zellij", 2544, 2.1, 64),
+];
+
+const SORT_COLUMNS: [&str; 4] = ["Name", "PID", "CPU %", "Mem MB"];
+
+fn scene_table(f: &mut Frame, body: Rect, step: usize) {
+    let column = step % SORT_COLUMNS.len();
+    let mut rows: Vec<_> = PROCESSES.to_vec();
+
+    match column {
+        0 => rows.sort_by_key(|p| p.0),
+        1 => rows.sort_by_key(|p| p.1),
+        2 => rows.sort_by(|a, b| b.2.total_cmp(&a.2)),
+        _ => rows.sort_by_key(|p| std::cmp::Reverse(p.3)),
+    }
+
+    let header = Row::new(SORT_COLUMNS.iter().enumerate().map(|(i, name)| {
+        let style = if i == column {
+            Style::new().fg(Color::Rgb(255, 200, 80)).bold()
+        } else {
+            Style::new().fg(Color::Rgb(140, 140, 170))
+        };
+        Span::styled(*name, style)
+    }));
+
+    let table_rows = rows.iter().map(|(name, pid, cpu, mem)| {
+        let heat = (cpu * 2.5).min(255.0) as u8;
+        Row::new(vec![
+            Span::styled(*name, Style::new().fg(Color::Rgb(210, 210, 230))),
+            Span::styled(pid.to_string(), Style::new().fg(Color::Rgb(150, 150, 180))),
+            Span::styled(
+                format!("{cpu:.1}"),
+                Style::new().fg(Color::Rgb(120 + heat / 2, 220 - heat / 2, 120)),
+            ),
+            Span::styled(mem.to_string(), Style::new().fg(Color::Rgb(120, 180, 255))),
+        ])
+    });
+
+    let widths = [
+        Constraint::Length(16),
+        Constraint::Length(8),
+        Constraint::Length(8),
+        Constraint::Length(8),
+    ];
+
+    f.render_widget(
+        Table::new(table_rows, widths)
+            .header(header.bottom_margin(1))
+            .block(
+                Block::bordered()
+                    .title(format!(
+                        " Processes — sorted by {} [n] ",
+                        SORT_COLUMNS[column]
+                    ))
+                    .style(
+                        Style::new()
+                            .fg(Color::Rgb(100, 140, 255))
+                            .bg(Color::Rgb(12, 14, 24)),
+                    ),
+            ),
+        body,
+    );
+}
+
+const TABS: [&str; 4] = ["Overview", "Logs", "Settings", "Stats"];
+
+fn scene_tabs(f: &mut Frame, body: Rect, step: usize) {
+    let tab = step % TABS.len();
+    let rows = Layout::vertical([Constraint::Length(3), Constraint::Min(0)]).split(body);
+    let base = Style::new()
+        .fg(Color::Rgb(190, 190, 210))
+        .bg(Color::Rgb(16, 18, 28));
+
+    f.render_widget(
+        Tabs::new(TABS)
+            .select(tab)
+            .block(Block::bordered().title(" Project [n] "))
+            .style(base)
+            .highlight_style(
+                Style::new()
+                    .fg(Color::Rgb(16, 18, 28))
+                    .bg(Color::Rgb(120, 200, 160)),
+            ),
+        rows[0],
+    );
+
+    let content = Block::bordered().style(base);
+
+    match tab {
+        0 => f.render_widget(
+            Paragraph::new(
+                "tui-morph\n\n\
+                 Frame-level morphing layer for ratatui.\n\
+                 Diffs consecutive frames, matches moved glyphs,\n\
+                 and interpolates colors in Oklch.\n\n\
+                 Status: all checks passing.",
+            )
+            .block(content.title(" Overview ")),
+            rows[1],
+        ),
+        1 => {
+            let lines = [
+                ("INFO", "solver: 412 stable, 38 mutating, 12 displaced"),
+                ("INFO", "transition: 200ms at 60fps"),
+                ("WARN", "frame budget exceeded by 2ms"),
+                ("INFO", "flush: 1.8 KiB"),
+                ("ERROR", "sink closed: broken pipe"),
+                ("INFO", "resize: 100x30"),
+            ];
+            let items: Vec<ListItem> = lines
+                .iter()
+                .map(|(level, msg)| {
+                    let color = match *level {
+                        "WARN" => Color::Rgb(255, 200, 80),
+                        "ERROR" => Color::Rgb(255, 100, 100),
+                        _ => Color::Rgb(120, 180, 255),
+                    };
+                    ListItem::new(Line::from(vec![
+                        Span::styled(format!("{level:<6}"), Style::new().fg(color)),
+                        Span::raw(*msg),
+                    ]))
+                })
+                .collect();
+
+            f.render_widget(List::new(items).block(content.title(" Logs ")), rows[1]);
+        }
+        2 => {
+            let settings = Table::new(
+                [
+                    Row::new(["duration", "200ms"]),
+                    Row::new(["easing", "ease_in_out"]),
+                    Row::new(["weights", "CRISP"]),
+                    Row::new(["fps", "60"]),
+                    Row::new(["flash limit", "off"]),
+                ],
+                [Constraint::Length(16), Constraint::Min(0)],
+            );
+
+            f.render_widget(settings.block(content.title(" Settings ")), rows[1]);
+        }
+        _ => {
+            let stats = [
+                ("stable", 412),
+                ("mutating", 38),
+                ("displaced", 12),
+                ("orphan", 9),
+            ];
+            let max = stats.iter().map(|(_, n)| *n).max().unwrap_or(1);
+            let width = rows[1].width.saturating_sub(16) as usize;
+            let lines: Vec<Line> = stats
+                .iter()
+                .map(|(name, n)| {
+                    let bar = "█".repeat(width * n / max);
+                    Line::from(vec![
+                        Span::raw(format!("{name:<11}")),
+                        Span::styled(bar, Style::new().fg(Color::Rgb(120, 200, 160))),
+                    ])
+                })
+                .collect();
+
+            f.render_widget(
+                Paragraph::new(lines).block(content.title(" Stats ")),
+                rows[1],
+            );
+        }
+    }
+}
+
+#[rustfmt::skip]
+const CHAT: &[(&str, &str)] = &[
+    ("ana", "anyone seen the flaky resize test?"),
+    ("raj", "only on CI, can't repro locally"),
+    ("mei", "looks like a timing thing in the tick loop"),
+    ("ana", "bumping the duration fixes it for me"),
+    ("raj", "that just hides it though"),
+    ("kofi", "I'll add a fake clock"),
+    ("mei", "+1, sleeping in tests is asking for it"),
+    ("ana", "PR is up"),
+    ("raj", "reviewing now"),
+    ("kofi", "nit: the trait could live in its own module"),
+];
+
+const CHAT_USERS: [(&str, Color); 4] = [
+    ("ana", Color::Rgb(255, 140, 120)),
+    ("raj", Color::Rgb(120, 200, 255)),
+    ("mei", Color::Rgb(180, 255, 140)),
+    ("kofi", Color::Rgb(230, 170, 255)),
+];
+
+/// Every step appends a message; every fourth also edits the one posted
+/// three steps earlier.
+fn scene_chat(f: &mut Frame, body: Rect, step: usize) {
+    let visible = body.height.saturating_sub(2) as usize;
+    let first = (step + 1).saturating_sub(visible);
+
+    let items: Vec<ListItem> = (first..=step)
+        .map(|i| {
+            let (who, text) = CHAT[i % CHAT.len()];
+            let color = CHAT_USERS
+                .iter()
+                .find(|(name, _)| *name == who)
+                .map_or(Color::White, |(_, color)| *color);
+            let edited = i % 4 == 1 && i + 3 <= step;
+
+            let mut spans = vec![
+                Span::styled(format!("{:>5} ", i + 1), Style::new().fg(Color::DarkGray)),
+                Span::styled(format!("{who:<5}"), Style::new().fg(color).bold()),
+                Span::raw(" "),
+                Span::raw(text),
+            ];
+
+            if edited {
+                spans.push(Span::styled(
+                    " (edited)",
+                    Style::new().fg(Color::DarkGray).italic(),
+                ));
+            }
+
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    f.render_widget(
+        List::new(items).block(
+            Block::bordered().title(" #tui-morph ").style(
+                Style::new()
+                    .fg(Color::Rgb(210, 210, 225))
+                    .bg(Color::Rgb(20, 20, 28)),
+            ),
+        ),
+        body,
+    );
+}
+
+const KANBAN_COLUMNS: [&str; 3] = ["Todo", "Doing", "Done"];
+
+#[rustfmt::skip]
+const CARDS: &[(&str, Color, usize)] = &[
+    ("Fake clock", Color::Rgb(255, 140, 120), 0),
+    ("Resize handling", Color::Rgb(120, 200, 255), 0),
+    ("Plan cache", Color::Rgb(180, 255, 140), 0),
+    ("SVG export", Color::Rgb(230, 170, 255), 0),
+    ("Flash limit", Color::Rgb(255, 210, 100), 0),
+    ("Docs pass", Color::Rgb(120, 230, 220), 2),
+];
+
+/// (card, destination column), applied in order one per step.
+const KANBAN_MOVES: &[(usize, usize)] = &[
+    (0, 1),
+    (1, 1),
+    (0, 2),
+    (3, 1),
+    (1, 2),
+    (2, 1),
+    (3, 2),
+    (4, 1),
+    (2, 2),
+];
+
+fn scene_kanban(f: &mut Frame, body: Rect, step: usize) {
+    let mut column: Vec<usize> = CARDS.iter().map(|&(_, _, lane)| lane).collect();
+
+    for &(card, to) in &KANBAN_MOVES[..step % (KANBAN_MOVES.len() + 1)] {
+        column[card] = to;
+    }
+
+    let lanes = Layout::horizontal([Constraint::Ratio(1, 3); 3]).split(body);
+
+    for (lane, (area, title)) in lanes.iter().zip(KANBAN_COLUMNS).enumerate() {
+        f.render_widget(
+            Block::bordered().title(format!(" {title} ")).style(
+                Style::new()
+                    .fg(Color::Rgb(150, 150, 175))
+                    .bg(Color::Rgb(18, 18, 26)),
+            ),
+            *area,
+        );
+
+        let inner = area.inner(Margin::new(1, 1));
+        let cards = CARDS.iter().enumerate().filter(|(i, _)| column[*i] == lane);
+
+        for (slot, (_, (name, color, _))) in cards.enumerate() {
+            let card = Rect::new(inner.x, inner.y + slot as u16 * 3, inner.width, 3);
+
+            if card.bottom() > inner.bottom() {
+                break;
+            }
+
+            f.render_widget(
+                Paragraph::new(*name).block(
+                    Block::bordered()
+                        .border_style(Style::new().fg(*color))
+                        .style(
+                            Style::new()
+                                .fg(Color::Rgb(230, 230, 240))
+                                .bg(Color::Rgb(32, 32, 46)),
+                        ),
+                ),
+                card,
+            );
+        }
+    }
+}
+
+const CODE_BEFORE: &[&str] = &[
+    "fn flush(&mut self) -> io::Result<()> {",
+    "    let next = self.current_frame.clone();",
+    "",
+    "    if let Some(prev) = self.prev_frame.take() {",
+    "        self.run_transition(&prev, &next)?;",
+    "    } else {",
+    "        self.flush_buffer_to_inner(&next)?;",
+    "    }",
+    "",
+    "    // TODO: metrics",
+    "    self.prev_frame = Some(next);",
+    "    Ok(())",
+    "}",
+];
+
+const CODE_AFTER: &[&str] = &[
+    "fn flush(&mut self) -> io::Result<()> {",
+    "    let next = self.current_frame.clone();",
+    "    let start = Instant::now();",
+    "",
+    "    match self.prev_frame.take() {",
+    "        Some(prev) => self.run_transition(&prev, &next)?,",
+    "        None => self.flush_buffer_to_inner(&next, 1.0)?,",
+    "    }",
+    "",
+    "    self.metrics.flush_time = start.elapsed();",
+    "    self.prev_frame = Some(next);",
+    "    Ok(())",
+    "}",
+];
+
+const KEYWORDS: [&str; 9] = [
+    "fn", "let", "if", "else", "match", "mut", "self", "Some", "None",
+];
+
+fn highlight(line: &str) -> Line<'_> {
+    if line.trim_start().starts_with("//") {
+        return Line::styled(line, Style::new().fg(Color::Rgb(110, 120, 110)).italic());
+    }
+
+    let spans: Vec<Span> = line
+        .split_inclusive(|c: char| !c.is_alphanumeric() && c != '_')
+        .map(|token| {
+            let word = token.trim_end_matches(|c: char| !c.is_alphanumeric() && c != '_');
+
+            if KEYWORDS.contains(&word) {
+                Span::styled(token, Style::new().fg(Color::Rgb(200, 140, 255)))
+            } else {
+                Span::styled(token, Style::new().fg(Color::Rgb(210, 210, 200)))
+            }
+        })
+        .collect();
+
+    Line::from(spans)
+}
+
+/// Toggles between two versions of a function with inserted, removed,
+/// and rewritten lines.
+fn scene_code(f: &mut Frame, body: Rect, step: usize) {
+    let (title, code) = if step.is_multiple_of(2) {
+        (" src/backend.rs — before [n] ", CODE_BEFORE)
+    } else {
+        (" src/backend.rs — after [n] ", CODE_AFTER)
+    };
+
+    let lines: Vec<Line> = code
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            let mut numbered = highlight(line);
+            numbered.spans.insert(
+                0,
+                Span::styled(format!("{:>3}  ", i + 1), Style::new().fg(Color::DarkGray)),
+            );
+            numbered
+        })
+        .collect();
+
+    f.render_widget(
+        Paragraph::new(lines).block(
+            Block::bordered().title(title).style(
+                Style::new()
+                    .fg(Color::Rgb(140, 140, 160))
+                    .bg(Color::Rgb(22, 22, 20)),
+            ),
+        ),
+        body,
+    );
+}
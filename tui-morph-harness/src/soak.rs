@@ -0,0 +1,136 @@
+//! `--soak[=MINUTES]`: headless endurance run over random scene changes.
+//!
+//! Drives every scene through a `TestBackend` for the given time (default
+//! 60 minutes), printing a status line every `REPORT_EVERY` transitions,
+//! and fails once resident memory grows more than `MAX_GROWTH_MB` past
+//! its post-warmup baseline.
+
+use std::io;
+use std::time::{Duration, Instant};
+
+use ratatui::backend::TestBackend;
+
+use tui_morph::backend::{MorphBackend, MorphConfig};
+
+use crate::{SCENES, draw_scene};
+
+const WARMUP: u64 = 50;
+const REPORT_EVERY: u64 = 100;
+const MAX_GROWTH_MB: f64 = 64.0;
+
+pub fn run(minutes: u64) -> io::Result<()> {
+    let config = MorphConfig {
+        duration: Duration::from_millis(40),
+        ..MorphConfig::default()
+    };
+    let mut terminal = MorphBackend::wrap(TestBackend::new(100, 30), config)?;
+
+    let deadline = Instant::now() + Duration::from_secs(minutes * 60);
+    let start = Instant::now();
+    let mut rng = XorShift(0x9E37_79B9_7F4A_7C15);
+    let mut baseline = None;
+    let mut window = Window::default();
+
+    for n in 1.. {
+        if Instant::now() >= deadline {
+            break;
+        }
+
+        let scene = rng.below(SCENES.len());
+        let step = rng.below(16);
+        terminal.backend_mut().config_mut().weights = SCENES[scene].weights;
+
+        if let Some(theme) = SCENES[scene].theme {
+            terminal
+                .backend_mut()
+                .theme_switch(&theme(step), Duration::from_millis(40))?;
+        }
+
+        terminal.draw(|f| draw_scene(f, scene, step))?;
+        window.add(terminal.backend().metrics());
+
+        if n == WARMUP {
+            baseline = rss_mb();
+        }
+
+        if n % REPORT_EVERY == 0 {
+            let rss = rss_mb();
+            println!(
+                "{:>7.1}s  #{n}  rss {}  {}",
+                start.elapsed().as_secs_f32(),
+                rss.map_or("n/a".to_string(), |mb| format!("{mb:.1}MB")),
+                window.report(),
+            );
+            window = Window::default();
+
+            if let (Some(base), Some(now)) = (baseline, rss)
+                && now - base > MAX_GROWTH_MB
+            {
+                return Err(io::Error::other(format!(
+                    "soak: rss grew {:.1}MB past baseline {base:.1}MB after {n} transitions",
+                    now - base
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Aggregates over one report interval.
+#[derive(Default)]
+struct Window {
+    transitions: u32,
+    cells: usize,
+    max_cells: usize,
+    drift: Duration,
+    max_drift: Duration,
+    dropped: u32,
+}
+
+impl Window {
+    fn add(&mut self, m: &tui_morph::metrics::MorphMetrics) {
+        let cells = m.plan.changed();
+        let drift = m.elapsed.saturating_sub(m.duration);
+
+        self.transitions += 1;
+        self.cells += cells;
+        self.max_cells = self.max_cells.max(cells);
+        self.drift += drift;
+        self.max_drift = self.max_drift.max(drift);
+        self.dropped += m.dropped_frames;
+    }
+
+    fn report(&self) -> String {
+        let n = self.transitions.max(1);
+
+        format!(
+            "plan {} avg / {} max cells  drift {:?} avg / {:?} max  dropped {}",
+            self.cells / n as usize,
+            self.max_cells,
+            self.drift / n,
+            self.max_drift,
+            self.dropped,
+        )
+    }
+}
+
+/// Resident set size, where `/proc` exists.
+fn rss_mb() -> Option<f64> {
+    let statm = std::fs::read_to_string("/proc/self/statm").ok()?;
+    let pages: f64 = statm.split_whitespace().nth(1)?.parse().ok()?;
+
+    Some(pages * 4096.0 / (1024.0 * 1024.0))
+}
+
+/// Deterministic, so a failing run can be replayed.
+struct XorShift(u64);
+
+impl XorShift {
+    fn below(&mut self, n: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % n as u64) as usize
+    }
+}
@@ -0,0 +1,132 @@
+use std::f32::consts::TAU;
+use std::time::Duration;
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Color;
+
+use crate::oklch::{self, Oklch};
+
+/// Which cells an ambient layer drifts: every cell drawn in `Fg(color)`
+/// or on `Bg(color)`, and only that channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Accent {
+    Fg(Color),
+    Bg(Color),
+}
+
+/// Periodic color motion, as a function of time since the backend started.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Drift {
+    /// Lightness oscillates by `±amplitude` (Oklch L, so `0.05` is subtle).
+    Breathe { amplitude: f32, period: Duration },
+    /// Hue turns a full circle every `period`.
+    HueRotate { period: Duration },
+}
+
+impl Drift {
+    fn apply(self, lch: Oklch, elapsed: Duration) -> Oklch {
+        let phase = |period: Duration| {
+            if period.is_zero() {
+                0.0
+            } else {
+                (elapsed.as_secs_f64() / period.as_secs_f64()).fract() as f32
+            }
+        };
+
+        match self {
+            Self::Breathe { amplitude, period } => Oklch {
+                l: (lch.l + amplitude * (phase(period) * TAU).sin()).clamp(0.0, 1.0),
+                ..lch
+            },
+            Self::HueRotate { period } => Oklch {
+                h: (lch.h + phase(period) * TAU) % TAU,
+                ..lch
+            },
+        }
+    }
+}
+
+/// Idle animation over designated accent cells, played by
+/// `MorphBackend::ambient_tick` between transitions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AmbientLayer {
+    pub accent: Accent,
+    /// Restricts the layer to part of the screen; whole screen when `None`.
+    pub area: Option<Rect>,
+    pub drift: Drift,
+}
+
+impl AmbientLayer {
+    pub fn new(accent: Accent, drift: Drift) -> Self {
+        Self {
+            accent,
+            area: None,
+            drift,
+        }
+    }
+
+    pub fn within(mut self, area: Rect) -> Self {
+        self.area = Some(area);
+        self
+    }
+
+    /// Drift matching cells of `frame` to where they are `elapsed` in.
+    /// Colors without an RGB value (e.g. `Reset`) are left alone.
+    pub fn apply(&self, frame: &mut Buffer, elapsed: Duration) {
+        let area = self
+            .area
+            .map_or(frame.area, |area| area.intersection(frame.area));
+        let (base, is_fg) = match self.accent {
+            Accent::Fg(color) => (color, true),
+            Accent::Bg(color) => (color, false),
+        };
+
+        let Some(lch) = oklch::from_color(base) else {
+            return;
+        };
+        let drifted = oklch::to_color(self.drift.apply(lch, elapsed));
+
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                let cell = &mut frame[(x, y)];
+
+                if is_fg && cell.fg == base {
+                    cell.fg = drifted;
+                } else if !is_fg && cell.bg == base {
+                    cell.bg = drifted;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::style::Style;
+
+    use super::*;
+
+    #[test]
+    fn drifts_only_the_accent() {
+        let accent = Color::Rgb(40, 120, 220);
+        let mut frame = Buffer::empty(Rect::new(0, 0, 3, 1));
+        frame[(0, 0)].set_style(Style::new().fg(accent));
+        frame[(1, 0)].set_style(Style::new().fg(Color::Rgb(200, 200, 200)));
+
+        let layer = AmbientLayer::new(
+            Accent::Fg(accent),
+            Drift::HueRotate {
+                period: Duration::from_secs(4),
+            },
+        );
+
+        let mut at_rest = frame.clone();
+        layer.apply(&mut at_rest, Duration::ZERO);
+        assert_eq!(at_rest[(0, 0)].fg, accent);
+
+        layer.apply(&mut frame, Duration::from_secs(1));
+        assert_ne!(frame[(0, 0)].fg, accent);
+        assert_eq!(frame[(1, 0)].fg, Color::Rgb(200, 200, 200));
+    }
+}
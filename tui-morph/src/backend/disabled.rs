@@ -0,0 +1,290 @@
+use std::io;
+use std::time::{Duration, Instant};
+
+use ratatui::backend::{Backend, ClearType, WindowSize};
+use ratatui::buffer::{Buffer, Cell};
+use ratatui::layout::{Position, Rect, Size};
+use ratatui::style::Style;
+use ratatui::{Terminal, TerminalOptions, Viewport};
+
+use crate::ambient::AmbientLayer;
+use crate::clock::Clock;
+use crate::config::{MorphConfig, TransitionOverrides};
+use crate::control::MorphControl;
+use crate::effect::FrameEffect;
+use crate::export::FrameSink;
+use crate::focus::FocusHandle;
+use crate::history::MorphHistory;
+use crate::keys::KeyHandle;
+use crate::metrics::MorphMetrics;
+use crate::plan::InterpolationPlan;
+use crate::theme::ThemeMap;
+
+/// `MorphBackend` under the `disabled` feature: every `Backend` call goes
+/// straight to the inner backend, and the morph API is kept but does
+/// nothing, so apps build unchanged. Holds no frames; `config` is kept
+/// only to hand back, and `metrics` and `history` stay empty.
+pub struct MorphBackend<B: Backend> {
+    inner: B,
+    config: MorphConfig,
+    metrics: MorphMetrics,
+    history: MorphHistory,
+}
+
+impl<B: Backend> MorphBackend<B> {
+    pub fn new(inner: B, config: MorphConfig) -> io::Result<Self> {
+        Ok(Self {
+            inner,
+            config,
+            metrics: MorphMetrics::default(),
+            history: MorphHistory::default(),
+        })
+    }
+
+    pub fn wrap(backend: B, config: MorphConfig) -> io::Result<Terminal<Self>> {
+        Terminal::new(Self::new(backend, config)?)
+    }
+
+    pub fn wrap_inline(backend: B, config: MorphConfig, height: u16) -> io::Result<Terminal<Self>> {
+        let viewport = Viewport::Inline(height);
+        Terminal::with_options(Self::new(backend, config)?, TerminalOptions { viewport })
+    }
+
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+
+    pub fn inner_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+
+    pub fn config(&self) -> &MorphConfig {
+        &self.config
+    }
+
+    pub fn config_mut(&mut self) -> &mut MorphConfig {
+        &mut self.config
+    }
+
+    pub fn metrics(&self) -> &MorphMetrics {
+        &self.metrics
+    }
+
+    pub fn history(&self) -> &MorphHistory {
+        &self.history
+    }
+
+    pub fn history_mut(&mut self) -> &mut MorphHistory {
+        &mut self.history
+    }
+
+    pub fn last_plan(&self) -> Option<&InterpolationPlan> {
+        None
+    }
+
+    pub fn take_last_plan(&mut self) -> Option<InterpolationPlan> {
+        None
+    }
+
+    pub fn set_sink(&mut self, _sink: Box<dyn FrameSink>) {}
+
+    pub fn take_sink(&mut self) -> Option<Box<dyn FrameSink>> {
+        None
+    }
+
+    pub fn add_effect(&mut self, _effect: Box<dyn FrameEffect>) {}
+
+    pub fn clear_effects(&mut self) {}
+
+    pub fn add_exclusion(&mut self, _area: Rect) {}
+
+    pub fn clear_exclusions(&mut self) {}
+
+    pub fn add_region(&mut self, _area: Rect, _overrides: TransitionOverrides) {}
+
+    pub fn clear_regions(&mut self) {}
+
+    pub fn add_ambient(&mut self, _layer: AmbientLayer) {}
+
+    pub fn clear_ambient(&mut self) {}
+
+    pub fn ambient_tick(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    pub fn suspend(&mut self) {}
+
+    pub fn resume(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    pub fn set_focused(&self, _focused: bool) {}
+
+    /// A handle nothing reads.
+    pub fn focus_handle(&self) -> FocusHandle {
+        FocusHandle::new()
+    }
+
+    /// A handle nothing reads.
+    pub fn control(&self) -> MorphControl {
+        MorphControl::new()
+    }
+
+    /// A handle nothing reads.
+    pub fn keys(&self) -> KeyHandle {
+        KeyHandle::new()
+    }
+
+    pub fn invalidate(&mut self) {}
+
+    /// Dropped: the app's next draw shows its own frame.
+    pub fn queue(&mut self, _target: Buffer, _overrides: TransitionOverrides) -> io::Result<()> {
+        Ok(())
+    }
+
+    pub fn clear_queue(&mut self) {}
+
+    pub fn next_transition(&mut self, _overrides: TransitionOverrides) {}
+
+    pub fn skip_next(&mut self) {}
+
+    pub fn theme_switch(&mut self, _map: &ThemeMap, _duration: Duration) -> io::Result<()> {
+        Ok(())
+    }
+
+    pub fn pulse(&mut self, _area: Rect, _style: Style) -> io::Result<()> {
+        Ok(())
+    }
+
+    pub fn set_clock(&mut self, _clock: Box<dyn Clock>) {}
+
+    pub fn tick(&mut self) -> io::Result<bool> {
+        Ok(false)
+    }
+
+    pub fn advance(&mut self, _now: Instant) -> io::Result<bool> {
+        Ok(false)
+    }
+
+    pub fn is_animating(&self) -> bool {
+        false
+    }
+
+    pub fn next_frame_in(&self) -> Option<Duration> {
+        None
+    }
+}
+
+impl<B: Backend + io::Write> MorphBackend<B> {
+    pub fn synchronized(self) -> Self {
+        self
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<B: Backend> MorphBackend<B> {
+    pub async fn flush_async(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+
+    pub async fn finish_async(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<B: Backend> Backend for MorphBackend<B> {
+    fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        self.inner.draw(content)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        self.inner.hide_cursor()
+    }
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        self.inner.show_cursor()
+    }
+
+    fn get_cursor_position(&mut self) -> io::Result<Position> {
+        self.inner.get_cursor_position()
+    }
+
+    fn set_cursor_position<P: Into<Position>>(&mut self, position: P) -> io::Result<()> {
+        self.inner.set_cursor_position(position)
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        self.inner.clear()
+    }
+
+    fn clear_region(&mut self, clear_type: ClearType) -> io::Result<()> {
+        self.inner.clear_region(clear_type)
+    }
+
+    fn append_lines(&mut self, n: u16) -> io::Result<()> {
+        self.inner.append_lines(n)
+    }
+
+    fn size(&self) -> io::Result<Size> {
+        self.inner.size()
+    }
+
+    fn window_size(&mut self) -> io::Result<WindowSize> {
+        self.inner.window_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::backend::TestBackend;
+    use ratatui::style::Stylize;
+    use ratatui::widgets::Paragraph;
+
+    use crate::config::TickMode;
+
+    use super::*;
+
+    #[test]
+    fn frames_pass_straight_through() {
+        let config = MorphConfig {
+            tick: TickMode::Manual,
+            ..MorphConfig::default()
+        };
+        let mut terminal = MorphBackend::wrap(TestBackend::new(4, 1), config).unwrap();
+
+        for text in ["ab", "ba"] {
+            terminal
+                .draw(|f| f.render_widget(Paragraph::new(text), f.area()))
+                .unwrap();
+            terminal
+                .backend()
+                .inner()
+                .assert_buffer_lines([format!("{text:4}")]);
+        }
+
+        let backend = terminal.backend_mut();
+        backend
+            .queue(
+                Buffer::empty(Rect::new(0, 0, 4, 1)),
+                TransitionOverrides::default(),
+            )
+            .unwrap();
+        backend
+            .pulse(Rect::new(0, 0, 4, 1), Style::new().reversed())
+            .unwrap();
+        assert!(!backend.is_animating());
+        assert!(backend.last_plan().is_none());
+        backend.inner().assert_buffer_lines(["ba  "]);
+    }
+}
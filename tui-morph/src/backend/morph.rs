@@ -0,0 +1,2551 @@
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::io;
+use std::time::{Duration, Instant};
+
+use ratatui::backend::Backend;
+use ratatui::backend::ClearType;
+use ratatui::buffer::{Buffer, Cell};
+use ratatui::layout::{Position, Rect, Size};
+use ratatui::style::Style;
+use ratatui::{Terminal, TerminalOptions, Viewport};
+
+use crate::ambient::AmbientLayer;
+use crate::cache::PlanCache;
+use crate::clock::{Clock, SystemClock};
+use crate::config::{
+    Coalesce, CursorMotion, MorphConfig, SyncOutput, TickMode, TransitionOverrides,
+};
+use crate::control::MorphControl;
+use crate::effect::FrameEffect;
+use crate::export::FrameSink;
+use crate::focus::FocusHandle;
+use crate::graphics::Passthrough;
+use crate::history::MorphHistory;
+use crate::interpolate::ColorMode;
+use crate::keys::{FrameKeys, KeyHandle};
+use crate::metrics::MorphMetrics;
+use crate::plan::InterpolationPlan;
+use crate::solver::{self, SolveScratch, SolveStats};
+use crate::theme::ThemeMap;
+use crate::transition::{Pacing, PendingTransition, Transition};
+use crate::updates;
+
+/// Wraps any ratatui Backend, intercepting frames to produce smooth morph transitions.
+///
+/// Use `MorphBackend::wrap` to create a `Terminal<MorphBackend<B>>`.
+/// The application renders normally — morphing is transparent.
+pub struct MorphBackend<B: Backend> {
+    inner: B,
+    config: MorphConfig,
+
+    /// Full current frame, assembled incrementally from Terminal's deltas.
+    current_frame: Buffer,
+
+    /// Previous logical frame for diffing. `None` on first render.
+    prev_frame: Option<Buffer>,
+
+    /// Last frame sent to inner backend, for efficient diff-based updates.
+    last_flushed: Buffer,
+
+    /// Receives a copy of every frame sent to the inner backend.
+    sink: Option<Box<dyn FrameSink>>,
+
+    /// Set by `invalidate`; the next flush clears and repaints everything.
+    invalidated: bool,
+
+    /// Post-processing applied to intermediate frames, in order.
+    effects: Vec<Box<dyn FrameEffect>>,
+
+    /// Areas morphed separately; see `add_region`.
+    regions: Vec<(Rect, TransitionOverrides)>,
+
+    /// Idle color drift, applied by `ambient_tick`.
+    ambient: Vec<AmbientLayer>,
+
+    /// Time source for playback, sink timestamps and ambient drift.
+    clock: Box<dyn Clock>,
+
+    /// Zero point for sink timestamps and ambient drift.
+    epoch: Instant,
+
+    metrics: MorphMetrics,
+
+    /// Recent transitions, sized by `MorphConfig::history`.
+    history: MorphHistory,
+
+    /// Plan of the most recent transition, kept for inspection.
+    last_plan: Option<InterpolationPlan>,
+
+    /// Frame on screen at `suspend`, repainted by `resume`.
+    suspended: Option<Buffer>,
+
+    /// Transitions snap while unfocused.
+    focus: FocusHandle,
+
+    /// Settings changed from other threads; see `control`.
+    control: MorphControl,
+
+    /// Keys registered for the frame being drawn; see `keys`.
+    keys: KeyHandle,
+
+    /// Keys of the last flushed frame.
+    prev_keys: FrameKeys,
+
+    /// In-flight transition.
+    playing: Option<Playback>,
+
+    /// Consumed by the next flush; see `next_transition`.
+    overrides: Option<TransitionOverrides>,
+
+    /// The next flush cuts straight to its frame; see `skip_next`.
+    skip_next: bool,
+
+    /// A frame flushed under `Coalesce::Latest` waits for the in-flight
+    /// transition to finish.
+    pending: bool,
+
+    /// Targets to morph through after the in-flight transition; see
+    /// `queue`.
+    queue: VecDeque<(Buffer, TransitionOverrides)>,
+
+    /// The queue is playing; app flushes wait, as with `pending`.
+    queue_playing: bool,
+
+    /// The head of `queue`, solving on a worker thread while the step
+    /// before it plays.
+    solving: Option<PendingTransition>,
+
+    /// Last position passed to `set_cursor_position`.
+    cursor: Position,
+
+    /// Where the cursor was left by the last flushed frame.
+    cursor_shown: Position,
+
+    /// Last `show_cursor` / `hide_cursor` from the app.
+    cursor_visible: bool,
+
+    /// The cursor is hidden for `CursorMotion::Hide`.
+    cursor_hidden: bool,
+
+    /// Solver allocations kept between transitions.
+    scratch: SolveScratch,
+
+    /// Plans of recent frame pairs, per `config.plan_cache`.
+    plans: PlanCache,
+
+    /// Estimated output size of the current transition's in-between
+    /// frames, for `config.max_bytes_per_second`. Starts over with each
+    /// transition: a heavy one would otherwise hold the rate at zero, and
+    /// with no in-between frames nothing would measure it again.
+    frame_bytes: u32,
+
+    /// Writes a synchronized-output marker; see `synchronized`.
+    sync: Option<fn(&mut B, bool) -> io::Result<()>>,
+
+    /// `supports_synchronized_output`, checked once by `synchronized`.
+    sync_supported: bool,
+
+    /// `Some(height)` under `wrap_inline`.
+    inline: Option<u16>,
+
+    /// First row of the inline viewport, followed through `Terminal`'s
+    /// viewport clears.
+    viewport_top: u16,
+
+    /// Cells were drawn without a cursor call since. `Terminal::draw`
+    /// always touches the cursor before flushing; raw writes such as
+    /// `Terminal::insert_before` history lines do not.
+    unframed: bool,
+}
+
+impl<B: Backend> MorphBackend<B> {
+    pub fn new(inner: B, config: MorphConfig) -> io::Result<Self> {
+        let size = inner.size()?;
+        let area = Rect::new(0, 0, size.width, size.height);
+        let empty = Buffer::empty(area);
+
+        Ok(Self {
+            inner,
+            config,
+            current_frame: empty.clone(),
+            prev_frame: None,
+            last_flushed: empty,
+            sink: None,
+            effects: Vec::new(),
+            regions: Vec::new(),
+            ambient: Vec::new(),
+            invalidated: false,
+            clock: Box::new(SystemClock),
+            epoch: Instant::now(),
+            metrics: MorphMetrics::default(),
+            history: MorphHistory::default(),
+            last_plan: None,
+            suspended: None,
+            focus: FocusHandle::new(),
+            control: MorphControl::new(),
+            keys: KeyHandle::new(),
+            prev_keys: FrameKeys::new(),
+            playing: None,
+            overrides: None,
+            skip_next: false,
+            pending: false,
+            queue: VecDeque::new(),
+            queue_playing: false,
+            solving: None,
+            sync: None,
+            inline: None,
+            viewport_top: 0,
+            sync_supported: false,
+            cursor: Position::ORIGIN,
+            cursor_shown: Position::ORIGIN,
+            cursor_visible: true,
+            cursor_hidden: false,
+            scratch: SolveScratch::default(),
+            plans: PlanCache::new(),
+            frame_bytes: 0,
+            unframed: false,
+        })
+    }
+
+    pub fn wrap(backend: B, config: MorphConfig) -> io::Result<Terminal<Self>> {
+        let morph = Self::new(backend, config)?;
+        Terminal::new(morph)
+    }
+
+    /// `wrap` with a `Viewport::Inline` of `height` rows, e.g. below a
+    /// REPL prompt. Morphs stay inside the viewport: scrollback and lines
+    /// added with `Terminal::insert_before` are never animated or cleared.
+    pub fn wrap_inline(backend: B, config: MorphConfig, height: u16) -> io::Result<Terminal<Self>> {
+        let mut morph = Self::new(backend, config)?;
+        morph.inline = Some(height);
+
+        let viewport = Viewport::Inline(height);
+        let mut terminal = Terminal::with_options(morph, TerminalOptions { viewport })?;
+        let top = terminal.get_frame().area().y;
+        terminal.backend_mut().viewport_top = top;
+
+        Ok(terminal)
+    }
+
+    /// Where the app draws: the inline viewport, or the whole screen.
+    fn viewport(&self) -> Rect {
+        let area = self.current_frame.area;
+        let Some(height) = self.inline else {
+            return area;
+        };
+
+        let top = self.viewport_top.min(area.bottom());
+        Rect::new(area.x, top, area.width, height.min(area.bottom() - top))
+    }
+
+    /// A viewport-sized frame spliced into what is on screen.
+    fn on_screen_with(&self, part: Buffer) -> Buffer {
+        if part.area == self.last_flushed.area {
+            return part;
+        }
+
+        let mut full = self.last_flushed.clone();
+        full.merge(&part);
+        full
+    }
+
+    /// The wrapped backend.
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+
+    /// The wrapped backend, e.g. for backend-specific commands. Anything
+    /// written through it is invisible to the morph; call `invalidate` if
+    /// it changes the screen.
+    pub fn inner_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    /// Unwrap the morph layer. An in-flight transition stops where it is;
+    /// under `TickMode::Manual`, tick it to completion first.
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+
+    pub fn config(&self) -> &MorphConfig {
+        &self.config
+    }
+
+    /// Takes effect from the next flush.
+    pub fn config_mut(&mut self) -> &mut MorphConfig {
+        &mut self.config
+    }
+
+    /// Measurements from the most recent transition.
+    pub fn metrics(&self) -> &MorphMetrics {
+        &self.metrics
+    }
+
+    /// The last `MorphConfig::history` transitions, oldest first.
+    pub fn history(&self) -> &MorphHistory {
+        &self.history
+    }
+
+    pub fn history_mut(&mut self) -> &mut MorphHistory {
+        &mut self.history
+    }
+
+    /// How the most recent frame pair was classified. `None` until the
+    /// first transition, or after `take_last_plan`.
+    pub fn last_plan(&self) -> Option<&InterpolationPlan> {
+        self.last_plan.as_ref()
+    }
+
+    pub fn take_last_plan(&mut self) -> Option<InterpolationPlan> {
+        self.last_plan.take()
+    }
+
+    /// Tee every subsequently flushed frame into `sink`, replacing any
+    /// previous sink without finishing it.
+    pub fn set_sink(&mut self, sink: Box<dyn FrameSink>) {
+        self.sink = Some(sink);
+    }
+
+    /// Detach the current sink. The caller is responsible for `finish`.
+    pub fn take_sink(&mut self) -> Option<Box<dyn FrameSink>> {
+        self.sink.take()
+    }
+
+    /// Append `effect` to the post-processing chain. Takes effect from the
+    /// next transition.
+    pub fn add_effect(&mut self, effect: Box<dyn FrameEffect>) {
+        self.effects.push(effect);
+    }
+
+    pub fn clear_effects(&mut self) {
+        self.effects.clear();
+    }
+
+    /// Snap `area` straight to each new frame while the rest morphs.
+    pub fn add_exclusion(&mut self, area: Rect) {
+        self.config.solver.exclude.push(area);
+    }
+
+    pub fn clear_exclusions(&mut self) {
+        self.config.solver.exclude.clear();
+    }
+
+    /// Morph `area` on its own with `overrides`' duration, weights and
+    /// easing, e.g. a quick CRISP sidebar beside LIQUID main content. The
+    /// regions are composited over the rest of the frame, and a draw
+    /// finishes when the slowest of them does.
+    pub fn add_region(&mut self, area: Rect, overrides: TransitionOverrides) {
+        self.regions.push((area, overrides));
+    }
+
+    pub fn clear_regions(&mut self) {
+        self.regions.clear();
+    }
+
+    pub fn add_ambient(&mut self, layer: AmbientLayer) {
+        self.ambient.push(layer);
+    }
+
+    /// Remove all ambient layers. The next `ambient_tick` still repaints
+    /// the settled frame once, so no drifted color stays behind.
+    pub fn clear_ambient(&mut self) {
+        self.ambient.clear();
+    }
+
+    /// Repaint the settled frame with ambient layers advanced to now.
+    ///
+    /// Call from the app's idle loop (e.g. on an event-poll timeout) at
+    /// whatever rate the drift needs; `1 / config.fps` is the upper bound
+    /// worth using. The app's frame is untouched, so the next transition
+    /// still starts from what it drew. No-op while unfocused, before the
+    /// first draw, at `Quality::Low`, or when the color mode restricts
+    /// intermediate colors.
+    pub fn ambient_tick(&mut self) -> io::Result<()> {
+        let Some(settled) = self.prev_frame.as_ref() else {
+            return Ok(());
+        };
+
+        if self.playing.is_some()
+            || !self.focus.is_focused()
+            || !self.config.quality.ambient()
+            || self.config.color.resolve() != ColorMode::Full
+        {
+            return Ok(());
+        }
+
+        let elapsed = self.clock.now().saturating_duration_since(self.epoch);
+        let mut frame = settled.clone();
+
+        for layer in &self.ambient {
+            layer.apply(&mut frame, elapsed);
+        }
+
+        self.flush_buffer_to_inner(&frame, 1.0)
+    }
+
+    /// Snapshot the current frame before leaving the alternate screen,
+    /// e.g. to shell out. Pair with `resume`.
+    pub fn suspend(&mut self) {
+        self.suspended = self.prev_frame.clone();
+    }
+
+    /// Repaint the `suspend` snapshot right after re-entering the alternate
+    /// screen, so the app's next draw morphs from it instead of popping in
+    /// over a blank screen. With crossterm:
+    ///
+    /// ```ignore
+    /// terminal.backend_mut().suspend();
+    /// execute!(stdout(), LeaveAlternateScreen)?;
+    /// // ... run the child process ...
+    /// execute!(stdout(), EnterAlternateScreen)?;
+    /// terminal.clear()?;
+    /// terminal.backend_mut().resume()?;
+    /// terminal.draw(|f| app.render(f))?;
+    /// ```
+    pub fn resume(&mut self) -> io::Result<()> {
+        let Some(snapshot) = self.suspended.take() else {
+            return Ok(());
+        };
+
+        // `Terminal::clear` reset ratatui's buffer, so the next draw only
+        // sends non-blank cells; start from blank to match.
+        self.current_frame = Buffer::empty(snapshot.area);
+        self.invalidated = true;
+        self.flush_buffer_to_inner(&snapshot, 1.0)?;
+        self.prev_frame = Some(snapshot);
+
+        Ok(())
+    }
+
+    /// Report a terminal focus change. Unfocused, transitions (including
+    /// one in flight) jump to their final frame.
+    pub fn set_focused(&self, focused: bool) {
+        self.focus.set_focused(focused);
+    }
+
+    /// Shared focus flag, for setting from another thread while a
+    /// transition plays.
+    pub fn focus_handle(&self) -> FocusHandle {
+        self.focus.clone()
+    }
+
+    /// Shared handle for changing duration, weights or easing, or turning
+    /// morphing off, from another thread.
+    pub fn control(&self) -> MorphControl {
+        self.control.clone()
+    }
+
+    /// Shared handle for naming regions of the frame being drawn, e.g.
+    /// list entries, so they slide to their new places when reordered.
+    /// See `FrameKeys`.
+    pub fn keys(&self) -> KeyHandle {
+        self.keys.clone()
+    }
+
+    /// Forget what is on screen. The next flush clears the terminal and
+    /// repaints every cell — use after something else wrote to it.
+    pub fn invalidate(&mut self) {
+        self.invalidated = true;
+    }
+
+    fn flush_buffer_to_inner(&mut self, buf: &Buffer, t: f32) -> io::Result<()> {
+        self.flush_frame(buf, t, self.cursor)
+    }
+
+    /// Show `buf` with the cursor at `cursor`. Drawing moves the terminal's
+    /// cursor, so it is put back every frame.
+    fn flush_frame(&mut self, buf: &Buffer, t: f32, cursor: Position) -> io::Result<()> {
+        if std::mem::take(&mut self.invalidated) {
+            if self.inline.is_some() {
+                // Only the viewport is ours to clear.
+                let top = self.viewport().as_position();
+                self.inner.set_cursor_position(top)?;
+                self.inner.clear_region(ClearType::AfterCursor)?;
+                clear_after(&mut self.last_flushed, top);
+            } else {
+                self.inner.clear()?;
+                self.last_flushed = Buffer::empty(buf.area);
+            }
+        }
+
+        let sync = self.sync.filter(|_| match self.config.sync_output {
+            SyncOutput::Auto => self.sync_supported,
+            SyncOutput::Always => true,
+            SyncOutput::Never => false,
+        });
+
+        if let Some(mark) = sync {
+            mark(&mut self.inner, true)?;
+        }
+
+        let updates = updates::coalesced(&self.last_flushed, buf);
+        if t < 1.0 {
+            let bytes = u32::try_from(updates::estimated_bytes(&updates)).unwrap_or(u32::MAX);
+            self.frame_bytes = self.frame_bytes / 2 + bytes / 2;
+        }
+        self.inner.draw(updates.into_iter())?;
+        self.inner.set_cursor_position(cursor)?;
+        self.cursor_shown = cursor;
+
+        if t >= 1.0 && std::mem::take(&mut self.cursor_hidden) && self.cursor_visible {
+            self.inner.show_cursor()?;
+        }
+
+        if let Some(mark) = sync {
+            mark(&mut self.inner, false)?;
+        }
+
+        self.inner.flush()?;
+        self.last_flushed = buf.clone();
+
+        if let Some(sink) = self.sink.as_mut() {
+            let at = self.clock.now().saturating_duration_since(self.epoch);
+            sink.frame(t, buf, at)?;
+        }
+
+        Ok(())
+    }
+
+    /// Morph to `target` once the in-flight transition and any targets
+    /// queued before it have played, for guided A → B → C animations;
+    /// `overrides` set this step's duration, weights or easing. Starts
+    /// at once when idle.
+    ///
+    /// App flushes wait until the queue drains, then morph from its last
+    /// target to the app's frame, so draw the final step too.
+    pub fn queue(&mut self, target: Buffer, overrides: TransitionOverrides) -> io::Result<()> {
+        self.queue.push_back((target, overrides));
+
+        if std::mem::replace(&mut self.queue_playing, true) || self.playing.is_some() {
+            self.prefetch();
+            return Ok(());
+        }
+
+        self.play_queued()
+    }
+
+    /// Drop queued targets that have not started.
+    pub fn clear_queue(&mut self) {
+        self.queue.clear();
+        self.solving = None;
+    }
+
+    /// A queued target sized to the screen.
+    fn fitted(&self, target: &Buffer) -> Buffer {
+        let mut next = Buffer::empty(self.current_frame.area);
+        for pos in next.area.intersection(target.area).positions() {
+            next[pos] = target[pos].clone();
+        }
+        next
+    }
+
+    /// Start solving the head of the queue while the transition before it
+    /// plays, so back-to-back steps don't wait on the solver in turn.
+    fn prefetch(&mut self) {
+        if self.solving.is_some() || self.playing.is_none() || !self.morphing_enabled() {
+            return;
+        }
+        let (Some(prev), Some(&(ref target, overrides))) = (&self.prev_frame, self.queue.front())
+        else {
+            return;
+        };
+
+        let next = self.fitted(target);
+        let (prev, next, _) = self.solver_frames(prev, &next);
+        let (prev, next) = (prev.into_owned(), next.into_owned());
+
+        let undo = overrides.apply(&mut self.config);
+        let excluded = self.config.solver.exclude.len();
+        let areas = self.regions.iter().map(|(area, _)| *area);
+        self.config.solver.exclude.extend(areas);
+        self.solving = Some(Transition::spawn(prev, next, &self.config));
+        self.config.solver.exclude.truncate(excluded);
+        undo.apply(&mut self.config);
+    }
+
+    /// Start the next queued morph, snapping through any that can't morph;
+    /// once the queue is empty, play a flush that waited for it.
+    fn play_queued(&mut self) -> io::Result<()> {
+        while let Some((target, overrides)) = self.queue.pop_front() {
+            let next = self.fitted(&target);
+            let solved = self.solving.take();
+
+            let prev = self.prev_frame.replace(next.clone());
+            match prev {
+                Some(prev) if self.focus.is_focused() && self.morphing_enabled() => {
+                    self.run_transition(&prev, &next, Some(overrides), Step::Queued(solved))?;
+                }
+                _ => self.flush_buffer_to_inner(&next, 1.0)?,
+            }
+
+            if self.playing.is_some() {
+                self.prefetch();
+                return Ok(());
+            }
+        }
+
+        self.queue_playing = false;
+
+        if std::mem::take(&mut self.pending) {
+            Backend::flush(self)?;
+        }
+
+        Ok(())
+    }
+
+    /// Change duration, weights or easing for the next flush only, e.g. a
+    /// slow LIQUID morph when opening a view but CRISP tab switches.
+    /// Replaces any overrides not yet used.
+    pub fn next_transition(&mut self, overrides: TransitionOverrides) {
+        self.overrides = Some(overrides);
+    }
+
+    /// Follow a terminal resize. `Terminal` clears the screen and redraws
+    /// every cell after one, so the frame buffers restart blank; the
+    /// previous frame is cropped or padded so the next draw still morphs.
+    fn fit_to_terminal(&mut self) -> io::Result<()> {
+        let size = self.inner.size()?;
+        let area = Rect::new(0, 0, size.width, size.height);
+
+        if area == self.current_frame.area {
+            return Ok(());
+        }
+
+        if let Some(frame) = self.interrupt() {
+            self.prev_frame = Some(frame);
+        }
+
+        let anchor = self.config.solver.anchor;
+        self.prev_frame = self
+            .prev_frame
+            .take()
+            .map(|prev| solver::refit(&prev, area, anchor));
+        self.current_frame = Buffer::empty(area);
+        self.last_flushed = Buffer::empty(area);
+        self.invalidated = true;
+
+        Ok(())
+    }
+
+    /// A zero `fps`, a zero `duration` with fixed pacing, or
+    /// `MorphControl::set_enabled(false)` turns the backend into a
+    /// passthrough.
+    fn morphing_enabled(&self) -> bool {
+        let instant = self.config.pacing == Pacing::Fixed && self.config.duration.is_zero();
+        self.config.fps > 0 && !instant && self.control.is_enabled()
+    }
+
+    /// `config.fps`, lowered to keep in-between frames the size of this
+    /// transition's so far within `config.max_bytes_per_second`.
+    fn fps(&self) -> u32 {
+        match self.config.max_bytes_per_second {
+            Some(budget) if self.frame_bytes > 0 => self.config.fps.min(budget / self.frame_bytes),
+            _ => self.config.fps,
+        }
+    }
+
+    /// Whether `prev` → `next` is worth a morph, per `min_changed_cells`
+    /// and `max_changed_ratio`.
+    fn worth_morphing(&self, prev: &Buffer, next: &Buffer) -> bool {
+        if !self.morphing_enabled() {
+            return false;
+        }
+
+        let (min_cells, max_ratio) = (self.config.min_changed_cells, self.config.max_changed_ratio);
+
+        if min_cells == 0 && max_ratio.is_none() {
+            return true;
+        }
+
+        let changed = changed_cells(prev, next);
+        let ratio = changed as f32 / next.content.len().max(1) as f32;
+
+        changed >= min_cells && max_ratio.is_none_or(|max| ratio <= max)
+    }
+
+    /// Blank the screen, fading out with `fade_on_clear`. `Terminal` then
+    /// redraws every cell, morphing from the last frame the app drew (or
+    /// from blank after a fade).
+    fn clear_screen(&mut self) -> io::Result<()> {
+        let size = self.inner.size()?;
+        let resized = size != self.current_frame.area.as_size();
+        self.fit_to_terminal()?;
+
+        let blank = Buffer::empty(self.current_frame.area);
+        let on_screen = self.interrupt();
+        let fade = self.config.fade_on_clear
+            && !resized
+            && self.focus.is_focused()
+            && self.morphing_enabled();
+
+        if fade {
+            let from = on_screen.unwrap_or_else(|| self.last_flushed.clone());
+            self.run_transition(&from, &blank, None, Step::Fresh)?;
+            self.prev_frame = Some(blank.clone());
+        } else {
+            self.inner.clear()?;
+            self.last_flushed = blank.clone();
+        }
+
+        self.current_frame = blank;
+
+        Ok(())
+    }
+
+    /// Show the next frame without a morph, e.g. when leaving a splash
+    /// screen for an unrelated view.
+    pub fn skip_next(&mut self) {
+        self.skip_next = true;
+    }
+
+    /// `prev` and `next` as the solver sees them: cropped to an inline
+    /// viewport, with image placeholders masked out.
+    fn solver_frames<'a>(
+        &self,
+        prev: &'a Buffer,
+        next: &'a Buffer,
+    ) -> (Cow<'a, Buffer>, Cow<'a, Buffer>, Passthrough) {
+        let (prev, next) = if self.inline.is_some() {
+            let viewport = self.viewport();
+            (
+                Cow::Owned(crop(prev, viewport)),
+                Cow::Owned(crop(next, viewport)),
+            )
+        } else {
+            (Cow::Borrowed(prev), Cow::Borrowed(next))
+        };
+
+        let passthrough = Passthrough::detect(&prev, &next);
+        if passthrough.is_empty() {
+            return (prev, next, passthrough);
+        }
+
+        let masked = (passthrough.mask(&prev), passthrough.mask(&next));
+        (Cow::Owned(masked.0), Cow::Owned(masked.1), passthrough)
+    }
+
+    fn run_transition(
+        &mut self,
+        prev: &Buffer,
+        next: &Buffer,
+        overrides: Option<TransitionOverrides>,
+        step: Step<'_>,
+    ) -> io::Result<()> {
+        let (prev, next, passthrough) = self.solver_frames(prev, next);
+        let (prev, next) = (prev.as_ref(), next.as_ref());
+        let undo = overrides.map(|o| o.apply(&mut self.config));
+
+        let solve_start = Instant::now();
+        let excluded = self.config.solver.exclude.len();
+        let areas = self.regions.iter().map(|(area, _)| *area);
+        self.config.solver.exclude.extend(areas);
+        let transition = match step {
+            Step::Queued(Some(solved)) => {
+                // Solved on the worker: no stats to report.
+                self.scratch.stats = SolveStats::default();
+                solved.join(&self.config)
+            }
+            Step::Retarget(plan) => {
+                let plan = solver::update_in(
+                    plan,
+                    prev,
+                    next,
+                    &self.config.weights,
+                    &self.config.solver,
+                    &mut self.scratch,
+                );
+                Transition::from_plan(prev, next, plan, &self.config)
+            }
+            Step::Fresh | Step::Queued(None) => {
+                self.plans
+                    .transition(prev, next, &self.config, &mut self.scratch)
+            }
+        };
+        self.config.solver.exclude.truncate(excluded);
+        let stats = self.scratch.stats();
+
+        let mut regions = Vec::with_capacity(self.regions.len());
+        for (area, overrides) in self.regions.clone() {
+            let area = area.intersection(next.area);
+            let undo = overrides.apply(&mut self.config);
+            regions.push(Transition::new_in(
+                &crop(prev, area),
+                &crop(next, area),
+                &self.config,
+                &mut self.scratch,
+            ));
+            undo.apply(&mut self.config);
+        }
+        let solve_time = solve_start.elapsed();
+
+        if let Some(undo) = undo {
+            undo.apply(&mut self.config);
+        }
+
+        self.play(transition, regions, passthrough, solve_time, stats)
+    }
+
+    /// Animate a whole-palette theme switch of the current frame over
+    /// `duration`, bypassing the solver. The app's next draw should be the
+    /// recolored frame; any remaining differences morph normally. Recolors
+    /// at once while morphing is off or the terminal is unfocused.
+    pub fn theme_switch(&mut self, map: &ThemeMap, duration: Duration) -> io::Result<()> {
+        let Some(prev) = self.prev_frame.take() else {
+            return Ok(());
+        };
+
+        let passthrough = Passthrough::detect(&prev, &prev);
+
+        if duration.is_zero() || !self.focus.is_focused() || !self.morphing_enabled() {
+            let mut target = map.recolor(&passthrough.mask(&prev));
+            passthrough.restore(&mut target);
+            self.flush_buffer_to_inner(&target, 1.0)?;
+            self.prev_frame = Some(target);
+            return Ok(());
+        }
+
+        let solve_start = Instant::now();
+        let transition = map
+            .transition(&passthrough.mask(&prev), &self.config)
+            .with_duration(duration);
+        let solve_time = solve_start.elapsed();
+
+        let mut target = transition.target().clone();
+        passthrough.restore(&mut target);
+        self.prev_frame = Some(target);
+        self.play(
+            transition,
+            Vec::new(),
+            passthrough,
+            solve_time,
+            SolveStats::default(),
+        )
+    }
+
+    /// Flash `area` in `style` and fade it back to the current frame, to
+    /// draw attention to it. Runs over the configured duration even though
+    /// nothing has changed; a no-op when morphing is off.
+    pub fn pulse(&mut self, area: Rect, style: Style) -> io::Result<()> {
+        let Some(prev) = self.prev_frame.as_ref() else {
+            return Ok(());
+        };
+
+        if !self.morphing_enabled() {
+            return Ok(());
+        }
+
+        let passthrough = Passthrough::detect(prev, prev);
+        let target = passthrough.mask(prev);
+        let mut flash = target.clone();
+        flash.set_style(area.intersection(target.area), style);
+
+        let solve_start = Instant::now();
+        let transition = Transition::new_in(&flash, &target, &self.config, &mut self.scratch);
+        let solve_time = solve_start.elapsed();
+
+        let stats = self.scratch.stats();
+        self.play(transition, Vec::new(), passthrough, solve_time, stats)
+    }
+
+    /// Image placeholders in `passthrough` are left as they are on screen
+    /// until the final frame.
+    fn play(
+        &mut self,
+        transition: Transition,
+        regions: Vec<Transition>,
+        passthrough: Passthrough,
+        solve_time: Duration,
+        solve: SolveStats,
+    ) -> io::Result<()> {
+        for effect in &mut self.effects {
+            effect.reset();
+        }
+        self.frame_bytes = 0;
+
+        if self.config.cursor == CursorMotion::Hide && self.cursor_visible && !self.cursor_hidden {
+            self.inner.hide_cursor()?;
+            self.cursor_hidden = true;
+        }
+
+        let now = self.clock.now();
+        self.playing = Some(Playback {
+            cursor_from: self.cursor_shown,
+            transition,
+            regions,
+            passthrough,
+            solve_time,
+            solve,
+            start: now,
+            last_tick: now,
+            next_tick: now,
+            frames: 0,
+            frame_cost: Duration::ZERO,
+        });
+
+        if self.config.tick == TickMode::Manual {
+            self.advance(now)?;
+            return Ok(());
+        }
+
+        while self.advance(self.clock.now())? {
+            if let Some(wait) = self.next_frame_in() {
+                self.clock.sleep(wait);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Replace the time source, e.g. with a `ManualClock` in tests or a
+    /// game's fixed-timestep clock. Restarts sink timestamps and ambient
+    /// drift from the new clock's now.
+    pub fn set_clock(&mut self, clock: Box<dyn Clock>) {
+        self.epoch = clock.now();
+        self.clock = clock;
+    }
+
+    /// `advance` to the clock's now.
+    pub fn tick(&mut self) -> io::Result<bool> {
+        self.advance(self.clock.now())
+    }
+
+    /// Flush the in-flight transition's frame for `now`, if one is due.
+    /// Returns whether the transition is still running.
+    ///
+    /// Only needed with `TickMode::Manual`: call it from the app's event
+    /// loop, e.g. polling input with `next_frame_in` as the timeout.
+    pub fn advance(&mut self, now: Instant) -> io::Result<bool> {
+        let Some(mut playback) = self.playing.take() else {
+            return Ok(false);
+        };
+
+        let elapsed = now.saturating_duration_since(playback.start);
+        let duration = playback.duration();
+        // Land on the target now if another in-between frame would
+        // overrun the deadline.
+        let raw_t = if self.focus.is_focused()
+            && self.fps() > 0
+            && elapsed + playback.frame_cost < duration
+        {
+            elapsed.as_secs_f32() / duration.as_secs_f32()
+        } else {
+            1.0
+        };
+
+        if raw_t < 1.0 && playback.frames > 0 && now < playback.next_tick {
+            self.playing = Some(playback);
+            return Ok(true);
+        }
+
+        let mut interpolated = playback.frame_at(elapsed, raw_t >= 1.0);
+
+        if raw_t >= 1.0 {
+            playback.passthrough.restore(&mut interpolated);
+
+            let nth = self.metrics.transitions + 1;
+            if self
+                .config
+                .repaint_every
+                .is_some_and(|n| nth.is_multiple_of(u64::from(n)))
+            {
+                self.invalidated = true;
+            }
+        } else {
+            let delta = if playback.frames == 0 {
+                Duration::ZERO
+            } else {
+                now.saturating_duration_since(playback.last_tick)
+            };
+            playback.last_tick = now;
+
+            if self.config.quality.effects() {
+                for effect in &mut self.effects {
+                    effect.process(&mut interpolated, raw_t, delta);
+                }
+            }
+
+            if let Some(hook) = self.config.post_process.as_mut() {
+                hook(raw_t, &mut interpolated);
+            }
+
+            playback
+                .passthrough
+                .hold(&mut interpolated, &self.last_flushed);
+        }
+
+        let cursor = match self.config.cursor {
+            CursorMotion::Glide if self.config.quality.cursor_glide() => {
+                let (from, to) = (playback.cursor_from, self.cursor);
+                let t = playback.transition.progress(raw_t);
+                Position::new(lerp_pos(from.x, to.x, t), lerp_pos(from.y, to.y, t))
+            }
+            CursorMotion::Glide | CursorMotion::Hide | CursorMotion::Jump => self.cursor,
+        };
+
+        let interpolated = self.on_screen_with(interpolated);
+        self.flush_frame(&interpolated, raw_t, cursor)?;
+        playback.frames += 1;
+
+        let flushed = self.clock.now();
+        playback.frame_cost = flushed.saturating_duration_since(now);
+
+        if raw_t >= 1.0 {
+            self.finish(playback, now);
+
+            if self.queue_playing {
+                self.play_queued()?;
+            } else if std::mem::take(&mut self.pending) {
+                Backend::flush(self)?;
+            }
+
+            return Ok(self.is_animating());
+        }
+
+        let frame_interval = Duration::from_secs(1)
+            .checked_div(self.fps())
+            .unwrap_or_default();
+        // The first tick strictly after this frame finished flushing, on a
+        // grid from when this frame was due; ticks a slow flush overran are
+        // dropped. A low frame rate still lands on time.
+        let due = playback.next_tick;
+        let late = flushed.saturating_duration_since(due);
+        let ticks = late.as_nanos() / frame_interval.as_nanos().max(1) + 1;
+        let next = due + frame_interval * u32::try_from(ticks).unwrap_or(u32::MAX);
+        let end = playback.start.checked_add(duration);
+        playback.next_tick = end.map_or(next, |end| next.min(end));
+        self.playing = Some(playback);
+
+        Ok(true)
+    }
+
+    /// A transition is in flight.
+    pub fn is_animating(&self) -> bool {
+        self.playing.is_some()
+    }
+
+    /// Time until the in-flight transition's next frame is due; `None`
+    /// when idle.
+    pub fn next_frame_in(&self) -> Option<Duration> {
+        self.playing
+            .as_ref()
+            .map(|p| p.next_tick.saturating_duration_since(self.clock.now()))
+    }
+
+    /// Stop the in-flight transition, if any, returning its frame for now
+    /// (without effects) as the source for the next one.
+    fn interrupt(&mut self) -> Option<Buffer> {
+        let playback = self.playing.take()?;
+        let now = self.clock.now();
+        let elapsed = now.saturating_duration_since(playback.start);
+        let mut frame = playback.frame_at(elapsed, false);
+        playback.passthrough.restore(&mut frame);
+        self.finish(playback, now);
+
+        Some(self.on_screen_with(frame))
+    }
+
+    fn finish(&mut self, playback: Playback, now: Instant) {
+        let duration = playback.duration();
+        let Playback {
+            transition,
+            solve_time,
+            solve,
+            start,
+            frames,
+            ..
+        } = playback;
+        let expected = MorphMetrics::expected_frames(duration, self.fps());
+
+        self.metrics = MorphMetrics {
+            solve_time,
+            solve,
+            plan: transition.plan().counts(),
+            duration,
+            elapsed: now.saturating_duration_since(start),
+            target_fps: self.fps(),
+            frames,
+            dropped_frames: expected.saturating_sub(frames),
+            transitions: self.metrics.transitions + 1,
+        };
+        self.history
+            .record(self.config.history, self.metrics, transition.plan());
+        self.last_plan = Some(transition.into_plan());
+    }
+}
+
+impl<B: Backend + io::Write> MorphBackend<B> {
+    /// Frame every flush in synchronized-output markers, per
+    /// `config.sync_output`. For backends that pass raw bytes through to
+    /// the terminal, such as ratatui's `CrosstermBackend`:
+    ///
+    /// ```ignore
+    /// let morph = MorphBackend::new(CrosstermBackend::new(stdout()), config)?.synchronized();
+    /// let mut terminal = Terminal::new(morph)?;
+    /// ```
+    pub fn synchronized(mut self) -> Self {
+        self.sync = Some(|inner, begin| {
+            let mark: &[u8] = if begin {
+                b"\x1b[?2026h"
+            } else {
+                b"\x1b[?2026l"
+            };
+            inner.write_all(mark)
+        });
+        self.sync_supported = crate::config::supports_synchronized_output();
+        self
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<B: Backend> MorphBackend<B> {
+    /// `flush` that awaits between frames with `tokio::time::sleep`
+    /// instead of blocking the thread. Plays the whole transition
+    /// regardless of `config.tick`.
+    pub async fn flush_async(&mut self) -> io::Result<()> {
+        let tick = std::mem::replace(&mut self.config.tick, TickMode::Manual);
+        let flushed = Backend::flush(self);
+        self.config.tick = tick;
+        flushed?;
+
+        self.finish_async().await
+    }
+
+    /// Await the in-flight transition's remaining frames. With
+    /// `TickMode::Manual`, this is the async counterpart of a
+    /// `Terminal::draw` that blocks:
+    ///
+    /// ```ignore
+    /// terminal.draw(|f| app.render(f))?;
+    /// terminal.backend_mut().finish_async().await?;
+    /// ```
+    pub async fn finish_async(&mut self) -> io::Result<()> {
+        while self.tick()? {
+            if let Some(wait) = self.next_frame_in() {
+                tokio::time::sleep(wait).await;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Where `run_transition` gets its plan.
+enum Step<'a> {
+    /// Solved now, or taken from the plan cache.
+    Fresh,
+    /// Re-solved from an interrupted transition's plan.
+    Retarget(&'a InterpolationPlan),
+    /// A queued step, with its solve if `prefetch` started one.
+    Queued(Option<PendingTransition>),
+}
+
+/// A transition being played out, one `advance` at a time.
+struct Playback {
+    transition: Transition,
+    /// Per-region transitions, composited over `transition`.
+    regions: Vec<Transition>,
+    /// Where the cursor was when the transition started.
+    cursor_from: Position,
+    passthrough: Passthrough,
+    solve_time: Duration,
+    solve: SolveStats,
+    start: Instant,
+    last_tick: Instant,
+    next_tick: Instant,
+    frames: u32,
+    /// How long the last frame took to flush.
+    frame_cost: Duration,
+}
+
+impl Playback {
+    /// The slowest of the frame's and its regions' durations.
+    fn duration(&self) -> Duration {
+        self.regions
+            .iter()
+            .map(Transition::duration)
+            .fold(self.transition.duration(), Duration::max)
+    }
+
+    /// The frame `elapsed` in, each region at its own pace; all at their
+    /// targets once `done`.
+    fn frame_at(&self, elapsed: Duration, done: bool) -> Buffer {
+        let t = |transition: &Transition| {
+            let duration = transition.duration();
+
+            if done || duration.is_zero() {
+                1.0
+            } else {
+                (elapsed.as_secs_f32() / duration.as_secs_f32()).min(1.0)
+            }
+        };
+
+        let mut frame = self.transition.frame_at(t(&self.transition));
+        for region in &self.regions {
+            frame.merge(&region.frame_at(t(region)));
+        }
+
+        frame
+    }
+}
+
+impl<B: Backend> Backend for MorphBackend<B> {
+    fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a ratatui::buffer::Cell)>,
+    {
+        self.fit_to_terminal()?;
+
+        for (x, y, cell) in content {
+            if x < self.current_frame.area().width && y < self.current_frame.area().height {
+                self.current_frame[(x, y)] = cell.clone();
+                self.unframed = true;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.fit_to_terminal()?;
+
+        if let Some(changes) = self.control.take() {
+            changes.apply(&mut self.config);
+        }
+
+        let coalesce = self.config.coalesce;
+        let waits = self.queue_playing || coalesce == Coalesce::Latest && self.playing.is_some();
+        if waits && !self.unframed && !self.skip_next {
+            // `advance` morphs to the newest frame once this one lands.
+            self.pending = true;
+            return Ok(());
+        }
+
+        // A raw write or skipped morph cuts a queue short.
+        self.queue.clear();
+        self.solving = None;
+        self.queue_playing = false;
+        self.pending = false;
+        let deadline = self
+            .playing
+            .as_ref()
+            .filter(|_| coalesce == Coalesce::KeepDeadline)
+            .and_then(|p| p.start.checked_add(p.duration()));
+        let interrupted = self.interrupt();
+        let mut overrides = self.overrides.take();
+
+        if let Some(deadline) = deadline {
+            let frame_interval = Duration::from_secs(1)
+                .checked_div(self.fps())
+                .unwrap_or_default();
+            let remaining = deadline.saturating_duration_since(self.clock.now());
+            overrides = Some(
+                overrides
+                    .unwrap_or_default()
+                    .duration(remaining.max(frame_interval)),
+            );
+        }
+
+        let skip = std::mem::take(&mut self.skip_next);
+        let next = self.current_frame.clone();
+        let keys = self.keys.take();
+        let keyed = self.prev_keys.moves(&keys);
+        self.prev_keys = keys;
+
+        if std::mem::take(&mut self.unframed) {
+            // Raw write (e.g. history lines above an inline viewport):
+            // show it as-is and keep it out of the next morph.
+            self.flush_buffer_to_inner(&next, 1.0)?;
+            self.prev_frame = Some(next);
+            return Ok(());
+        }
+
+        // Retarget from wherever an interrupted transition had got to.
+        let retargeted = interrupted.is_some();
+        let prev = interrupted.or_else(|| self.prev_frame.take());
+
+        let morph =
+            |prev: &Buffer| !skip && self.focus.is_focused() && self.worth_morphing(prev, &next);
+
+        if let Some(prev) = prev.filter(morph) {
+            let configured = self.config.solver.keyed.len();
+            self.config.solver.keyed.extend(keyed);
+            // The interrupted transition's plan, to re-solve from.
+            let retarget = retargeted.then(|| self.last_plan.take()).flatten();
+            let step = retarget.as_ref().map_or(Step::Fresh, Step::Retarget);
+            let played = self.run_transition(&prev, &next, overrides, step);
+            self.last_plan = self.last_plan.take().or(retarget);
+            self.config.solver.keyed.truncate(configured);
+            played?;
+        } else {
+            self.flush_buffer_to_inner(&next, 1.0)?;
+        }
+
+        self.prev_frame = Some(next);
+
+        Ok(())
+    }
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        self.unframed = false;
+        self.cursor_visible = false;
+        self.inner.hide_cursor()
+    }
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        self.unframed = false;
+        self.cursor_visible = true;
+
+        if self.cursor_hidden {
+            // Shown when the transition that hid it ends.
+            return Ok(());
+        }
+
+        self.inner.show_cursor()
+    }
+
+    fn get_cursor_position(&mut self) -> io::Result<ratatui::layout::Position> {
+        self.inner.get_cursor_position()
+    }
+
+    fn set_cursor_position<P: Into<ratatui::layout::Position>>(
+        &mut self,
+        position: P,
+    ) -> io::Result<()> {
+        self.unframed = false;
+        self.cursor = position.into();
+        self.inner.set_cursor_position(self.cursor)
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        self.clear_screen()
+    }
+
+    fn clear_region(&mut self, clear_type: ClearType) -> io::Result<()> {
+        if clear_type == ClearType::All {
+            return self.clear_screen();
+        }
+
+        self.inner.clear_region(clear_type)?;
+
+        if clear_type == ClearType::AfterCursor {
+            // Inline viewports clear themselves after `insert_before`; what
+            // was there is gone, so the next frame is drawn without a morph.
+            let cursor = self.cursor;
+            if self.inline.is_some() {
+                self.viewport_top = cursor.y;
+            }
+            clear_after(&mut self.current_frame, cursor);
+            clear_after(&mut self.last_flushed, cursor);
+            self.prev_frame = None;
+        }
+
+        Ok(())
+    }
+
+    fn append_lines(&mut self, n: u16) -> io::Result<()> {
+        self.inner.append_lines(n)?;
+
+        // Appending at the bottom row scrolls the whole screen up.
+        scroll_up(&mut self.current_frame, n);
+        scroll_up(&mut self.last_flushed, n);
+
+        if let Some(prev) = self.prev_frame.as_mut() {
+            scroll_up(prev, n);
+        }
+
+        Ok(())
+    }
+
+    fn size(&self) -> io::Result<Size> {
+        self.inner.size()
+    }
+
+    fn window_size(&mut self) -> io::Result<ratatui::backend::WindowSize> {
+        self.inner.window_size()
+    }
+}
+
+/// Cells that differ between two frames; every cell of `next` when they
+/// differ in size.
+fn changed_cells(prev: &Buffer, next: &Buffer) -> usize {
+    if prev.area != next.area {
+        return next.content.len();
+    }
+
+    prev.content
+        .iter()
+        .zip(&next.content)
+        .filter(|(a, b)| a != b)
+        .count()
+}
+
+/// The part of `buf` inside `area`.
+fn crop(buf: &Buffer, area: Rect) -> Buffer {
+    let mut out = Buffer::empty(area.intersection(buf.area));
+
+    for pos in out.area.positions() {
+        out[pos] = buf[pos].clone();
+    }
+
+    out
+}
+
+fn lerp_pos(from: u16, to: u16, t: f32) -> u16 {
+    (f32::from(from) + (f32::from(to) - f32::from(from)) * t).round() as u16
+}
+
+/// Reset every cell from `from` to the end of the buffer, row-major.
+fn clear_after(buf: &mut Buffer, from: Position) {
+    let width = buf.area.width as usize;
+    let start = (from.y as usize * width + from.x as usize).min(buf.content.len());
+
+    for cell in &mut buf.content[start..] {
+        cell.reset();
+    }
+}
+
+/// Shift rows up by `n`, blanking the bottom.
+fn scroll_up(buf: &mut Buffer, n: u16) {
+    let shift = (n as usize * buf.area.width as usize).min(buf.content.len());
+    buf.content.drain(..shift);
+    buf.content
+        .resize(buf.area.area() as usize, Cell::default());
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell as StdCell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use ratatui::backend::TestBackend;
+    use ratatui::style::{Color, Stylize};
+    use ratatui::widgets::{Paragraph, Widget};
+
+    use crate::backend::MorphTerminalExt;
+    use crate::clock::ManualClock;
+    use crate::cost::MatchCell;
+    use crate::keys::Morphed;
+
+    use super::*;
+
+    fn quick_terminal() -> Terminal<MorphBackend<TestBackend>> {
+        let config = MorphConfig {
+            duration: Duration::from_millis(1),
+            ..MorphConfig::default()
+        };
+
+        MorphBackend::wrap(TestBackend::new(8, 2), config).unwrap()
+    }
+
+    #[test]
+    fn last_plan_reflects_latest_transition() {
+        let mut terminal = quick_terminal();
+
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("ab"), f.area()))
+            .unwrap();
+        assert!(terminal.backend().last_plan().is_none());
+
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("ax"), f.area()))
+            .unwrap();
+
+        let plan = terminal.backend().last_plan().unwrap();
+        assert_eq!(plan.mutating.len(), 1);
+        assert_eq!(plan.mutating[0].dst_symbol, "x");
+
+        assert!(terminal.backend_mut().take_last_plan().is_some());
+        assert!(terminal.backend().last_plan().is_none());
+    }
+
+    #[test]
+    fn effects_touch_only_intermediate_frames() {
+        let config = MorphConfig {
+            duration: Duration::from_millis(30),
+            ..MorphConfig::default()
+        };
+        let mut terminal = MorphBackend::wrap(TestBackend::new(8, 2), config).unwrap();
+        let calls = Rc::new(StdCell::new(0));
+        let counter = Rc::clone(&calls);
+
+        terminal.backend_mut().add_effect(Box::new(
+            move |frame: &mut Buffer, t: f32, _: Duration| {
+                assert!(t < 1.0);
+                counter.set(counter.get() + 1);
+                frame[(7, 1)].set_symbol("*");
+            },
+        ));
+
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("ab"), f.area()))
+            .unwrap();
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("ba"), f.area()))
+            .unwrap();
+
+        assert!(calls.get() > 0);
+        assert_eq!(terminal.backend().inner.buffer()[(7, 1)].symbol(), " ");
+    }
+
+    #[test]
+    fn ambient_tick_leaves_app_frame_alone() {
+        use crate::ambient::{Accent, Drift};
+        use ratatui::style::{Color, Style};
+
+        let accent = Color::Rgb(40, 120, 220);
+        let config = MorphConfig {
+            color: ColorMode::Full,
+            ..MorphConfig::default()
+        };
+        let mut terminal = MorphBackend::wrap(TestBackend::new(8, 2), config).unwrap();
+        let clock = ManualClock::new();
+        terminal.backend_mut().set_clock(Box::new(clock.clone()));
+        terminal.backend_mut().add_ambient(AmbientLayer::new(
+            Accent::Fg(accent),
+            Drift::HueRotate {
+                period: Duration::from_secs(1),
+            },
+        ));
+
+        terminal
+            .draw(|f| {
+                f.render_widget(
+                    Paragraph::new("ab").style(Style::new().fg(accent)),
+                    f.area(),
+                )
+            })
+            .unwrap();
+        clock.advance(Duration::from_millis(100));
+        terminal.backend_mut().ambient_tick().unwrap();
+
+        let backend = terminal.backend();
+        assert_ne!(backend.inner.buffer()[(0, 0)].fg, accent);
+        assert_eq!(backend.prev_frame.as_ref().unwrap()[(0, 0)].fg, accent);
+    }
+
+    #[test]
+    fn resume_morphs_from_snapshot() {
+        let mut terminal = quick_terminal();
+
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("ab"), f.area()))
+            .unwrap();
+        terminal.backend_mut().suspend();
+
+        terminal.backend_mut().inner.clear().unwrap();
+        terminal.clear().unwrap();
+        terminal.backend_mut().resume().unwrap();
+        assert_eq!(terminal.backend().inner.buffer()[(0, 0)].symbol(), "a");
+
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("b"), f.area()))
+            .unwrap();
+
+        let backend = terminal.backend();
+        assert_eq!(backend.metrics().transitions, 1);
+        assert_eq!(backend.inner.buffer()[(0, 0)].symbol(), "b");
+        assert_eq!(backend.inner.buffer()[(1, 0)].symbol(), " ");
+    }
+
+    #[test]
+    fn manual_tick_returns_from_flush() {
+        let config = MorphConfig {
+            duration: Duration::from_millis(30),
+            tick: TickMode::Manual,
+            ..MorphConfig::default()
+        };
+        let mut terminal = MorphBackend::wrap(TestBackend::new(8, 2), config).unwrap();
+
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("ab"), f.area()))
+            .unwrap();
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("ba"), f.area()))
+            .unwrap();
+
+        let backend = terminal.backend_mut();
+        assert!(backend.is_animating());
+        assert_eq!(backend.metrics().transitions, 0);
+
+        while backend.tick().unwrap() {
+            std::thread::sleep(backend.next_frame_in().unwrap_or_default());
+        }
+
+        assert_eq!(backend.metrics().transitions, 1);
+        assert!(backend.metrics().frames > 1);
+        assert_eq!(backend.inner.buffer()[(0, 0)].symbol(), "b");
+    }
+
+    #[test]
+    fn draw_mid_transition_retargets() {
+        let config = MorphConfig {
+            duration: Duration::from_millis(200),
+            tick: TickMode::Manual,
+            ..MorphConfig::default()
+        };
+        let mut terminal = MorphBackend::wrap(TestBackend::new(8, 1), config).unwrap();
+        let clock = ManualClock::new();
+        terminal.backend_mut().set_clock(Box::new(clock.clone()));
+
+        for text in ["a", "b"] {
+            terminal
+                .draw(|f| f.render_widget(Paragraph::new(text), f.area()))
+                .unwrap();
+        }
+
+        clock.advance(Duration::from_millis(20));
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("c"), f.area()))
+            .unwrap();
+
+        let backend = terminal.backend_mut();
+        assert_eq!(backend.metrics().transitions, 1);
+        assert_eq!(backend.metrics().elapsed, Duration::from_millis(20));
+        assert!(backend.is_animating());
+
+        while backend.tick().unwrap() {
+            clock.advance(backend.next_frame_in().unwrap_or_default());
+        }
+
+        assert_eq!(backend.metrics().transitions, 2);
+        assert_eq!(backend.inner.buffer()[(0, 0)].symbol(), "c");
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test(flavor = "current_thread")]
+    async fn finish_async_plays_to_the_end() {
+        let config = MorphConfig {
+            duration: Duration::from_millis(30),
+            tick: TickMode::Manual,
+            ..MorphConfig::default()
+        };
+        let mut terminal = MorphBackend::wrap(TestBackend::new(8, 1), config).unwrap();
+
+        for text in ["ab", "ba"] {
+            terminal
+                .draw(|f| f.render_widget(Paragraph::new(text), f.area()))
+                .unwrap();
+        }
+
+        let backend = terminal.backend_mut();
+        backend.finish_async().await.unwrap();
+
+        assert!(!backend.is_animating());
+        assert!(backend.metrics().frames > 1);
+        assert_eq!(backend.inner.buffer()[(0, 0)].symbol(), "b");
+    }
+
+    fn coalescing(coalesce: Coalesce) -> (Terminal<MorphBackend<TestBackend>>, ManualClock) {
+        let config = MorphConfig {
+            duration: Duration::from_millis(500),
+            tick: TickMode::Manual,
+            coalesce,
+            ..MorphConfig::default()
+        };
+        let mut terminal = MorphBackend::wrap(TestBackend::new(8, 1), config).unwrap();
+        let clock = ManualClock::new();
+        terminal.backend_mut().set_clock(Box::new(clock.clone()));
+
+        // Redraw at 10Hz, faster than the transitions play.
+        for text in ["a", "b", "c", "d"] {
+            terminal
+                .draw(|f| f.render_widget(Paragraph::new(text), f.area()))
+                .unwrap();
+            clock.advance(Duration::from_millis(100));
+            terminal.backend_mut().tick().unwrap();
+        }
+
+        (terminal, clock)
+    }
+
+    #[test]
+    fn keep_deadline_lands_on_schedule() {
+        let (mut terminal, clock) = coalescing(Coalesce::KeepDeadline);
+        let backend = terminal.backend_mut();
+
+        clock.advance(Duration::from_millis(200));
+        assert!(!backend.tick().unwrap());
+        assert_eq!(backend.inner.buffer()[(0, 0)].symbol(), "d");
+        assert_eq!(clock.elapsed(), Duration::from_millis(600));
+    }
+
+    #[test]
+    fn latest_drops_intermediate_frames() {
+        let (mut terminal, clock) = coalescing(Coalesce::Latest);
+        let backend = terminal.backend_mut();
+
+        // "a" → "b" lands at 600ms and hands straight over to "b" → "d".
+        clock.advance(Duration::from_millis(200));
+        assert!(backend.tick().unwrap());
+        assert_eq!(backend.metrics().transitions, 1);
+
+        clock.advance(Duration::from_millis(500));
+        assert!(!backend.tick().unwrap());
+        assert_eq!(backend.metrics().transitions, 2);
+        assert_eq!(backend.inner.buffer()[(0, 0)].symbol(), "d");
+    }
+
+    #[test]
+    fn regions_keep_their_own_pace() {
+        let config = MorphConfig {
+            duration: Duration::from_millis(100),
+            easing: crate::easing::linear,
+            tick: TickMode::Manual,
+            ..MorphConfig::default()
+        };
+        let mut terminal = MorphBackend::wrap(TestBackend::new(8, 1), config).unwrap();
+        let clock = ManualClock::new();
+        let backend = terminal.backend_mut();
+        backend.set_clock(Box::new(clock.clone()));
+        backend.add_region(
+            Rect::new(4, 0, 4, 1),
+            TransitionOverrides::default().duration(Duration::from_millis(400)),
+        );
+
+        for text in ["a   a", "b   b"] {
+            terminal
+                .draw(|f| f.render_widget(Paragraph::new(text), f.area()))
+                .unwrap();
+        }
+
+        let backend = terminal.backend_mut();
+        clock.advance(Duration::from_millis(150));
+        assert!(backend.tick().unwrap());
+        assert_eq!(backend.inner.buffer()[(0, 0)].symbol(), "b");
+        assert_eq!(backend.inner.buffer()[(4, 0)].symbol(), "a");
+
+        clock.advance(Duration::from_millis(250));
+        assert!(!backend.tick().unwrap());
+        assert_eq!(backend.inner.buffer()[(4, 0)].symbol(), "b");
+    }
+
+    #[test]
+    fn keyed_entries_slide_when_reordered() {
+        let config = MorphConfig {
+            duration: Duration::from_millis(1),
+            weights: crate::weights::MorphWeights::CRISP,
+            ..MorphConfig::default()
+        };
+        let mut terminal = MorphBackend::wrap(TestBackend::new(4, 2), config).unwrap();
+        let keys = terminal.backend().keys();
+
+        for items in [["abc", "xyz"], ["xyz", "abc"]] {
+            terminal
+                .draw(|f| {
+                    for (y, item) in (0..).zip(items) {
+                        let area = Rect::new(0, y, 4, 1);
+                        keys.insert(item, area);
+                        f.render_widget(Paragraph::new(item), area);
+                    }
+                })
+                .unwrap();
+        }
+
+        let plan = terminal.backend().last_plan().unwrap();
+        assert_eq!(plan.displaced.len(), 6);
+        assert!(plan.displaced.iter().all(|c| c.src_symbol == c.dst_symbol));
+    }
+
+    #[test]
+    fn morphed_widgets_register_their_keys() {
+        let config = MorphConfig {
+            duration: Duration::from_millis(1),
+            weights: crate::weights::MorphWeights::CRISP,
+            ..MorphConfig::default()
+        };
+        let mut terminal = MorphBackend::wrap(TestBackend::new(4, 2), config).unwrap();
+
+        for items in [["abc", "xyz"], ["xyz", "abc"]] {
+            terminal
+                .draw(|f| {
+                    for (y, item) in (0..).zip(items) {
+                        let entry = Morphed::new(item, Paragraph::new(item));
+                        f.render_widget(entry, Rect::new(0, y, 4, 1));
+                    }
+                })
+                .unwrap();
+        }
+
+        let plan = terminal.backend().last_plan().unwrap();
+        assert_eq!(plan.displaced.len(), 6);
+        assert!(plan.displaced.iter().all(|c| c.src_y != c.dst_y));
+    }
+
+    #[test]
+    fn pulse_flashes_an_unchanged_frame() {
+        let config = MorphConfig {
+            duration: Duration::from_millis(100),
+            tick: TickMode::Manual,
+            ..MorphConfig::default()
+        };
+        let mut terminal = MorphBackend::wrap(TestBackend::new(4, 1), config).unwrap();
+        let clock = ManualClock::new();
+        terminal.backend_mut().set_clock(Box::new(clock.clone()));
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("ab"), f.area()))
+            .unwrap();
+
+        let backend = terminal.backend_mut();
+        let red = Style::new().bg(Color::Red);
+        backend.pulse(Rect::new(0, 0, 1, 1), red).unwrap();
+        assert!(backend.is_animating());
+        assert_eq!(backend.inner.buffer()[(0, 0)].bg, Color::Red);
+        assert_eq!(backend.inner.buffer()[(1, 0)].bg, Color::Reset);
+
+        clock.advance(Duration::from_millis(100));
+        assert!(!backend.tick().unwrap());
+        assert_eq!(backend.inner.buffer()[(0, 0)].bg, Color::Reset);
+        assert_eq!(backend.inner.buffer()[(0, 0)].symbol(), "a");
+    }
+
+    /// A terminal that takes 40ms to show each frame.
+    struct Slow(ManualClock);
+
+    impl FrameSink for Slow {
+        fn frame(&mut self, _: f32, _: &Buffer, _: Duration) -> io::Result<()> {
+            self.0.advance(Duration::from_millis(40));
+            Ok(())
+        }
+
+        fn finish(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn slow_frames_are_dropped_to_keep_the_duration() {
+        let config = MorphConfig {
+            duration: Duration::from_millis(100),
+            ..MorphConfig::default()
+        };
+        let mut terminal = MorphBackend::wrap(TestBackend::new(4, 1), config).unwrap();
+        let clock = ManualClock::new();
+        let backend = terminal.backend_mut();
+        backend.set_clock(Box::new(clock.clone()));
+        backend.set_sink(Box::new(Slow(clock.clone())));
+
+        for text in ["ab", "ba"] {
+            terminal
+                .draw(|f| f.render_widget(Paragraph::new(text), f.area()))
+                .unwrap();
+        }
+
+        // The first draw takes 40ms, then frames go out at 0ms, 50ms (the
+        // ticks the first frame overran are dropped) and 100ms: the target
+        // lands on time rather than a frame behind.
+        assert_eq!(terminal.backend().metrics().frames, 3);
+        assert!(clock.elapsed() <= Duration::from_millis(180));
+    }
+
+    #[test]
+    fn bandwidth_budget_lowers_the_frame_rate() {
+        for (budget, frames) in [(None, 7), (Some(400), 4), (Some(10), 3)] {
+            let config = MorphConfig {
+                duration: Duration::from_millis(100),
+                fps: 60,
+                max_bytes_per_second: budget,
+                ..MorphConfig::default()
+            };
+            let mut terminal = MorphBackend::wrap(TestBackend::new(8, 1), config).unwrap();
+            terminal
+                .backend_mut()
+                .set_clock(Box::new(ManualClock::new()));
+
+            for color in [Color::Rgb(255, 0, 0), Color::Rgb(0, 0, 255)] {
+                let text = Paragraph::new("abcdefgh").fg(color);
+                terminal.draw(|f| f.render_widget(text, f.area())).unwrap();
+            }
+
+            let metrics = terminal.backend().metrics();
+            assert_eq!(metrics.frames, frames, "{budget:?}");
+            assert_eq!(
+                terminal.backend().inner.buffer()[(0, 0)].fg,
+                Color::Rgb(0, 0, 255)
+            );
+        }
+    }
+
+    #[test]
+    fn bandwidth_budget_recovers_after_a_large_transition() {
+        let config = MorphConfig {
+            duration: Duration::from_secs(1),
+            max_bytes_per_second: Some(60),
+            ..MorphConfig::default()
+        };
+        let mut terminal = MorphBackend::wrap(TestBackend::new(80, 1), config).unwrap();
+        terminal
+            .backend_mut()
+            .set_clock(Box::new(ManualClock::new()));
+
+        // A distinct color per cell, so no two cells share an escape.
+        let gradient = |blue: bool| {
+            move |f: &mut ratatui::Frame| {
+                for x in 0..80u8 {
+                    let level = x * 3;
+                    let color = if blue {
+                        Color::Rgb(0, level, 255 - level)
+                    } else {
+                        Color::Rgb(255 - level, level, 0)
+                    };
+                    f.buffer_mut()[(u16::from(x), 0)]
+                        .set_symbol("x")
+                        .set_fg(color);
+                }
+            }
+        };
+        terminal.draw(gradient(false)).unwrap();
+        terminal.draw(gradient(true)).unwrap();
+        assert_eq!(terminal.backend().metrics().target_fps, 0);
+
+        for symbol in ["a", "b"] {
+            terminal
+                .draw(|f| {
+                    gradient(true)(f);
+                    f.buffer_mut()[(0, 0)].set_symbol(symbol);
+                })
+                .unwrap();
+
+            let metrics = terminal.backend().metrics();
+            assert!(metrics.frames > 1, "{symbol}: {metrics:?}");
+        }
+    }
+
+    #[test]
+    fn queued_targets_play_back_to_back() {
+        let config = MorphConfig {
+            duration: Duration::from_millis(100),
+            tick: TickMode::Manual,
+            ..MorphConfig::default()
+        };
+        let mut terminal = MorphBackend::wrap(TestBackend::new(4, 1), config).unwrap();
+        let clock = ManualClock::new();
+        terminal.backend_mut().set_clock(Box::new(clock.clone()));
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("a"), f.area()))
+            .unwrap();
+
+        let backend = terminal.backend_mut();
+        let slow = TransitionOverrides::default().duration(Duration::from_millis(200));
+        backend
+            .queue(Buffer::with_lines(["b   "]), TransitionOverrides::default())
+            .unwrap();
+        backend.queue(Buffer::with_lines(["c   "]), slow).unwrap();
+        assert!(backend.is_animating());
+
+        clock.advance(Duration::from_millis(100));
+        assert!(backend.tick().unwrap());
+        assert_eq!(backend.inner.buffer()[(0, 0)].symbol(), "b");
+
+        // The app's frame waits for the queue, then morphs back from "c".
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("a"), f.area()))
+            .unwrap();
+        let backend = terminal.backend_mut();
+        clock.advance(Duration::from_millis(200));
+        assert!(backend.tick().unwrap());
+        assert_eq!(backend.inner.buffer()[(0, 0)].symbol(), "c");
+        assert_eq!(backend.metrics().transitions, 2);
+
+        clock.advance(Duration::from_millis(100));
+        assert!(!backend.tick().unwrap());
+        assert_eq!(backend.inner.buffer()[(0, 0)].symbol(), "a");
+    }
+
+    #[test]
+    fn queued_step_solves_while_previous_plays() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let mut config = MorphConfig {
+            duration: Duration::from_secs(1),
+            tick: TickMode::Manual,
+            ..MorphConfig::default()
+        };
+        config.solver.cost = Some(Arc::new(move |_: &MatchCell, _: &MatchCell, base| {
+            counter.fetch_add(1, Ordering::Relaxed);
+            base
+        }));
+        config.solver.words = false;
+        config.solver.components = false;
+        let mut terminal = MorphBackend::wrap(TestBackend::new(4, 1), config).unwrap();
+        let clock = ManualClock::new();
+        terminal.backend_mut().set_clock(Box::new(clock.clone()));
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("a"), f.area()))
+            .unwrap();
+
+        let backend = terminal.backend_mut();
+        let none = TransitionOverrides::default();
+        backend.queue(Buffer::with_lines(["   a"]), none).unwrap();
+        let first = calls.load(Ordering::Relaxed);
+        backend.queue(Buffer::with_lines(["a   "]), none).unwrap();
+
+        while !backend.solving.as_ref().unwrap().is_finished() {
+            std::thread::yield_now();
+        }
+        let second = calls.load(Ordering::Relaxed);
+        assert!(second > first);
+        assert!(backend.is_animating());
+
+        clock.advance(Duration::from_secs(1));
+        assert!(backend.tick().unwrap());
+        assert!(backend.solving.is_none());
+        assert_eq!(backend.metrics().transitions, 1);
+        assert_eq!(calls.load(Ordering::Relaxed), second);
+    }
+
+    #[test]
+    fn cursor_glides_then_hides() {
+        for (motion, midway, hidden) in [
+            (CursorMotion::Glide, Position::new(3, 0), false),
+            (CursorMotion::Hide, Position::new(6, 0), true),
+        ] {
+            let config = MorphConfig {
+                duration: Duration::from_millis(100),
+                easing: crate::easing::linear,
+                tick: TickMode::Manual,
+                cursor: motion,
+                ..MorphConfig::default()
+            };
+            let mut terminal = MorphBackend::wrap(TestBackend::new(8, 1), config).unwrap();
+            let clock = ManualClock::new();
+            terminal.backend_mut().set_clock(Box::new(clock.clone()));
+
+            for (text, x) in [("ab", 0), ("ba", 6)] {
+                terminal
+                    .draw(|f| {
+                        f.render_widget(Paragraph::new(text), f.area());
+                        f.set_cursor_position((x, 0));
+                    })
+                    .unwrap();
+            }
+
+            let backend = terminal.backend_mut();
+            clock.advance(Duration::from_millis(50));
+            backend.tick().unwrap();
+            assert_eq!(backend.inner.get_cursor_position().unwrap(), midway);
+            assert_eq!(backend.cursor_hidden, hidden);
+
+            clock.advance(Duration::from_millis(50));
+            backend.tick().unwrap();
+            assert_eq!(
+                backend.inner.get_cursor_position().unwrap(),
+                Position::new(6, 0)
+            );
+            assert!(!backend.cursor_hidden);
+        }
+    }
+
+    #[test]
+    fn manual_clock_drives_playback() {
+        let config = MorphConfig {
+            duration: Duration::from_secs(10),
+            fps: 10,
+            ..MorphConfig::default()
+        };
+        let mut terminal = MorphBackend::wrap(TestBackend::new(8, 1), config).unwrap();
+        let clock = ManualClock::new();
+        terminal.backend_mut().set_clock(Box::new(clock.clone()));
+
+        for text in ["ab", "ba"] {
+            terminal
+                .draw(|f| f.render_widget(Paragraph::new(text), f.area()))
+                .unwrap();
+        }
+
+        let metrics = terminal.backend().metrics();
+        assert_eq!(metrics.frames, 101);
+        assert_eq!(metrics.elapsed, Duration::from_secs(10));
+        assert_eq!(clock.elapsed(), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn overrides_apply_to_one_draw() {
+        let config = MorphConfig {
+            duration: Duration::from_millis(1),
+            ..MorphConfig::default()
+        };
+        let mut terminal = MorphBackend::wrap(TestBackend::new(8, 1), config).unwrap();
+        terminal
+            .backend_mut()
+            .set_clock(Box::new(ManualClock::new()));
+        let slow = TransitionOverrides::default().duration(Duration::from_millis(600));
+
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("a"), f.area()))
+            .unwrap();
+        terminal
+            .draw_with(slow, |f| f.render_widget(Paragraph::new("b"), f.area()))
+            .unwrap();
+        assert_eq!(terminal.backend().metrics().duration.as_millis(), 600);
+
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("c"), f.area()))
+            .unwrap();
+        assert_eq!(terminal.backend().metrics().duration.as_millis(), 1);
+        assert_eq!(terminal.backend().config().duration.as_millis(), 1);
+    }
+
+    #[test]
+    fn skip_next_cuts_once() {
+        let mut terminal = quick_terminal();
+
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("a"), f.area()))
+            .unwrap();
+        terminal.backend_mut().skip_next();
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("b"), f.area()))
+            .unwrap();
+        assert_eq!(terminal.backend().metrics().transitions, 0);
+
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("c"), f.area()))
+            .unwrap();
+        assert_eq!(terminal.backend().metrics().transitions, 1);
+    }
+
+    #[test]
+    fn resize_reallocates_and_morphs() {
+        let mut terminal = quick_terminal();
+
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("ab"), f.area()))
+            .unwrap();
+        terminal.backend_mut().inner.resize(12, 3);
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("abcdefghijk"), f.area()))
+            .unwrap();
+
+        let backend = terminal.backend();
+        assert_eq!(backend.current_frame.area, Rect::new(0, 0, 12, 3));
+        assert_eq!(backend.metrics().transitions, 1);
+        assert_eq!(backend.inner.buffer()[(10, 0)].symbol(), "k");
+
+        terminal.backend_mut().inner.resize(4, 1);
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("xy"), f.area()))
+            .unwrap();
+        assert_eq!(terminal.backend().inner.buffer()[(1, 0)].symbol(), "y");
+    }
+
+    #[test]
+    fn unfocused_transitions_snap() {
+        let config = MorphConfig {
+            duration: Duration::from_secs(10),
+            ..MorphConfig::default()
+        };
+        let mut terminal = MorphBackend::wrap(TestBackend::new(8, 2), config).unwrap();
+        terminal.backend().set_focused(false);
+
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("ab"), f.area()))
+            .unwrap();
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("ba"), f.area()))
+            .unwrap();
+
+        assert_eq!(terminal.backend().metrics().transitions, 0);
+        assert_eq!(terminal.backend().inner.buffer()[(0, 0)].symbol(), "b");
+    }
+
+    #[test]
+    fn post_process_sees_every_intermediate_frame() {
+        let seen = Rc::new(StdCell::new(0));
+        let counter = Rc::clone(&seen);
+        let config = MorphConfig::builder()
+            .duration(Duration::from_millis(30))
+            .post_process(move |t, _| {
+                assert!(t < 1.0);
+                counter.set(counter.get() + 1);
+            })
+            .build()
+            .unwrap();
+        let mut terminal = MorphBackend::wrap(TestBackend::new(8, 2), config).unwrap();
+
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("ab"), f.area()))
+            .unwrap();
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("ba"), f.area()))
+            .unwrap();
+
+        let frames = terminal.backend().metrics().frames;
+        assert_eq!(seen.get(), frames - 1);
+    }
+
+    #[test]
+    fn into_inner_returns_the_screen() {
+        let mut morph = MorphBackend::new(TestBackend::new(8, 1), MorphConfig::default()).unwrap();
+        let cell = Cell::new("x");
+
+        morph.draw([(0, 0, &cell)].into_iter()).unwrap();
+        Backend::flush(&mut morph).unwrap();
+
+        let inner = morph.into_inner();
+        assert_eq!(inner.buffer()[(0, 0)].symbol(), "x");
+    }
+
+    /// `TestBackend` that logs raw writes and draws in order.
+    struct Raw(TestBackend, Vec<String>);
+
+    impl io::Write for Raw {
+        fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+            self.1.push(String::from_utf8_lossy(bytes).into_owned());
+            Ok(bytes.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Backend for Raw {
+        fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+        where
+            I: Iterator<Item = (u16, u16, &'a Cell)>,
+        {
+            self.1.push("draw".into());
+            self.0.draw(content)
+        }
+
+        fn hide_cursor(&mut self) -> io::Result<()> {
+            self.0.hide_cursor()
+        }
+
+        fn show_cursor(&mut self) -> io::Result<()> {
+            self.0.show_cursor()
+        }
+
+        fn get_cursor_position(&mut self) -> io::Result<Position> {
+            self.0.get_cursor_position()
+        }
+
+        fn set_cursor_position<P: Into<Position>>(&mut self, position: P) -> io::Result<()> {
+            self.0.set_cursor_position(position)
+        }
+
+        fn clear(&mut self) -> io::Result<()> {
+            self.0.clear()
+        }
+
+        fn size(&self) -> io::Result<Size> {
+            self.0.size()
+        }
+
+        fn window_size(&mut self) -> io::Result<ratatui::backend::WindowSize> {
+            self.0.window_size()
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            self.0.flush()
+        }
+    }
+
+    #[test]
+    fn synchronized_frames_each_flush() {
+        let config = MorphConfig {
+            duration: Duration::from_millis(1),
+            sync_output: SyncOutput::Always,
+            ..MorphConfig::default()
+        };
+        let morph = MorphBackend::new(Raw(TestBackend::new(8, 1), Vec::new()), config)
+            .unwrap()
+            .synchronized();
+        let mut terminal = Terminal::new(morph).unwrap();
+
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("a"), f.area()))
+            .unwrap();
+
+        let log = &terminal.backend().inner().1;
+        assert_eq!(log, &["\x1b[?2026h", "draw", "\x1b[?2026l"]);
+
+        terminal.backend_mut().config_mut().sync_output = SyncOutput::Never;
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("b"), f.area()))
+            .unwrap();
+        assert!(
+            terminal.backend().inner().1[3..]
+                .iter()
+                .all(|e| e == "draw")
+        );
+    }
+
+    #[test]
+    fn wholesale_changes_snap() {
+        let config = MorphConfig {
+            max_changed_ratio: Some(0.5),
+            ..MorphConfig::default()
+        };
+        let mut terminal = MorphBackend::wrap(TestBackend::new(4, 1), config).unwrap();
+
+        for text in ["aaaa", "abbb", "abcc"] {
+            terminal
+                .draw(|f| f.render_widget(Paragraph::new(text), f.area()))
+                .unwrap();
+        }
+
+        // "aaaa" → "abbb" changes 3/4 cells and snaps; "abbb" → "abcc" morphs.
+        assert_eq!(terminal.backend().metrics().transitions, 1);
+        assert_eq!(terminal.backend().inner.buffer()[(3, 0)].symbol(), "c");
+    }
+
+    #[test]
+    fn tiny_changes_snap() {
+        let config = MorphConfig {
+            min_changed_cells: 2,
+            ..MorphConfig::default()
+        };
+        let mut terminal = MorphBackend::wrap(TestBackend::new(5, 1), config).unwrap();
+
+        for text in ["12:00", "12:01", "12:10"] {
+            terminal
+                .draw(|f| f.render_widget(Paragraph::new(text), f.area()))
+                .unwrap();
+        }
+
+        assert_eq!(terminal.backend().metrics().transitions, 1);
+        assert_eq!(terminal.backend().inner.buffer()[(4, 0)].symbol(), "0");
+    }
+
+    #[test]
+    fn resized_frames_count_as_all_changed() {
+        let prev = Buffer::with_lines(["ab", "cd"]);
+        let next = Buffer::with_lines(["abcd"]);
+        assert_eq!(changed_cells(&prev, &next), 4);
+        assert_eq!(changed_cells(&prev, &prev), 0);
+    }
+
+    #[test]
+    fn zero_duration_or_fps_passes_through() {
+        let configs = [
+            MorphConfig {
+                duration: Duration::ZERO,
+                ..MorphConfig::default()
+            },
+            MorphConfig {
+                fps: 0,
+                ..MorphConfig::default()
+            },
+        ];
+
+        for config in configs {
+            let mut terminal = MorphBackend::wrap(TestBackend::new(4, 1), config).unwrap();
+
+            for text in ["ab", "ba"] {
+                terminal
+                    .draw(|f| f.render_widget(Paragraph::new(text), f.area()))
+                    .unwrap();
+            }
+
+            let map = ThemeMap::new().map(Color::Reset, Color::Red);
+            let duration = terminal.backend().config().duration;
+            terminal.backend_mut().theme_switch(&map, duration).unwrap();
+
+            let backend = terminal.backend();
+            assert_eq!(backend.metrics().transitions, 0);
+            assert_eq!(backend.inner.buffer()[(0, 0)].symbol(), "b");
+        }
+    }
+
+    #[test]
+    fn disabled_theme_switch_recolors_at_once() {
+        let config = MorphConfig {
+            tick: TickMode::Manual,
+            ..MorphConfig::default()
+        };
+        let mut terminal = MorphBackend::wrap(TestBackend::new(4, 1), config).unwrap();
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("ab"), f.area()))
+            .unwrap();
+
+        let backend = terminal.backend_mut();
+        backend.control().set_enabled(false);
+        let map = ThemeMap::new().map(Color::Reset, Color::Red);
+        backend
+            .theme_switch(&map, Duration::from_millis(200))
+            .unwrap();
+
+        assert!(!backend.is_animating());
+        assert_eq!(backend.metrics().transitions, 0);
+        assert_eq!(backend.inner.buffer()[(0, 0)].fg, Color::Red);
+    }
+
+    #[test]
+    fn control_tunes_from_another_thread() {
+        let mut terminal = quick_terminal();
+        let control = terminal.backend().control();
+
+        std::thread::spawn(move || {
+            control.set_duration(Duration::from_millis(2));
+            control.set_enabled(false);
+        })
+        .join()
+        .unwrap();
+
+        for text in ["a", "b"] {
+            terminal
+                .draw(|f| f.render_widget(Paragraph::new(text), f.area()))
+                .unwrap();
+        }
+
+        let backend = terminal.backend_mut();
+        assert_eq!(backend.config().duration, Duration::from_millis(2));
+        assert_eq!(backend.metrics().transitions, 0);
+
+        backend.control().set_enabled(true);
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("c"), f.area()))
+            .unwrap();
+        assert_eq!(terminal.backend().metrics().transitions, 1);
+    }
+
+    #[test]
+    fn clear_repaints_the_next_frame() {
+        let mut terminal = quick_terminal();
+
+        for _ in 0..2 {
+            terminal
+                .draw(|f| f.render_widget(Paragraph::new("ab"), f.area()))
+                .unwrap();
+            terminal.clear().unwrap();
+        }
+
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("ab"), f.area()))
+            .unwrap();
+        assert_eq!(terminal.backend().inner.buffer()[(1, 0)].symbol(), "b");
+    }
+
+    #[test]
+    fn fade_on_clear_morphs_to_blank() {
+        let config = MorphConfig {
+            duration: Duration::from_millis(1),
+            fade_on_clear: true,
+            ..MorphConfig::default()
+        };
+        let mut terminal = MorphBackend::wrap(TestBackend::new(4, 1), config).unwrap();
+
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("ab"), f.area()))
+            .unwrap();
+        terminal.clear().unwrap();
+
+        let backend = terminal.backend();
+        assert_eq!(backend.metrics().transitions, 1);
+        assert_eq!(backend.metrics().plan.disappearing, 2);
+        assert_eq!(backend.inner.buffer()[(0, 0)].symbol(), " ");
+    }
+
+    #[test]
+    fn invalidate_repaints_everything() {
+        let mut terminal = quick_terminal();
+
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("ab"), f.area()))
+            .unwrap();
+
+        // Something else scribbles on the terminal behind our back.
+        let stray = Cell::new("!");
+        let backend = terminal.backend_mut();
+        backend.inner.draw([(0, 1, &stray)].into_iter()).unwrap();
+        backend.invalidate();
+
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("ab"), f.area()))
+            .unwrap();
+
+        assert_eq!(terminal.backend().inner.buffer()[(0, 1)].symbol(), " ");
+    }
+
+    #[test]
+    fn insert_before_keeps_history_and_viewport() {
+        let config = MorphConfig {
+            duration: Duration::from_millis(1),
+            ..MorphConfig::default()
+        };
+        let morph = MorphBackend::new(TestBackend::new(8, 4), config).unwrap();
+        let options = TerminalOptions {
+            viewport: Viewport::Inline(2),
+        };
+        let mut terminal = Terminal::with_options(morph, options).unwrap();
+
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("prompt"), f.area()))
+            .unwrap();
+
+        for line in ["log 1", "log 2", "log 3"] {
+            terminal
+                .insert_before(1, |buf| Paragraph::new(line).render(buf.area, buf))
+                .unwrap();
+            terminal
+                .draw(|f| f.render_widget(Paragraph::new("prompt"), f.area()))
+                .unwrap();
+        }
+
+        let screen = terminal.backend().inner.buffer().clone();
+        let rows: Vec<String> = (0..4)
+            .map(|y| (0..8).map(|x| screen[(x, y)].symbol()).collect())
+            .collect();
+
+        assert_eq!(rows, ["log 2   ", "log 3   ", "prompt  ", "        "]);
+    }
+
+    #[test]
+    fn inline_morphs_stay_in_the_viewport() {
+        let config = MorphConfig {
+            duration: Duration::from_millis(1),
+            ..MorphConfig::default()
+        };
+        let mut terminal = MorphBackend::wrap_inline(TestBackend::new(8, 4), config, 2).unwrap();
+
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("one"), f.area()))
+            .unwrap();
+        terminal
+            .insert_before(1, |buf| Paragraph::new("log").render(buf.area, buf))
+            .unwrap();
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("two"), f.area()))
+            .unwrap();
+
+        terminal.backend_mut().invalidate();
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("three"), f.area()))
+            .unwrap();
+
+        let viewport = terminal.get_frame().area();
+        let backend = terminal.backend();
+        assert_eq!(backend.last_plan().map(|p| p.area()), Some(viewport));
+
+        let screen = backend.inner.buffer();
+        let rows: Vec<String> = (0..4)
+            .map(|y| (0..8).map(|x| screen[(x, y)].symbol()).collect())
+            .collect();
+        assert_eq!(rows[usize::from(viewport.y) - 1], "log     ");
+        assert_eq!(rows[usize::from(viewport.y)], "three   ");
+    }
+
+    #[test]
+    fn image_placeholders_pass_through() {
+        let mut terminal = quick_terminal();
+        let kitty = "\u{10EEEE}\u{305}\u{305}";
+
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("ab"), f.area()))
+            .unwrap();
+        terminal
+            .draw(|f| {
+                f.render_widget(Paragraph::new("ba"), f.area());
+                f.buffer_mut()[(4, 0)].set_symbol(kitty);
+            })
+            .unwrap();
+
+        let plan = terminal.backend().last_plan().unwrap();
+        assert!(plan.appearing.iter().all(|cell| cell.x != 4));
+        assert_eq!(terminal.backend().inner.buffer()[(4, 0)].symbol(), kitty);
+    }
+}
@@ -0,0 +1,243 @@
+use ratatui::buffer::{Buffer, Cell};
+use ratatui::layout::Rect;
+
+/// Weight of one arm of a box-drawing glyph.
+const NONE: u8 = 0;
+const LIGHT: u8 = 1;
+const HEAVY: u8 = 2;
+const DOUBLE: u8 = 3;
+
+/// Arms in switching order: left, right, up, down.
+type Arms = [u8; 4];
+
+/// Box-drawing glyphs by their arms. Rounded corners come last so a
+/// reverse lookup finds the square corner first.
+#[rustfmt::skip]
+const GLYPHS: &[(&str, Arms)] = {
+    const N: u8 = NONE;
+    const L: u8 = LIGHT;
+    const H: u8 = HEAVY;
+    const D: u8 = DOUBLE;
+
+    &[
+        ("─", [L, L, N, N]), ("━", [H, H, N, N]), ("│", [N, N, L, L]), ("┃", [N, N, H, H]),
+        ("┌", [N, L, N, L]), ("┍", [N, H, N, L]), ("┎", [N, L, N, H]), ("┏", [N, H, N, H]),
+        ("┐", [L, N, N, L]), ("┑", [H, N, N, L]), ("┒", [L, N, N, H]), ("┓", [H, N, N, H]),
+        ("└", [N, L, L, N]), ("┕", [N, H, L, N]), ("┖", [N, L, H, N]), ("┗", [N, H, H, N]),
+        ("┘", [L, N, L, N]), ("┙", [H, N, L, N]), ("┚", [L, N, H, N]), ("┛", [H, N, H, N]),
+        ("├", [N, L, L, L]), ("┝", [N, H, L, L]), ("┞", [N, L, H, L]), ("┟", [N, L, L, H]),
+        ("┠", [N, L, H, H]), ("┡", [N, H, H, L]), ("┢", [N, H, L, H]), ("┣", [N, H, H, H]),
+        ("┤", [L, N, L, L]), ("┥", [H, N, L, L]), ("┦", [L, N, H, L]), ("┧", [L, N, L, H]),
+        ("┨", [L, N, H, H]), ("┩", [H, N, H, L]), ("┪", [H, N, L, H]), ("┫", [H, N, H, H]),
+        ("┬", [L, L, N, L]), ("┭", [H, L, N, L]), ("┮", [L, H, N, L]), ("┯", [H, H, N, L]),
+        ("┰", [L, L, N, H]), ("┱", [H, L, N, H]), ("┲", [L, H, N, H]), ("┳", [H, H, N, H]),
+        ("┴", [L, L, L, N]), ("┵", [H, L, L, N]), ("┶", [L, H, L, N]), ("┷", [H, H, L, N]),
+        ("┸", [L, L, H, N]), ("┹", [H, L, H, N]), ("┺", [L, H, H, N]), ("┻", [H, H, H, N]),
+        ("┼", [L, L, L, L]), ("┽", [H, L, L, L]), ("┾", [L, H, L, L]), ("┿", [H, H, L, L]),
+        ("╀", [L, L, H, L]), ("╁", [L, L, L, H]), ("╂", [L, L, H, H]), ("╃", [H, L, H, L]),
+        ("╄", [L, H, H, L]), ("╅", [H, L, L, H]), ("╆", [L, H, L, H]), ("╇", [H, H, H, L]),
+        ("╈", [H, H, L, H]), ("╉", [H, L, H, H]), ("╊", [L, H, H, H]), ("╋", [H, H, H, H]),
+        ("═", [D, D, N, N]), ("║", [N, N, D, D]),
+        ("╒", [N, D, N, L]), ("╓", [N, L, N, D]), ("╔", [N, D, N, D]),
+        ("╕", [D, N, N, L]), ("╖", [L, N, N, D]), ("╗", [D, N, N, D]),
+        ("╘", [N, D, L, N]), ("╙", [N, L, D, N]), ("╚", [N, D, D, N]),
+        ("╛", [D, N, L, N]), ("╜", [L, N, D, N]), ("╝", [D, N, D, N]),
+        ("╞", [N, D, L, L]), ("╟", [N, L, D, D]), ("╠", [N, D, D, D]),
+        ("╡", [D, N, L, L]), ("╢", [L, N, D, D]), ("╣", [D, N, D, D]),
+        ("╤", [D, D, N, L]), ("╥", [L, L, N, D]), ("╦", [D, D, N, D]),
+        ("╧", [D, D, L, N]), ("╨", [L, L, D, N]), ("╩", [D, D, D, N]),
+        ("╪", [D, D, L, L]), ("╫", [L, L, D, D]), ("╬", [D, D, D, D]),
+        ("╴", [L, N, N, N]), ("╵", [N, N, L, N]), ("╶", [N, L, N, N]), ("╷", [N, N, N, L]),
+        ("╸", [H, N, N, N]), ("╹", [N, N, H, N]), ("╺", [N, H, N, N]), ("╻", [N, N, N, H]),
+        ("╼", [L, H, N, N]), ("╽", [N, N, L, H]), ("╾", [H, L, N, N]), ("╿", [N, N, H, L]),
+        ("╭", [N, L, N, L]), ("╮", [L, N, N, L]), ("╯", [L, N, L, N]), ("╰", [N, L, L, N]),
+    ]
+};
+
+fn arms(glyph: &str) -> Option<Arms> {
+    GLYPHS
+        .iter()
+        .find(|(g, _)| *g == glyph)
+        .map(|(_, arms)| *arms)
+}
+
+fn glyph(arms: Arms) -> Option<&'static str> {
+    GLYPHS.iter().find(|(_, a)| *a == arms).map(|(g, _)| *g)
+}
+
+/// Glyph to show at eased progress `t` when a box-drawing glyph changes
+/// weight or style but keeps its shape, e.g. `─` → `━` or `┌` → `╔`.
+///
+/// Differing arms switch one at a time (left, right, up, down) through the
+/// mixed-weight glyphs, so a thickening border grows rather than pops.
+/// `None` when either glyph isn't box drawing or the shapes differ. Where
+/// Unicode has no mixed glyph (heavy with double), swaps at the midpoint.
+pub fn between(src: &str, dst: &str, t: f32) -> Option<&'static str> {
+    let (from, to) = (arms(src)?, arms(dst)?);
+
+    if from.map(|w| w != NONE) != to.map(|w| w != NONE) {
+        return None;
+    }
+
+    let differing: Vec<usize> = (0..4).filter(|&i| from[i] != to[i]).collect();
+    let steps = differing.len();
+    let switched = ((t.clamp(0.0, 1.0) * (steps + 1) as f32) as usize).min(steps);
+    let midpoint = if t < 0.5 { src } else { dst };
+
+    if steps == 0 {
+        // Same arms, different style: square vs. rounded corner.
+        return interned(midpoint);
+    }
+
+    if switched == 0 {
+        return interned(src);
+    }
+
+    if switched == steps {
+        return interned(dst);
+    }
+
+    let mut now = from;
+    for &i in &differing[..switched] {
+        now[i] = to[i];
+    }
+
+    glyph(now).or_else(|| interned(midpoint))
+}
+
+/// A rectangle outlined in box-drawing glyphs, such as a `Block` border.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Outline {
+    pub area: Rect,
+    /// Offsets from `area.x` of top and bottom edge cells holding
+    /// something other than a line, such as a title.
+    pub top_gaps: Vec<u16>,
+    pub bottom_gaps: Vec<u16>,
+}
+
+impl Outline {
+    /// Positions of the outline's line glyphs, gaps excluded.
+    pub fn lines(&self) -> impl Iterator<Item = (u16, u16)> + '_ {
+        let Rect { x, y, .. } = self.area;
+        let (right, bottom) = (self.area.right() - 1, self.area.bottom() - 1);
+        let top = (x..=right).filter(move |&cx| !self.top_gaps.contains(&(cx - x)));
+        let base = (x..=right).filter(move |&cx| !self.bottom_gaps.contains(&(cx - x)));
+
+        top.map(move |cx| (cx, y))
+            .chain(base.map(move |cx| (cx, bottom)))
+            .chain((y + 1..bottom).flat_map(move |cy| [(x, cy), (right, cy)]))
+    }
+}
+
+/// Which arms a glyph has, ignoring weight.
+fn shape(cell: &Cell) -> Option<[bool; 4]> {
+    arms(cell.symbol()).map(|arms| arms.map(|w| w != NONE))
+}
+
+const HORIZONTAL: [bool; 4] = [true, true, false, false];
+const VERTICAL: [bool; 4] = [false, false, true, true];
+const TOP_LEFT: [bool; 4] = [false, true, false, true];
+const TOP_RIGHT: [bool; 4] = [true, false, false, true];
+const BOTTOM_LEFT: [bool; 4] = [false, true, true, false];
+const BOTTOM_RIGHT: [bool; 4] = [true, false, true, false];
+
+/// Every box outlined in `buf`: four corners joined by straight lines,
+/// where the top and bottom edges may be interrupted by other glyphs.
+pub(crate) fn outlines(buf: &Buffer) -> Vec<Outline> {
+    let area = buf.area;
+    let mut found = Vec::new();
+
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            if shape(&buf[(x, y)]) != Some(TOP_LEFT) {
+                continue;
+            }
+
+            let Some((right, top_gaps)) = edge(buf, x, y, TOP_RIGHT) else {
+                continue;
+            };
+            let down = (y + 1..area.bottom()).find(|&cy| shape(&buf[(x, cy)]) != Some(VERTICAL));
+            let Some(bottom) = down.filter(|&cy| shape(&buf[(x, cy)]) == Some(BOTTOM_LEFT)) else {
+                continue;
+            };
+            let Some((end, bottom_gaps)) = edge(buf, x, bottom, BOTTOM_RIGHT) else {
+                continue;
+            };
+            let sides = (y + 1..bottom).all(|cy| shape(&buf[(right, cy)]) == Some(VERTICAL));
+
+            if end == right && sides {
+                found.push(Outline {
+                    area: Rect::new(x, y, right - x + 1, bottom - y + 1),
+                    top_gaps,
+                    bottom_gaps,
+                });
+            }
+        }
+    }
+
+    found
+}
+
+/// From a left corner at `(x, y)`, the column of the first `corner` to its
+/// right, and the offsets of non-line glyphs on the way. `None` if another
+/// box-drawing glyph comes first.
+fn edge(buf: &Buffer, x: u16, y: u16, corner: [bool; 4]) -> Option<(u16, Vec<u16>)> {
+    let mut gaps = Vec::new();
+
+    for cx in x + 1..buf.area.right() {
+        match shape(&buf[(cx, y)]) {
+            Some(shape) if shape == corner => return Some((cx, gaps)),
+            Some(HORIZONTAL) => {}
+            Some(_) => return None,
+            None => gaps.push(cx - x),
+        }
+    }
+
+    None
+}
+
+/// `glyph` as the table's `'static` copy.
+fn interned(glyph: &str) -> Option<&'static str> {
+    GLYPHS.iter().find(|(g, _)| *g == glyph).map(|(g, _)| *g)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn thickening_line_grows() {
+        let frames: Vec<&str> = [0.0, 0.4, 0.9, 1.0]
+            .iter()
+            .filter_map(|&t| between("─", "━", t))
+            .collect();
+
+        assert_eq!(frames, vec!["─", "╾", "━", "━"]);
+    }
+
+    #[test]
+    fn outlines_allow_titles() {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 8, 4));
+        for (y, row) in (0..).zip(["┌Hi────┐", "│      │", "└──────┘", "┌─┐ ├─┤"])
+        {
+            buf.set_string(0, y, row, ratatui::style::Style::new());
+        }
+
+        let found = outlines(&buf);
+
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].area, Rect::new(0, 0, 8, 3));
+        assert_eq!(found[0].top_gaps, [1, 2]);
+        assert_eq!(found[0].lines().count(), 8 + 8 + 2 - 2);
+    }
+
+    #[test]
+    fn corner_styles() {
+        assert_eq!(between("┌", "╔", 0.5), Some("╒"));
+        assert_eq!(between("┌", "╭", 0.4), Some("┌"));
+        assert_eq!(between("┌", "╭", 0.6), Some("╭"));
+        assert_eq!(between("┏", "╔", 0.5), Some("╔"));
+        assert_eq!(between("┌", "─", 0.5), None);
+        assert_eq!(between("a", "─", 0.5), None);
+    }
+}
@@ -0,0 +1,210 @@
+use std::collections::VecDeque;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use ratatui::buffer::Buffer;
+
+use crate::config::MorphConfig;
+use crate::plan::InterpolationPlan;
+use crate::solver::{SolveOptions, SolveScratch, SolveStats};
+use crate::transition::Transition;
+use crate::weights::MorphWeights;
+
+/// Recently solved frame pairs, most recent last, so toggling between two
+/// screens (list ⇄ detail) skips the solver after the first time.
+///
+/// Entries are keyed by both buffers and the solver inputs (weights and
+/// `SolveOptions`; hooks by identity), and hold copies of the buffers to
+/// rule out hash collisions.
+#[derive(Clone, Default)]
+pub struct PlanCache {
+    entries: VecDeque<Entry>,
+}
+
+#[derive(Clone)]
+struct Entry {
+    key: u64,
+    src: Buffer,
+    dst: Buffer,
+    plan: InterpolationPlan,
+}
+
+impl PlanCache {
+    pub const fn new() -> Self {
+        Self {
+            entries: VecDeque::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// `Transition::new_in`, reusing a cached plan for the same pair and
+    /// solver inputs. Keeps up to `config.plan_cache` plans, dropping the
+    /// least recently used.
+    pub fn transition(
+        &mut self,
+        src: &Buffer,
+        dst: &Buffer,
+        config: &MorphConfig,
+        scratch: &mut SolveScratch,
+    ) -> Transition {
+        let capacity = config.plan_cache;
+        if capacity == 0 {
+            self.entries.clear();
+            return Transition::new_in(src, dst, config, scratch);
+        }
+
+        let key = key(src, dst, &config.weights, &config.solver);
+        let hit = self
+            .entries
+            .iter()
+            .position(|e| e.key == key && e.src == *src && e.dst == *dst);
+
+        let entry = match hit.and_then(|i| self.entries.remove(i)) {
+            Some(entry) => entry,
+            None => {
+                let transition = Transition::new_in(src, dst, config, scratch);
+                let entry = Entry {
+                    key,
+                    src: src.clone(),
+                    dst: dst.clone(),
+                    plan: transition.plan().clone(),
+                };
+                self.remember(entry, capacity);
+                return transition;
+            }
+        };
+
+        scratch.stats = SolveStats::default();
+        let transition = Transition::from_plan(src, dst, entry.plan.clone(), config);
+        self.remember(entry, capacity);
+        transition
+    }
+
+    fn remember(&mut self, entry: Entry, capacity: usize) {
+        while self.entries.len() >= capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+}
+
+fn key(src: &Buffer, dst: &Buffer, weights: &MorphWeights, options: &SolveOptions) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    src.hash(&mut hasher);
+    dst.hash(&mut hasher);
+    // Everything but the hooks prints its value; those print a name.
+    format!("{weights:?} {options:?}").hash(&mut hasher);
+    options
+        .cost
+        .as_ref()
+        .map(Arc::as_ptr)
+        .map(|p| p.cast::<()>())
+        .hash(&mut hasher);
+    options
+        .classifier
+        .as_ref()
+        .map(Arc::as_ptr)
+        .map(|p| p.cast::<()>())
+        .hash(&mut hasher);
+    options
+        .blank
+        .predicate
+        .as_ref()
+        .map(Arc::as_ptr)
+        .map(|p| p.cast::<()>())
+        .hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use ratatui::layout::Rect;
+    use ratatui::style::Style;
+
+    use crate::cost::MatchCell;
+
+    use super::*;
+
+    #[test]
+    fn repeated_pairs_skip_the_solver() {
+        let area = Rect::new(0, 0, 8, 1);
+        let (mut list, mut detail) = (Buffer::empty(area), Buffer::empty(area));
+        list.set_string(0, 0, "ab", Style::new());
+        detail.set_string(4, 0, "ba", Style::new());
+
+        let solves = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&solves);
+        let mut config = MorphConfig {
+            plan_cache: 2,
+            ..MorphConfig::default()
+        };
+        config.solver.cost = Some(Arc::new(move |_: &MatchCell, _: &MatchCell, base| {
+            counter.fetch_add(1, Ordering::Relaxed);
+            base
+        }));
+        let mut cache = PlanCache::new();
+        let mut scratch = SolveScratch::default();
+        let mut solve = |src: &Buffer, dst: &Buffer| {
+            let before = solves.load(Ordering::Relaxed);
+            let plan = cache
+                .transition(src, dst, &config, &mut scratch)
+                .into_plan();
+            (plan, solves.load(Ordering::Relaxed) > before)
+        };
+
+        let (first, solved) = solve(&list, &detail);
+        assert!(solved);
+        assert!(solve(&detail, &list).1);
+
+        let (again, solved) = solve(&list, &detail);
+        assert!(!solved);
+        assert!(first.diff(&again).is_empty());
+    }
+
+    #[test]
+    fn least_recently_used_goes_first() {
+        let area = Rect::new(0, 0, 4, 1);
+        let frames: Vec<Buffer> = ["a", "b", "c"]
+            .iter()
+            .map(|text| {
+                let mut buf = Buffer::empty(area);
+                buf.set_string(0, 0, text, Style::new());
+                buf
+            })
+            .collect();
+        let config = MorphConfig {
+            plan_cache: 2,
+            ..MorphConfig::default()
+        };
+        let mut cache = PlanCache::new();
+        let mut scratch = SolveScratch::default();
+
+        cache.transition(&frames[0], &frames[1], &config, &mut scratch);
+        cache.transition(&frames[1], &frames[2], &config, &mut scratch);
+        cache.transition(&frames[0], &frames[1], &config, &mut scratch);
+        cache.transition(&frames[2], &frames[0], &config, &mut scratch);
+
+        assert_eq!(cache.len(), 2);
+        let keys: Vec<_> = cache
+            .entries
+            .iter()
+            .map(|e| (e.src.clone(), e.dst.clone()))
+            .collect();
+        assert!(keys.contains(&(frames[0].clone(), frames[1].clone())));
+        assert!(!keys.contains(&(frames[1].clone(), frames[2].clone())));
+    }
+}
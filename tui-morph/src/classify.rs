@@ -0,0 +1,160 @@
+use std::fmt;
+use std::sync::Arc;
+
+use ratatui::buffer::Cell;
+use ratatui::style::Color;
+
+/// How the solver treats one position, from the source cell and the
+/// target cell there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellClass {
+    /// Shown as the target from the first frame.
+    Stable,
+    /// Morphs in place: glyph, colors and modifiers blend.
+    Mutating,
+    /// Glyphs on either side go to matching, so they can fly in from or
+    /// out to another position, or fade; the background blends in place.
+    Unmatched,
+}
+
+/// Overrides the solver's classification of changed positions, e.g. to
+/// snap cells whose only difference is a `Modifier`, or to keep
+/// box-drawing characters morphing in place. Set on
+/// `SolveOptions::classifier`.
+///
+/// `default` is what the solver would pick; see `default_class`. Cells
+/// that are skipped, excluded, moved with a keyed region or scrolled row,
+/// or part of a tweened number are never classified; the line glyphs of a
+/// moving border are classified as blanks. Closures
+/// `Fn(&Cell, &Cell, CellClass) -> CellClass` implement it.
+pub trait CellClassifier: Send + Sync {
+    fn classify(&self, src: &Cell, dst: &Cell, default: CellClass) -> CellClass;
+}
+
+impl<F> CellClassifier for F
+where
+    F: Fn(&Cell, &Cell, CellClass) -> CellClass + Send + Sync,
+{
+    fn classify(&self, src: &Cell, dst: &Cell, default: CellClass) -> CellClass {
+        self(src, dst, default)
+    }
+}
+
+impl fmt::Debug for dyn CellClassifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CellClassifier")
+    }
+}
+
+/// The solver's own classification: identical cells are stable, a glyph
+/// appearing or vanishing is unmatched, blanks differing only in
+/// foreground or modifiers snap, and anything else mutates.
+pub fn default_class(src: &Cell, dst: &Cell, blank: &BlankCells) -> CellClass {
+    let (src_blank, dst_blank) = (blank.is_blank(src), blank.is_blank(dst));
+
+    if src.symbol() == dst.symbol()
+        && src.fg == dst.fg
+        && src.bg == dst.bg
+        && src.modifier == dst.modifier
+    {
+        CellClass::Stable
+    } else if src_blank != dst_blank {
+        CellClass::Unmatched
+    } else if src_blank && src.bg == dst.bg {
+        CellClass::Stable
+    } else {
+        CellClass::Mutating
+    }
+}
+
+/// Which cells count as empty: nothing to match, move or fade, only a
+/// background to blend. Set on `SolveOptions::blank`.
+///
+/// By default a space or an empty symbol is blank whatever its colors.
+#[derive(Debug, Clone, Default)]
+pub struct BlankCells {
+    /// Any whitespace symbol is blank, including `U+00A0` and the empty
+    /// braille pattern, not just `" "`.
+    pub whitespace: bool,
+    /// A blank symbol over a non-default background is content, so a
+    /// colored panel mutates into what replaces it instead of fading.
+    pub backgrounds: bool,
+    /// Decides instead of the rules above.
+    pub predicate: Option<Arc<dyn BlankPredicate>>,
+}
+
+impl BlankCells {
+    pub const fn new() -> Self {
+        Self {
+            whitespace: false,
+            backgrounds: false,
+            predicate: None,
+        }
+    }
+
+    pub fn is_blank(&self, cell: &Cell) -> bool {
+        if let Some(predicate) = &self.predicate {
+            return predicate.is_blank(cell);
+        }
+
+        let symbol = cell.symbol();
+        let empty = if self.whitespace {
+            symbol.chars().all(|c| c.is_whitespace() || c == '\u{2800}')
+        } else {
+            symbol == " " || symbol.is_empty()
+        };
+
+        empty && !(self.backgrounds && cell.bg != Color::Reset)
+    }
+}
+
+/// Custom blank test for `BlankCells::predicate`. Closures
+/// `Fn(&Cell) -> bool` implement it.
+pub trait BlankPredicate: Send + Sync {
+    fn is_blank(&self, cell: &Cell) -> bool;
+}
+
+impl<F> BlankPredicate for F
+where
+    F: Fn(&Cell) -> bool + Send + Sync,
+{
+    fn is_blank(&self, cell: &Cell) -> bool {
+        self(cell)
+    }
+}
+
+impl fmt::Debug for dyn BlankPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("BlankPredicate")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blank_rules() {
+        let nbsp = Cell::new("\u{a0}");
+        let mut panel = Cell::new(" ");
+        panel.set_bg(Color::Blue);
+
+        assert!(BlankCells::new().is_blank(&panel));
+        assert!(!BlankCells::new().is_blank(&nbsp));
+
+        let blank = BlankCells {
+            whitespace: true,
+            backgrounds: true,
+            ..BlankCells::new()
+        };
+        assert!(blank.is_blank(&nbsp));
+        assert!(!blank.is_blank(&panel));
+
+        let dots = BlankCells {
+            predicate: Some(Arc::new(|cell: &Cell| cell.symbol() == "·")),
+            ..BlankCells::new()
+        };
+        assert!(dots.is_blank(&Cell::new("·")));
+        assert!(!dots.is_blank(&Cell::new(" ")));
+    }
+}
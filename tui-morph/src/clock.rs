@@ -0,0 +1,76 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Time source for transition playback.
+///
+/// `MorphBackend` reads the time and waits between frames only through
+/// its clock (see `MorphBackend::set_clock`). Solver timings in
+/// `MorphMetrics` stay on the wall clock.
+pub trait Clock {
+    fn now(&self) -> Instant;
+
+    /// Wait `duration` between frames of a blocking transition.
+    fn sleep(&self, duration: Duration);
+}
+
+/// The wall clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
+}
+
+/// A clock that only moves when told to, for tests and fixed-timestep
+/// game loops. Clones share the same time.
+///
+/// `sleep` advances the clock instead of waiting, so a blocking
+/// transition plays out instantly, one frame per frame interval.
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    base: Instant,
+    /// Nanoseconds since `base`.
+    offset: Arc<AtomicU64>,
+}
+
+impl ManualClock {
+    pub fn new() -> Self {
+        Self {
+            base: Instant::now(),
+            offset: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    pub fn advance(&self, by: Duration) {
+        let nanos = u64::try_from(by.as_nanos()).unwrap_or(u64::MAX);
+        self.offset.fetch_add(nanos, Ordering::Relaxed);
+    }
+
+    /// Time since the clock was created.
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_nanos(self.offset.load(Ordering::Relaxed))
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.base + self.elapsed()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.advance(duration);
+    }
+}
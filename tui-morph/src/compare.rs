@@ -0,0 +1,238 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use crate::plan::{InterpolationPlan, PlanCounts};
+
+/// What a plan does with the glyph at one source position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fate {
+    Stable,
+    Mutating,
+    Moved { x: u16, y: u16 },
+    Disappeared,
+}
+
+impl fmt::Display for Fate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Stable => write!(f, "stable"),
+            Self::Mutating => write!(f, "mutating"),
+            Self::Moved { x, y } => write!(f, "moved to ({x}, {y})"),
+            Self::Disappeared => write!(f, "disappeared"),
+        }
+    }
+}
+
+/// A source position whose fate differs between two plans. `None` means
+/// the plan has no entry there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FateChange {
+    pub x: u16,
+    pub y: u16,
+    pub before: Option<Fate>,
+    pub after: Option<Fate>,
+}
+
+/// Structured difference between two plans, for regression tests over
+/// recorded frame pairs. `Display` renders a readable failure report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlanDiff {
+    pub before: PlanCounts,
+    pub after: PlanCounts,
+    /// Row-major.
+    pub changed: Vec<FateChange>,
+    /// Appearing only in the newer plan.
+    pub appeared: Vec<(u16, u16)>,
+    /// Appearing only in the older plan.
+    pub unappeared: Vec<(u16, u16)>,
+}
+
+impl PlanDiff {
+    pub fn is_empty(&self) -> bool {
+        self.before == self.after
+            && self.changed.is_empty()
+            && self.appeared.is_empty()
+            && self.unappeared.is_empty()
+    }
+}
+
+impl InterpolationPlan {
+    /// How `other` differs from `self`, treating `self` as the baseline.
+    pub fn diff(&self, other: &InterpolationPlan) -> PlanDiff {
+        let before = fates(self);
+        let after = fates(other);
+        let keys: BTreeSet<(u16, u16)> = before.keys().chain(after.keys()).copied().collect();
+
+        let changed = keys
+            .into_iter()
+            .filter_map(|key| {
+                let (b, a) = (before.get(&key).copied(), after.get(&key).copied());
+                (b != a).then_some(FateChange {
+                    x: key.1,
+                    y: key.0,
+                    before: b,
+                    after: a,
+                })
+            })
+            .collect();
+
+        let old_appearing = appearing(self);
+        let new_appearing = appearing(other);
+
+        PlanDiff {
+            before: self.counts(),
+            after: other.counts(),
+            changed,
+            appeared: new_appearing.difference(&old_appearing).map(swap).collect(),
+            unappeared: old_appearing.difference(&new_appearing).map(swap).collect(),
+        }
+    }
+}
+
+/// Keyed `(y, x)` so iteration is row-major.
+fn fates(plan: &InterpolationPlan) -> BTreeMap<(u16, u16), Fate> {
+    let mut out = BTreeMap::new();
+
+    for cell in &plan.stable {
+        out.insert((cell.y, cell.x), Fate::Stable);
+    }
+
+    for cell in &plan.mutating {
+        out.insert((cell.y, cell.x), Fate::Mutating);
+    }
+
+    // Displaced and disappearing glyphs also leave a background-only
+    // mutating entry behind; the glyph's fate takes precedence.
+    for cell in &plan.displaced {
+        let fate = Fate::Moved {
+            x: cell.dst_x,
+            y: cell.dst_y,
+        };
+        out.insert((cell.src_y, cell.src_x), fate);
+    }
+
+    for cell in &plan.disappearing {
+        out.insert((cell.y, cell.x), Fate::Disappeared);
+    }
+
+    out
+}
+
+fn appearing(plan: &InterpolationPlan) -> BTreeSet<(u16, u16)> {
+    plan.appearing.iter().map(|c| (c.y, c.x)).collect()
+}
+
+fn swap(&(y, x): &(u16, u16)) -> (u16, u16) {
+    (x, y)
+}
+
+impl fmt::Display for PlanDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "plans are identical");
+        }
+
+        let (b, a) = (&self.before, &self.after);
+        let counts = [
+            ("stable", b.stable, a.stable),
+            ("mutating", b.mutating, a.mutating),
+            ("displaced", b.displaced, a.displaced),
+            ("appearing", b.appearing, a.appearing),
+            ("disappearing", b.disappearing, a.disappearing),
+        ];
+
+        for (name, before, after) in counts {
+            if before != after {
+                writeln!(f, "{name}: {before} -> {after}")?;
+            }
+        }
+
+        let show = |fate: Option<Fate>| fate.map_or("absent".to_string(), |f| f.to_string());
+
+        for change in &self.changed {
+            writeln!(
+                f,
+                "({}, {}): {} -> {}",
+                change.x,
+                change.y,
+                show(change.before),
+                show(change.after)
+            )?;
+        }
+
+        for (x, y) in &self.appeared {
+            writeln!(f, "({x}, {y}): now appearing")?;
+        }
+
+        for (x, y) in &self.unappeared {
+            writeln!(f, "({x}, {y}): no longer appearing")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::buffer::Buffer;
+    use ratatui::layout::Rect;
+    use ratatui::style::{Color, Style};
+
+    use crate::solver;
+    use crate::weights::MorphWeights;
+
+    use super::*;
+
+    fn row(cells: &[(u16, &str)]) -> Buffer {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 6, 1));
+
+        for &(x, sym) in cells {
+            buf[(x, 0)]
+                .set_symbol(sym)
+                .set_style(Style::new().fg(Color::Red));
+        }
+
+        buf
+    }
+
+    #[test]
+    fn identical_plans_are_empty() {
+        let src = row(&[(0, "M")]);
+        let dst = row(&[(4, "M")]);
+        let a = solver::diff(&src, &dst, &MorphWeights::LIQUID);
+        let b = solver::diff(&src, &dst, &MorphWeights::LIQUID);
+
+        assert!(a.diff(&b).is_empty());
+    }
+
+    #[test]
+    fn reports_changed_assignment() {
+        let src = row(&[(0, "M")]);
+        let dst = row(&[(4, "M")]);
+        let moved = solver::diff(&src, &dst, &MorphWeights::LIQUID);
+        let faded = solver::diff_with(
+            &src,
+            &dst,
+            &MorphWeights::LIQUID,
+            &solver::SolveOptions {
+                strategy: solver::MatchStrategy::Crossfade,
+                ..Default::default()
+            },
+        );
+
+        let diff = moved.diff(&faded);
+        assert_eq!(diff.before.displaced, 1);
+        assert_eq!(diff.after.displaced, 0);
+        assert_eq!(
+            diff.changed,
+            vec![FateChange {
+                x: 0,
+                y: 0,
+                before: Some(Fate::Moved { x: 4, y: 0 }),
+                after: Some(Fate::Disappeared),
+            }]
+        );
+        assert_eq!(diff.appeared, vec![(4, 0)]);
+        assert!(diff.to_string().contains("moved to (4, 0) -> disappeared"));
+    }
+}
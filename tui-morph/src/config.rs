@@ -0,0 +1,763 @@
+use std::fmt;
+use std::time::Duration;
+
+use ratatui::buffer::Buffer;
+
+use crate::easing::{self, CategoryEasing};
+use crate::history::HistoryOptions;
+use crate::interpolate::{Animate, BgSweep, ColorMode, TextDirection};
+use crate::modifiers::ModifierRules;
+use crate::motion::MotionLimit;
+use crate::oklch::HueMode;
+use crate::safety::FlashLimit;
+use crate::solver::{Displacement, MatchStrategy, SolveOptions};
+use crate::transition::Pacing;
+use crate::weights::MorphWeights;
+
+/// Who drives a transition's frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TickMode {
+    /// `flush` plays the whole transition before returning.
+    #[default]
+    Blocking,
+    /// `flush` shows the first frame and returns; the app calls
+    /// `MorphBackend::tick` from its event loop for the rest. A draw
+    /// during a transition retargets it: the morph continues from the
+    /// frame on screen toward the new one (see `Coalesce`).
+    Manual,
+}
+
+/// Whether frames are framed in synchronized-output markers (DEC mode
+/// 2026), so terminals paint each one whole instead of tearing mid-update.
+/// Needs a backend that accepts raw writes; see
+/// `MorphBackend::synchronized`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SyncOutput {
+    /// When `supports_synchronized_output` says so.
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+/// What the hardware cursor does while a transition plays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CursorMotion {
+    /// Glides from where it was to its new position on the transition's
+    /// easing curve.
+    #[default]
+    Glide,
+    /// Hidden until the final frame, then shown at its new position.
+    Hide,
+    /// Moves to its new position on the first frame.
+    Jump,
+}
+
+/// What a draw does to an in-flight transition under `TickMode::Manual`.
+/// Only matters when the app redraws faster than `duration`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Coalesce {
+    /// Start a fresh transition from the frame on screen. Redraws faster
+    /// than `duration` keep pushing the end back.
+    #[default]
+    Retarget,
+    /// Morph from the frame on screen to the newest frame, arriving when
+    /// the interrupted transition would have.
+    KeepDeadline,
+    /// Let the in-flight transition finish, then morph to the newest
+    /// frame drawn meanwhile. Frames drawn in between are never shown.
+    Latest,
+}
+
+/// One-off changes to a single transition; see
+/// `MorphBackend::next_transition`. `None` fields keep the config's value.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TransitionOverrides {
+    pub duration: Option<Duration>,
+    pub weights: Option<MorphWeights>,
+    pub easing: Option<fn(f32) -> f32>,
+}
+
+impl TransitionOverrides {
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    pub fn weights(mut self, weights: MorphWeights) -> Self {
+        self.weights = Some(weights);
+        self
+    }
+
+    pub fn easing(mut self, easing: fn(f32) -> f32) -> Self {
+        self.easing = Some(easing);
+        self
+    }
+
+    /// Write the set fields into `config`, returning overrides that put
+    /// the old values back.
+    #[cfg_attr(feature = "disabled", allow(dead_code))]
+    pub(crate) fn apply(self, config: &mut MorphConfig) -> Self {
+        Self {
+            duration: self
+                .duration
+                .map(|d| std::mem::replace(&mut config.duration, d)),
+            weights: self
+                .weights
+                .map(|w| std::mem::replace(&mut config.weights, w)),
+            easing: self
+                .easing
+                .map(|e| std::mem::replace(&mut config.easing, e)),
+        }
+    }
+}
+
+/// Per-frame hook; see `MorphConfig::post_process`.
+pub type PostProcess = Box<dyn FnMut(f32, &mut Buffer)>;
+
+pub struct MorphConfig {
+    pub weights: MorphWeights,
+    /// `ZERO` (with `Pacing::Fixed`) disables morphing: frames are shown
+    /// as drawn.
+    pub duration: Duration,
+    pub easing: fn(f32) -> f32,
+    /// Curves that replace `easing` for motion, in-place changes or fades.
+    pub category_easing: CategoryEasing,
+    /// Per-modifier overrides of the midpoint switch.
+    pub modifiers: ModifierRules,
+    pub hue: HueMode,
+    /// Per-category switches; disabled categories jump straight to the target.
+    pub animate: Animate,
+    /// Honors `NO_COLOR` by default.
+    pub color: ColorMode,
+    /// Large background changes sweep across their region when set;
+    /// `BgSweep::default()` sweeps along `text_direction`.
+    pub bg_sweep: Option<BgSweep>,
+    /// Directional defaults run this way, so RTL interfaces don't animate
+    /// backwards.
+    pub text_direction: TextDirection,
+    /// `0` disables morphing, like a zero `duration`. `build` rejects it.
+    pub fps: u32,
+    /// Below `High`, switches off costly extras whatever their own settings;
+    /// see `Quality`.
+    pub quality: Quality,
+    pub tick: TickMode,
+    pub coalesce: Coalesce,
+    pub sync_output: SyncOutput,
+    pub cursor: CursorMotion,
+    pub solver: SolveOptions,
+    pub pacing: Pacing,
+
+    /// Photosensitivity guard. When set, transitions that would change
+    /// lightness too quickly are lengthened to stay within the limit.
+    pub flash_limit: Option<FlashLimit>,
+
+    /// Displaced cells that would move faster than this fade instead.
+    pub motion_limit: MotionLimit,
+
+    /// Clear and fully repaint on the final frame of every Nth transition,
+    /// recovering from anything else that wrote to the terminal.
+    pub repaint_every: Option<u32>,
+
+    /// Snap straight to the target when more than this fraction (0–1) of
+    /// cells change, e.g. a full theme switch, where a morph only adds
+    /// latency.
+    pub max_changed_ratio: Option<f32>,
+
+    /// Snap straight to the target when fewer cells than this change, so
+    /// a ticking clock doesn't throb.
+    pub min_changed_cells: usize,
+
+    /// `Backend::clear` (and `Terminal::clear`) fades the screen out
+    /// instead of wiping it at once.
+    pub fade_on_clear: bool,
+
+    /// Output budget for slow links such as SSH: in-between frames are
+    /// sent at a lower rate when the recent ones would exceed it, and a
+    /// transition lands early when even one frame a second would. `None`
+    /// sends every frame.
+    pub max_bytes_per_second: Option<u32>,
+
+    /// Transitions `MorphBackend::history` remembers.
+    pub history: HistoryOptions,
+
+    /// Solved plans kept for frame pairs that recur, e.g. toggling
+    /// between two screens; see `PlanCache`. `0` always solves.
+    pub plan_cache: usize,
+
+    /// Runs on every intermediate frame, after any `FrameEffect`s, just
+    /// before it is flushed. Receives linear progress `t`.
+    pub post_process: Option<PostProcess>,
+}
+
+impl Default for MorphConfig {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl MorphConfig {
+    /// 200ms, ease-in-out, CRISP. Presets override it field by field.
+    pub const DEFAULT: Self = Self {
+        weights: MorphWeights::CRISP,
+        duration: Duration::from_millis(200),
+        easing: easing::ease_in_out,
+        category_easing: CategoryEasing::NONE,
+        modifiers: ModifierRules::new(),
+        hue: HueMode::Shortest,
+        animate: Animate::ALL,
+        color: ColorMode::Auto,
+        bg_sweep: None,
+        text_direction: TextDirection::LeftToRight,
+        fps: 60,
+        quality: Quality::High,
+        tick: TickMode::Blocking,
+        coalesce: Coalesce::Retarget,
+        sync_output: SyncOutput::Auto,
+        cursor: CursorMotion::Glide,
+        solver: SolveOptions::new(),
+        pacing: Pacing::Fixed,
+        flash_limit: None,
+        motion_limit: MotionLimit::NONE,
+        repaint_every: None,
+        max_changed_ratio: None,
+        min_changed_cells: 0,
+        fade_on_clear: false,
+        max_bytes_per_second: None,
+        history: HistoryOptions::DEFAULT,
+        plan_cache: 8,
+        post_process: None,
+    };
+
+    /// Quick in-place rewrites: 120ms, ease-out, CRISP.
+    pub const SNAPPY: Self = {
+        // Field by field: `..Self::DEFAULT` would drop the rest in a const.
+        let mut config = Self::DEFAULT;
+        config.weights = MorphWeights::CRISP;
+        config.duration = Duration::from_millis(120);
+        config.easing = easing::ease_out;
+        config
+    };
+
+    /// Unhurried flowing motion: 350ms, ease-in-out, LIQUID.
+    pub const SMOOTH: Self = {
+        let mut config = Self::DEFAULT;
+        config.weights = MorphWeights::LIQUID;
+        config.duration = Duration::from_millis(350);
+        config.easing = easing::ease_in_out;
+        config
+    };
+
+    /// Validated construction, starting from `Default`.
+    pub fn builder() -> MorphConfigBuilder {
+        MorphConfigBuilder {
+            config: Self::default(),
+            error: None,
+        }
+    }
+}
+
+/// One-knob performance/fidelity trade-off. Weak terminals (slow paint,
+/// high latency links) should use `Low`.
+///
+/// Covers frame rate, solver strategy and the effect toggles below. There
+/// is no sub-cell rendering to scale: displaced glyphs always move in
+/// whole cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quality {
+    /// 20fps, no displacement solve — glyphs crossfade in place. No
+    /// background sweeps, ambient drift, frame effects or cursor glide.
+    Low,
+    /// 30fps, assignment strategy chosen by problem size.
+    Medium,
+    /// 60fps, full assignment.
+    High,
+}
+
+impl Quality {
+    pub fn fps(self) -> u32 {
+        match self {
+            Self::Low => 20,
+            Self::Medium => 30,
+            Self::High => 60,
+        }
+    }
+
+    pub fn strategy(self) -> MatchStrategy {
+        match self {
+            Self::Low => MatchStrategy::Crossfade,
+            Self::Medium => MatchStrategy::Adaptive,
+            Self::High => MatchStrategy::Hungarian,
+        }
+    }
+
+    /// `MorphConfig::bg_sweep` is honored.
+    pub fn bg_sweep(self) -> bool {
+        self != Self::Low
+    }
+
+    /// `ambient_tick` repaints the ambient layers.
+    pub fn ambient(self) -> bool {
+        self != Self::Low
+    }
+
+    /// `FrameEffect`s run on in-between frames.
+    pub fn effects(self) -> bool {
+        self != Self::Low
+    }
+
+    /// `CursorMotion::Glide` glides; otherwise the cursor jumps.
+    pub fn cursor_glide(self) -> bool {
+        self != Self::Low
+    }
+}
+
+impl MorphConfig {
+    /// Degraded profile for the legacy Windows console (conhost): 16
+    /// colors, 15fps, and fades only — no glyph travels.
+    pub fn with_legacy_console(mut self) -> Self {
+        self.fps = 15;
+        self.solver.strategy = MatchStrategy::Crossfade;
+        self.color = ColorMode::Ansi16;
+        self
+    }
+
+    /// `with_legacy_console` when running under conhost; unchanged
+    /// everywhere else.
+    pub fn adapt_to_terminal(self) -> Self {
+        if is_legacy_console() {
+            self.with_legacy_console()
+        } else {
+            self
+        }
+    }
+
+    /// Set `quality`, and overwrite `fps` and the solver strategy with the
+    /// level's values.
+    pub fn with_quality(mut self, quality: Quality) -> Self {
+        self.quality = quality;
+        self.fps = quality.fps();
+        self.solver.strategy = quality.strategy();
+        self
+    }
+}
+
+/// Windows without any marker of a modern terminal (Windows Terminal,
+/// ConEmu, Alacritty, WezTerm, mintty, …), i.e. the classic conhost window.
+/// `TUI_MORPH_LEGACY_CONSOLE=1`/`0` forces the answer on any platform.
+pub fn is_legacy_console() -> bool {
+    match std::env::var("TUI_MORPH_LEGACY_CONSOLE").as_deref() {
+        Ok("1") => return true,
+        Ok("0") => return false,
+        _ => {}
+    }
+
+    const MODERN: [&str; 5] = [
+        "WT_SESSION",
+        "TERM_PROGRAM",
+        "ConEmuANSI",
+        "ALACRITTY_WINDOW_ID",
+        "TERM",
+    ];
+
+    cfg!(windows) && MODERN.iter().all(|var| std::env::var_os(var).is_none())
+}
+
+/// The terminal is known to honor synchronized output (kitty, WezTerm,
+/// iTerm2, foot, Ghostty, Alacritty, Contour, VS Code). Terminals without
+/// it ignore the markers, so a wrong "no" only costs tearing.
+/// `TUI_MORPH_SYNC_OUTPUT=1`/`0` forces the answer.
+pub fn supports_synchronized_output() -> bool {
+    match std::env::var("TUI_MORPH_SYNC_OUTPUT").as_deref() {
+        Ok("1") => return true,
+        Ok("0") => return false,
+        _ => {}
+    }
+
+    const PROGRAMS: [&str; 5] = ["WezTerm", "iTerm.app", "ghostty", "contour", "vscode"];
+    const TERMS: [&str; 5] = ["xterm-kitty", "foot", "xterm-ghostty", "contour", "wezterm"];
+
+    let var = |name| std::env::var(name).unwrap_or_default();
+    let (program, term) = (var("TERM_PROGRAM"), var("TERM"));
+
+    PROGRAMS.contains(&program.as_str())
+        || TERMS.iter().any(|t| term.starts_with(t))
+        || std::env::var_os("KITTY_WINDOW_ID").is_some()
+        || std::env::var_os("ALACRITTY_WINDOW_ID").is_some()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigError {
+    ZeroFps,
+    /// Negative, NaN, or infinite seconds.
+    InvalidDuration(f32),
+    /// Non-positive or non-finite `Pacing::Speed` rate.
+    InvalidSpeed(f32),
+    /// Negative or non-finite motion cap.
+    InvalidMotionLimit(f32),
+    /// A `FlashLimit` field that is non-finite or too small; see
+    /// `FlashLimit::invalid`.
+    InvalidFlashLimit(f32),
+    /// `repaint_every` of zero.
+    ZeroRepaintInterval,
+    /// `max_changed_ratio` outside 0–1.
+    InvalidChangedRatio(f32),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ZeroFps => write!(f, "fps must be greater than zero"),
+            Self::InvalidDuration(secs) => write!(f, "invalid duration: {secs}s"),
+            Self::InvalidSpeed(speed) => write!(f, "invalid speed: {speed} cells/s"),
+            Self::InvalidMotionLimit(cap) => write!(f, "invalid motion limit: {cap}"),
+            Self::InvalidFlashLimit(value) => write!(f, "invalid flash limit: {value}"),
+            Self::ZeroRepaintInterval => write!(f, "repaint interval must be greater than zero"),
+            Self::InvalidChangedRatio(ratio) => write!(f, "invalid changed-cell ratio: {ratio}"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Builder for `MorphConfig`; `build` rejects settings that would make
+/// the tick loop misbehave.
+pub struct MorphConfigBuilder {
+    config: MorphConfig,
+    error: Option<ConfigError>,
+}
+
+impl MorphConfigBuilder {
+    /// Start from a preset instead of `Default`.
+    pub fn from_preset(config: MorphConfig) -> Self {
+        Self {
+            config,
+            error: None,
+        }
+    }
+
+    pub fn weights(mut self, weights: MorphWeights) -> Self {
+        self.config.weights = weights;
+        self
+    }
+
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.config.duration = duration;
+        self
+    }
+
+    /// Fractional seconds, e.g. from a settings file.
+    pub fn duration_secs(mut self, secs: f32) -> Self {
+        match Duration::try_from_secs_f32(secs) {
+            Ok(duration) => self.config.duration = duration,
+            Err(_) => self.fail(ConfigError::InvalidDuration(secs)),
+        }
+        self
+    }
+
+    pub fn easing(mut self, easing: fn(f32) -> f32) -> Self {
+        self.config.easing = easing;
+        self
+    }
+
+    pub fn category_easing(mut self, curves: CategoryEasing) -> Self {
+        self.config.category_easing = curves;
+        self
+    }
+
+    pub fn modifiers(mut self, modifiers: ModifierRules) -> Self {
+        self.config.modifiers = modifiers;
+        self
+    }
+
+    pub fn hue(mut self, hue: HueMode) -> Self {
+        self.config.hue = hue;
+        self
+    }
+
+    pub fn animate(mut self, animate: Animate) -> Self {
+        self.config.animate = animate;
+        self
+    }
+
+    pub fn color(mut self, color: ColorMode) -> Self {
+        self.config.color = color;
+        self
+    }
+
+    pub fn tick(mut self, tick: TickMode) -> Self {
+        self.config.tick = tick;
+        self
+    }
+
+    pub fn coalesce(mut self, coalesce: Coalesce) -> Self {
+        self.config.coalesce = coalesce;
+        self
+    }
+
+    pub fn sync_output(mut self, sync: SyncOutput) -> Self {
+        self.config.sync_output = sync;
+        self
+    }
+
+    pub fn cursor(mut self, cursor: CursorMotion) -> Self {
+        self.config.cursor = cursor;
+        self
+    }
+
+    pub fn bg_sweep(mut self, sweep: BgSweep) -> Self {
+        self.config.bg_sweep = Some(sweep);
+        self
+    }
+
+    pub fn text_direction(mut self, direction: TextDirection) -> Self {
+        self.config.text_direction = direction;
+        self
+    }
+
+    pub fn fps(mut self, fps: u32) -> Self {
+        self.config.fps = fps;
+        self
+    }
+
+    pub fn quality(mut self, quality: Quality) -> Self {
+        self.config = self.config.with_quality(quality);
+        self
+    }
+
+    pub fn solver(mut self, solver: SolveOptions) -> Self {
+        self.config.solver = solver;
+        self
+    }
+
+    /// Keeps the rest of the solver options.
+    pub fn displacement(mut self, displacement: Displacement) -> Self {
+        self.config.solver.displacement = displacement;
+        self
+    }
+
+    pub fn pacing(mut self, pacing: Pacing) -> Self {
+        self.config.pacing = pacing;
+        self
+    }
+
+    pub fn flash_limit(mut self, limit: FlashLimit) -> Self {
+        self.config.flash_limit = Some(limit);
+        self
+    }
+
+    pub fn motion_limit(mut self, limit: MotionLimit) -> Self {
+        self.config.motion_limit = limit;
+        self
+    }
+
+    pub fn repaint_every(mut self, transitions: u32) -> Self {
+        self.config.repaint_every = Some(transitions);
+        self
+    }
+
+    pub fn max_changed_ratio(mut self, ratio: f32) -> Self {
+        self.config.max_changed_ratio = Some(ratio);
+        self
+    }
+
+    pub fn min_changed_cells(mut self, cells: usize) -> Self {
+        self.config.min_changed_cells = cells;
+        self
+    }
+
+    pub fn fade_on_clear(mut self, fade: bool) -> Self {
+        self.config.fade_on_clear = fade;
+        self
+    }
+
+    pub fn max_bytes_per_second(mut self, budget: u32) -> Self {
+        self.config.max_bytes_per_second = Some(budget);
+        self
+    }
+
+    pub fn history(mut self, history: HistoryOptions) -> Self {
+        self.config.history = history;
+        self
+    }
+
+    pub fn plan_cache(mut self, plans: usize) -> Self {
+        self.config.plan_cache = plans;
+        self
+    }
+
+    pub fn post_process(mut self, hook: impl FnMut(f32, &mut Buffer) + 'static) -> Self {
+        self.config.post_process = Some(Box::new(hook));
+        self
+    }
+
+    pub fn build(self) -> Result<MorphConfig, ConfigError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+
+        let config = self.config;
+
+        if config.fps == 0 {
+            return Err(ConfigError::ZeroFps);
+        }
+
+        if config.repaint_every == Some(0) {
+            return Err(ConfigError::ZeroRepaintInterval);
+        }
+
+        if let Some(ratio) = config.max_changed_ratio
+            && !(0.0..=1.0).contains(&ratio)
+        {
+            return Err(ConfigError::InvalidChangedRatio(ratio));
+        }
+
+        if let Pacing::Speed {
+            cells_per_second, ..
+        } = config.pacing
+            && !(cells_per_second.is_finite() && cells_per_second > 0.0)
+        {
+            return Err(ConfigError::InvalidSpeed(cells_per_second));
+        }
+
+        let caps = [
+            config.motion_limit.max_velocity,
+            config.motion_limit.max_acceleration,
+        ];
+
+        if let Some(cap) = caps
+            .into_iter()
+            .flatten()
+            .find(|cap| !(cap.is_finite() && *cap >= 0.0))
+        {
+            return Err(ConfigError::InvalidMotionLimit(cap));
+        }
+
+        if let Some(value) = config.flash_limit.as_ref().and_then(FlashLimit::invalid) {
+            return Err(ConfigError::InvalidFlashLimit(value));
+        }
+
+        Ok(config)
+    }
+
+    /// First error wins.
+    fn fail(&mut self, error: ConfigError) {
+        self.error.get_or_insert(error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_builds() {
+        assert!(MorphConfig::builder().build().is_ok());
+    }
+
+    #[test]
+    fn quality_levels_turn_off_extras() {
+        for (quality, fps, strategy, extras) in [
+            (Quality::Low, 20, MatchStrategy::Crossfade, false),
+            (Quality::Medium, 30, MatchStrategy::Adaptive, true),
+            (Quality::High, 60, MatchStrategy::Hungarian, true),
+        ] {
+            let config = MorphConfig::builder().quality(quality).build().unwrap();
+            assert_eq!(config.quality, quality);
+            assert_eq!(config.fps, fps);
+            assert_eq!(config.solver.strategy, strategy);
+            assert_eq!(quality.bg_sweep(), extras);
+            assert_eq!(quality.ambient(), extras);
+            assert_eq!(quality.effects(), extras);
+            assert_eq!(quality.cursor_glide(), extras);
+        }
+    }
+
+    #[test]
+    fn zero_fps_rejected() {
+        let result = MorphConfig::builder().fps(0).build();
+        assert_eq!(result.err(), Some(ConfigError::ZeroFps));
+    }
+
+    #[test]
+    fn non_finite_duration_rejected() {
+        let result = MorphConfig::builder().duration_secs(f32::NAN).build();
+        assert!(matches!(result, Err(ConfigError::InvalidDuration(_))));
+
+        let result = MorphConfig::builder().duration_secs(-1.0).build();
+        assert!(matches!(result, Err(ConfigError::InvalidDuration(_))));
+    }
+
+    #[test]
+    fn changed_ratio_must_be_a_fraction() {
+        let result = MorphConfig::builder().max_changed_ratio(1.5).build();
+        assert_eq!(result.err(), Some(ConfigError::InvalidChangedRatio(1.5)));
+
+        let result = MorphConfig::builder().max_changed_ratio(f32::NAN).build();
+        assert!(matches!(result, Err(ConfigError::InvalidChangedRatio(_))));
+    }
+
+    #[test]
+    fn presets_validate() {
+        let snappy = MorphConfigBuilder::from_preset(MorphConfig::SNAPPY).build();
+        let smooth = MorphConfigBuilder::from_preset(MorphConfig::SMOOTH).build();
+
+        assert_eq!(snappy.map(|c| c.duration), Ok(Duration::from_millis(120)));
+        assert_eq!(smooth.map(|c| c.duration), Ok(Duration::from_millis(350)));
+    }
+
+    #[test]
+    fn legacy_console_profile() {
+        let config = MorphConfig::default().with_legacy_console();
+
+        assert_eq!(config.fps, 15);
+        assert_eq!(config.solver.strategy, MatchStrategy::Crossfade);
+        assert_eq!(config.color, ColorMode::Ansi16);
+    }
+
+    #[test]
+    fn invalid_speed_rejected() {
+        let result = MorphConfig::builder()
+            .pacing(Pacing::Speed {
+                cells_per_second: 0.0,
+                min: Duration::ZERO,
+                max: Duration::from_secs(1),
+            })
+            .build();
+
+        assert_eq!(result.err(), Some(ConfigError::InvalidSpeed(0.0)));
+    }
+
+    #[test]
+    fn degenerate_flash_limit_rejected() {
+        let build = |limit| MorphConfig::builder().flash_limit(limit).build();
+
+        let tiny = FlashLimit {
+            max_step: 1e-40,
+            ..FlashLimit::WCAG
+        };
+        assert_eq!(
+            build(tiny).err(),
+            Some(ConfigError::InvalidFlashLimit(1e-40))
+        );
+
+        let nan = FlashLimit {
+            max_flashes_per_second: f32::NAN,
+            ..FlashLimit::WCAG
+        };
+        assert!(matches!(build(nan), Err(ConfigError::InvalidFlashLimit(_))));
+
+        let rare = FlashLimit {
+            max_flashes_per_second: 1e-40,
+            ..FlashLimit::WCAG
+        };
+        assert!(build(rare).is_err());
+
+        let unbounded = FlashLimit {
+            max_flashes_per_second: 0.0,
+            ..FlashLimit::WCAG
+        };
+        assert!(build(unbounded).is_ok());
+        assert!(build(FlashLimit::WCAG).is_ok());
+    }
+}
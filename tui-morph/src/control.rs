@@ -0,0 +1,84 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+use crate::config::TransitionOverrides;
+use crate::weights::MorphWeights;
+
+/// Live tuning for a `MorphBackend` from any thread, e.g. a settings
+/// screen adjusting animation feel. Get one with `MorphBackend::control`;
+/// clones share state.
+///
+/// Changes are written into the backend's config at its next flush; an
+/// in-flight transition keeps the settings it started with.
+#[derive(Debug, Clone)]
+pub struct MorphControl {
+    shared: Arc<Shared>,
+}
+
+#[derive(Debug)]
+struct Shared {
+    pending: Mutex<TransitionOverrides>,
+    enabled: AtomicBool,
+}
+
+impl MorphControl {
+    pub fn new() -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                pending: Mutex::default(),
+                enabled: AtomicBool::new(true),
+            }),
+        }
+    }
+
+    pub fn set_duration(&self, duration: Duration) {
+        self.update(|pending| pending.duration(duration));
+    }
+
+    pub fn set_weights(&self, weights: MorphWeights) {
+        self.update(|pending| pending.weights(weights));
+    }
+
+    pub fn set_easing(&self, easing: fn(f32) -> f32) {
+        self.update(|pending| pending.easing(easing));
+    }
+
+    /// Disabled, frames are shown as drawn.
+    pub fn set_enabled(&self, enabled: bool) {
+        self.shared.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.shared.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Changes made since the last call, if any.
+    #[cfg_attr(feature = "disabled", allow(dead_code))]
+    pub(crate) fn take(&self) -> Option<TransitionOverrides> {
+        let changes = std::mem::take(&mut *self.lock());
+        let changed =
+            changes.duration.is_some() || changes.weights.is_some() || changes.easing.is_some();
+
+        changed.then_some(changes)
+    }
+
+    fn update(&self, change: impl FnOnce(TransitionOverrides) -> TransitionOverrides) {
+        let mut pending = self.lock();
+        *pending = change(*pending);
+    }
+
+    /// A panic elsewhere can't leave the overrides half-written.
+    fn lock(&self) -> MutexGuard<'_, TransitionOverrides> {
+        self.shared
+            .pending
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Default for MorphControl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,41 @@
+use std::fmt;
+
+use ratatui::style::{Color, Modifier};
+
+/// A glyph the solver is trying to pair, as seen by a `CostFunction`.
+#[derive(Debug, Clone, Copy)]
+pub struct MatchCell<'a> {
+    pub x: u16,
+    pub y: u16,
+    pub symbol: &'a str,
+    pub fg: Color,
+    pub bg: Color,
+    pub modifier: Modifier,
+}
+
+/// Domain knowledge for the solver's matching, e.g. "digits prefer
+/// digits". Set on `SolveOptions::cost`.
+///
+/// Called for every candidate pair of unmatched glyphs with the built-in
+/// cost (`base`: distance, glyph mismatch and color, weighted by
+/// `MorphWeights`); returns the cost to use instead. Pairs above the
+/// fade-out-and-in threshold are not displaced, so a large cost forbids a
+/// pairing. Closures `Fn(&MatchCell, &MatchCell, f32) -> f32` implement it.
+pub trait CostFunction: Send + Sync {
+    fn cost(&self, from: &MatchCell<'_>, to: &MatchCell<'_>, base: f32) -> f32;
+}
+
+impl<F> CostFunction for F
+where
+    F: Fn(&MatchCell<'_>, &MatchCell<'_>, f32) -> f32 + Send + Sync,
+{
+    fn cost(&self, from: &MatchCell<'_>, to: &MatchCell<'_>, base: f32) -> f32 {
+        self(from, to, base)
+    }
+}
+
+impl fmt::Debug for dyn CostFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CostFunction")
+    }
+}
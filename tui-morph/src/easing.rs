@@ -1,3 +1,35 @@
+use crate::interpolate::Progress;
+
+/// Curves for single plan categories, used in place of
+/// `MorphConfig::easing` where set: say `ease_out` motion, `linear` color
+/// changes and `delayed` fade-ins.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CategoryEasing {
+    /// In-place glyph and color changes.
+    pub mutating: Option<fn(f32) -> f32>,
+    pub displaced: Option<fn(f32) -> f32>,
+    /// Appearing and disappearing cells.
+    pub orphans: Option<fn(f32) -> f32>,
+}
+
+impl CategoryEasing {
+    pub const NONE: Self = Self {
+        mutating: None,
+        displaced: None,
+        orphans: None,
+    };
+
+    /// Each category's progress at linear progress `t`, with `easing` for
+    /// those without a curve of their own.
+    pub fn progress(&self, easing: fn(f32) -> f32, t: f32) -> Progress {
+        Progress {
+            mutating: self.mutating.unwrap_or(easing)(t),
+            displaced: self.displaced.unwrap_or(easing)(t),
+            orphans: self.orphans.unwrap_or(easing)(t),
+        }
+    }
+}
+
 pub fn linear(t: f32) -> f32 {
     t
 }
@@ -18,6 +50,45 @@ pub fn ease_in_out(t: f32) -> f32 {
     }
 }
 
+/// Still for the first half, then linear: fade-ins that wait for moving
+/// glyphs to clear the way.
+pub fn delayed(t: f32) -> f32 {
+    (t * 2.0 - 1.0).clamp(0.0, 1.0)
+}
+
+const PROBE_SAMPLES: usize = 100;
+
+/// Steepest slope of `easing` over `[0, 1]`, sampled numerically.
+/// Peak velocity of a move is `distance * peak_slope / duration`.
+pub fn peak_slope(easing: impl Fn(f32) -> f32) -> f32 {
+    let step = 1.0 / PROBE_SAMPLES as f32;
+    let mut peak = 0.0f32;
+    let mut prev = easing(0.0);
+
+    for i in 1..=PROBE_SAMPLES {
+        let val = easing(i as f32 * step);
+        peak = peak.max((val - prev).abs() / step);
+        prev = val;
+    }
+
+    peak
+}
+
+/// Largest magnitude of the second derivative over `[0, 1]`, sampled
+/// numerically. Peak acceleration is `distance * peak_acceleration / duration²`.
+pub fn peak_acceleration(easing: impl Fn(f32) -> f32) -> f32 {
+    let step = 1.0 / PROBE_SAMPLES as f32;
+    let mut peak = 0.0f32;
+
+    for i in 1..PROBE_SAMPLES {
+        let t = i as f32 * step;
+        let second = (easing(t + step) - 2.0 * easing(t) + easing(t - step)) / (step * step);
+        peak = peak.max(second.abs());
+    }
+
+    peak
+}
+
 /// CSS `cubic-bezier(x1, y1, x2, y2)` semantics.
 pub fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32) -> impl Fn(f32) -> f32 {
     move |t| {
@@ -97,6 +168,7 @@ mod tests {
         assert_boundaries(ease_in);
         assert_boundaries(ease_out);
         assert_boundaries(ease_in_out);
+        assert_boundaries(delayed);
     }
 
     #[test]
@@ -104,6 +176,20 @@ mod tests {
         assert_monotonic(ease_in);
         assert_monotonic(ease_out);
         assert_monotonic(ease_in_out);
+        assert_monotonic(delayed);
+    }
+
+    #[test]
+    fn categories_fall_back_to_the_base_curve() {
+        let curves = CategoryEasing {
+            orphans: Some(delayed),
+            ..CategoryEasing::NONE
+        };
+        let progress = curves.progress(ease_in, 0.5);
+
+        assert_eq!(progress.mutating, 0.25);
+        assert_eq!(progress.displaced, 0.25);
+        assert_eq!(progress.orphans, 0.0);
     }
 
     #[test]
@@ -121,6 +207,18 @@ mod tests {
         assert!((ease_in_out(0.5) - 0.5).abs() < 1e-6);
     }
 
+    #[test]
+    fn peak_slope_of_builtins() {
+        assert!((peak_slope(linear) - 1.0).abs() < 1e-3);
+        assert!((peak_slope(ease_in_out) - 2.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn peak_acceleration_of_builtins() {
+        assert!(peak_acceleration(linear) < 1e-2);
+        assert!((peak_acceleration(ease_in) - 2.0).abs() < 0.05);
+    }
+
     #[test]
     fn cubic_bezier_boundaries() {
         let ease = cubic_bezier(0.25, 0.1, 0.25, 1.0);
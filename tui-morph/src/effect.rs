@@ -0,0 +1,38 @@
+use std::time::Duration;
+
+use ratatui::buffer::Buffer;
+
+/// Per-frame post-processing over interpolated frames, e.g. a tachyonfx
+/// `Effect` or a hand-written shader.
+///
+/// `MorphBackend` runs its effects (see `MorphBackend::add_effect`) on
+/// every intermediate frame of a transition, in the order they were
+/// added, just before the frame is flushed. The final frame is left
+/// alone so the terminal settles on exactly what the app drew.
+///
+/// Adapting a tachyonfx effect:
+///
+/// ```ignore
+/// struct Fx(tachyonfx::Effect);
+///
+/// impl FrameEffect for Fx {
+///     fn process(&mut self, frame: &mut Buffer, _t: f32, delta: Duration) {
+///         let area = frame.area;
+///         self.0.process(delta.into(), frame, area);
+///     }
+/// }
+/// ```
+pub trait FrameEffect {
+    /// `t` is linear progress through the transition; `delta` is wall time
+    /// since the previous frame (zero on the first).
+    fn process(&mut self, frame: &mut Buffer, t: f32, delta: Duration);
+
+    /// Called before the first frame of every transition.
+    fn reset(&mut self) {}
+}
+
+impl<F: FnMut(&mut Buffer, f32, Duration)> FrameEffect for F {
+    fn process(&mut self, frame: &mut Buffer, t: f32, delta: Duration) {
+        self(frame, t, delta)
+    }
+}
@@ -0,0 +1,52 @@
+//! Offline renderers for transitions: files for READMEs, docs, and video.
+
+pub mod cast;
+pub mod png;
+pub mod svg;
+
+use std::io;
+use std::time::Duration;
+
+use ratatui::buffer::{Buffer, Cell};
+use ratatui::style::{Color, Modifier};
+
+use crate::oklch;
+
+pub type Rgb = (u8, u8, u8);
+
+/// Receives every frame of a transition as it is produced.
+///
+/// `MorphBackend` tees flushed frames into a sink (see
+/// `MorphBackend::set_sink`); `Transition::record` drives one offline.
+/// `t` is linear progress through the current transition — `1.0` for
+/// frames flushed without morphing. `timestamp` increases monotonically
+/// from the sink's first frame.
+pub trait FrameSink {
+    fn frame(&mut self, t: f32, buf: &Buffer, timestamp: Duration) -> io::Result<()>;
+
+    /// Called once after the last frame. Sinks that write a single file
+    /// produce it here.
+    fn finish(&mut self) -> io::Result<()>;
+}
+
+/// Frames needed to sample `duration` at `fps`, endpoints included.
+pub(crate) fn frame_count(duration: std::time::Duration, fps: u32) -> usize {
+    let n = (duration.as_secs_f32() * fps.max(1) as f32).round() as usize + 1;
+    n.max(2)
+}
+
+/// Effective (fg, bg) after defaults and REVERSED.
+pub(crate) fn resolve(cell: &Cell, default_fg: Rgb, default_bg: Rgb) -> (Rgb, Rgb) {
+    let fg = srgb_or(cell.fg, default_fg);
+    let bg = srgb_or(cell.bg, default_bg);
+
+    if cell.modifier.contains(Modifier::REVERSED) {
+        (bg, fg)
+    } else {
+        (fg, bg)
+    }
+}
+
+fn srgb_or(color: Color, fallback: Rgb) -> Rgb {
+    oklch::to_srgb(color).unwrap_or(fallback)
+}
@@ -0,0 +1,217 @@
+use std::fmt::Write as _;
+use std::io;
+use std::time::Duration;
+
+use ratatui::buffer::{Buffer, Cell};
+use ratatui::style::{Color, Modifier};
+
+use crate::export::FrameSink;
+use crate::transition::Transition;
+
+/// Write `transition` as an asciinema v2 recording sampled at `fps`.
+pub fn write<W: io::Write>(transition: &Transition, out: W, fps: u32) -> io::Result<()> {
+    let mut sink = CastSink::new(out);
+    transition.record(&mut sink, fps)?;
+    sink.finish()
+}
+
+/// Streams frames as asciinema v2 output events. Each event carries only
+/// the cells that changed since the previous frame.
+pub struct CastSink<W: io::Write> {
+    out: W,
+    prev: Option<Buffer>,
+    start: Option<Duration>,
+}
+
+impl<W: io::Write> CastSink<W> {
+    pub fn new(out: W) -> Self {
+        Self {
+            out,
+            prev: None,
+            start: None,
+        }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
+
+impl<W: io::Write> FrameSink for CastSink<W> {
+    fn frame(&mut self, _t: f32, buf: &Buffer, timestamp: Duration) -> io::Result<()> {
+        let area = *buf.area();
+        let start = *self.start.get_or_insert(timestamp);
+
+        let data = match &self.prev {
+            Some(prev) if *prev.area() == area => encode(prev.diff(buf), area.x, area.y),
+            _ => {
+                if self.prev.is_none() {
+                    writeln!(
+                        self.out,
+                        r#"{{"version": 2, "width": {}, "height": {}}}"#,
+                        area.width, area.height
+                    )?;
+                }
+
+                let blank = Buffer::empty(area);
+                let mut data = String::from("\x1b[0m\x1b[2J");
+                data.push_str(&encode(blank.diff(buf), area.x, area.y));
+                data
+            }
+        };
+
+        self.prev = Some(buf.clone());
+
+        if data.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(
+            self.out,
+            "[{:.6}, \"o\", \"{}\"]",
+            timestamp.saturating_sub(start).as_secs_f64(),
+            json_escape(&data)
+        )
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+}
+
+/// ANSI for a set of cell updates, positioned relative to the area origin.
+fn encode(updates: Vec<(u16, u16, &Cell)>, origin_x: u16, origin_y: u16) -> String {
+    let mut out = String::new();
+    let mut cursor: Option<(u16, u16)> = None;
+    let mut style: Option<(Color, Color, Modifier)> = None;
+
+    for (x, y, cell) in updates {
+        if cursor != Some((x, y)) {
+            let _ = write!(out, "\x1b[{};{}H", y - origin_y + 1, x - origin_x + 1);
+        }
+
+        let next = (cell.fg, cell.bg, cell.modifier);
+
+        if style != Some(next) {
+            out.push_str("\x1b[0");
+            push_modifiers(&mut out, cell.modifier);
+            push_color(&mut out, cell.fg, false);
+            push_color(&mut out, cell.bg, true);
+            out.push('m');
+            style = Some(next);
+        }
+
+        out.push_str(cell.symbol());
+        cursor = Some((x + 1, y));
+    }
+
+    if style.is_some() {
+        out.push_str("\x1b[0m");
+    }
+
+    out
+}
+
+fn push_modifiers(out: &mut String, modifier: Modifier) {
+    const CODES: [(Modifier, u8); 8] = [
+        (Modifier::BOLD, 1),
+        (Modifier::DIM, 2),
+        (Modifier::ITALIC, 3),
+        (Modifier::UNDERLINED, 4),
+        (Modifier::SLOW_BLINK, 5),
+        (Modifier::REVERSED, 7),
+        (Modifier::HIDDEN, 8),
+        (Modifier::CROSSED_OUT, 9),
+    ];
+
+    for (flag, code) in CODES {
+        if modifier.contains(flag) {
+            let _ = write!(out, ";{code}");
+        }
+    }
+}
+
+fn push_color(out: &mut String, color: Color, background: bool) {
+    let base = if background { 40 } else { 30 };
+
+    let _ = match color {
+        Color::Reset => write!(out, ";{}", base + 9),
+        Color::Black => write!(out, ";{base}"),
+        Color::Red => write!(out, ";{}", base + 1),
+        Color::Green => write!(out, ";{}", base + 2),
+        Color::Yellow => write!(out, ";{}", base + 3),
+        Color::Blue => write!(out, ";{}", base + 4),
+        Color::Magenta => write!(out, ";{}", base + 5),
+        Color::Cyan => write!(out, ";{}", base + 6),
+        Color::Gray => write!(out, ";{}", base + 7),
+        Color::DarkGray => write!(out, ";{}", base + 60),
+        Color::LightRed => write!(out, ";{}", base + 61),
+        Color::LightGreen => write!(out, ";{}", base + 62),
+        Color::LightYellow => write!(out, ";{}", base + 63),
+        Color::LightBlue => write!(out, ";{}", base + 64),
+        Color::LightMagenta => write!(out, ";{}", base + 65),
+        Color::LightCyan => write!(out, ";{}", base + 66),
+        Color::White => write!(out, ";{}", base + 67),
+        Color::Indexed(i) => write!(out, ";{};5;{i}", base + 8),
+        Color::Rgb(r, g, b) => write!(out, ";{};2;{r};{g};{b}", base + 8),
+    };
+}
+
+fn json_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+
+    for ch in s.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::layout::Rect;
+    use ratatui::style::Style;
+
+    use crate::config::MorphConfig;
+
+    use super::*;
+
+    #[test]
+    fn header_then_events() {
+        let a = Buffer::empty(Rect::new(0, 0, 4, 1));
+        let mut b = a.clone();
+        b[(1, 0)]
+            .set_symbol("Z")
+            .set_style(Style::new().fg(Color::Rgb(1, 2, 3)));
+
+        let transition = Transition::new(&a, &b, &MorphConfig::default());
+        let mut out = Vec::new();
+        write(&transition, &mut out, 30).unwrap_or_default();
+
+        let text = String::from_utf8(out).unwrap_or_default();
+        let mut lines = text.lines();
+
+        assert_eq!(
+            lines.next(),
+            Some(r#"{"version": 2, "width": 4, "height": 1}"#)
+        );
+        assert!(lines.all(|l| l.starts_with('[') && l.contains(r#""o""#)));
+        assert!(text.contains("38;2;1;2;3"));
+        assert!(text.contains("\\u001b[1;2H"));
+    }
+
+    #[test]
+    fn json_escapes_control_characters() {
+        assert_eq!(json_escape("a\"\x1b"), "a\\\"\\u001b");
+    }
+}
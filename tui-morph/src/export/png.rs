@@ -0,0 +1,356 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use ratatui::buffer::Buffer;
+use ratatui::style::Modifier;
+
+use crate::export::{self, FrameSink, Rgb};
+use crate::transition::Transition;
+
+const GLYPH_W: usize = 8;
+const GLYPH_H: usize = 16;
+const RECORD: usize = 4 + GLYPH_W * GLYPH_H;
+
+/// Pre-rasterized glyphs; see `assets/README.md` for format and provenance.
+static ATLAS: &[u8] = include_bytes!("../../assets/glyphs-8x16.bin");
+
+#[derive(Clone)]
+pub struct PngOptions {
+    pub fps: u32,
+    /// Integer upscale of the 8×16 cell.
+    pub scale: u32,
+    /// Substituted for `Color::Reset` and colors without a fixed RGB value.
+    pub default_fg: Rgb,
+    pub default_bg: Rgb,
+}
+
+impl Default for PngOptions {
+    fn default() -> Self {
+        Self {
+            fps: 30,
+            scale: 1,
+            default_fg: (204, 204, 204),
+            default_bg: (0, 0, 0),
+        }
+    }
+}
+
+/// Write each frame of `transition` to `dir` as `frame_00000.png`, … —
+/// the numbering `ffmpeg -i frame_%05d.png` expects.
+pub fn write_frames(
+    transition: &Transition,
+    dir: &Path,
+    options: &PngOptions,
+) -> io::Result<Vec<PathBuf>> {
+    let mut sink = PngSink::new(dir, options.clone())?;
+    transition.record(&mut sink, options.fps)?;
+    sink.finish()?;
+    Ok(sink.paths)
+}
+
+/// Writes every received frame to its own numbered PNG.
+pub struct PngSink {
+    dir: PathBuf,
+    options: PngOptions,
+    paths: Vec<PathBuf>,
+}
+
+impl PngSink {
+    /// Creates `dir` if needed.
+    pub fn new(dir: &Path, options: PngOptions) -> io::Result<Self> {
+        fs::create_dir_all(dir)?;
+
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            options,
+            paths: Vec::new(),
+        })
+    }
+
+    pub fn paths(&self) -> &[PathBuf] {
+        &self.paths
+    }
+}
+
+impl FrameSink for PngSink {
+    fn frame(&mut self, _t: f32, buf: &Buffer, _timestamp: Duration) -> io::Result<()> {
+        let path = self.dir.join(format!("frame_{:05}.png", self.paths.len()));
+        fs::write(&path, encode(buf, &self.options))?;
+        self.paths.push(path);
+        Ok(())
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Rasterize a single buffer to PNG bytes.
+pub fn encode(buf: &Buffer, options: &PngOptions) -> Vec<u8> {
+    let (width, height, pixels) = rasterize(buf, options);
+    encode_rgb(width, height, &pixels)
+}
+
+fn rasterize(buf: &Buffer, options: &PngOptions) -> (u32, u32, Vec<u8>) {
+    let scale = options.scale.max(1) as usize;
+    let area = buf.area();
+    let cell_w = GLYPH_W * scale;
+    let cell_h = GLYPH_H * scale;
+    let width = area.width as usize * cell_w;
+    let height = area.height as usize * cell_h;
+    let mut pixels = vec![0u8; width * height * 3];
+
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            let cell = &buf[(x, y)];
+            let (fg, bg) = export::resolve(cell, options.default_fg, options.default_bg);
+            let fg = if cell.modifier.contains(Modifier::DIM) {
+                blend(bg, fg, 153)
+            } else {
+                fg
+            };
+            let coverage = cell_coverage(cell.symbol(), cell.modifier);
+
+            let ox = (x - area.x) as usize * cell_w;
+            let oy = (y - area.y) as usize * cell_h;
+
+            for py in 0..cell_h {
+                for px in 0..cell_w {
+                    let a = coverage[(py / scale) * GLYPH_W + px / scale];
+                    let (r, g, b) = blend(bg, fg, a);
+                    let i = ((oy + py) * width + ox + px) * 3;
+                    pixels[i] = r;
+                    pixels[i + 1] = g;
+                    pixels[i + 2] = b;
+                }
+            }
+        }
+    }
+
+    (width as u32, height as u32, pixels)
+}
+
+fn cell_coverage(symbol: &str, modifier: Modifier) -> [u8; GLYPH_W * GLYPH_H] {
+    let mut coverage = [0u8; GLYPH_W * GLYPH_H];
+
+    if modifier.contains(Modifier::HIDDEN) {
+        return coverage;
+    }
+
+    if let Some(glyph) = symbol
+        .chars()
+        .next()
+        .and_then(|ch| glyph(ch).or(glyph('?')))
+    {
+        coverage.copy_from_slice(glyph);
+    }
+
+    // Faux bold: smear one pixel right.
+    if modifier.contains(Modifier::BOLD) {
+        for row in coverage.chunks_mut(GLYPH_W) {
+            for x in (1..GLYPH_W).rev() {
+                row[x] = row[x].max(row[x - 1]);
+            }
+        }
+    }
+
+    if modifier.contains(Modifier::UNDERLINED) {
+        coverage[(GLYPH_H - 2) * GLYPH_W..(GLYPH_H - 1) * GLYPH_W].fill(255);
+    }
+
+    if modifier.contains(Modifier::CROSSED_OUT) {
+        coverage[8 * GLYPH_W..9 * GLYPH_W].fill(255);
+    }
+
+    coverage
+}
+
+fn glyph(ch: char) -> Option<&'static [u8]> {
+    if ch == ' ' {
+        return None;
+    }
+
+    let cp = ch as u32;
+    let count = ATLAS.len() / RECORD;
+    let (mut lo, mut hi) = (0, count);
+
+    while lo < hi {
+        let mid = (lo + hi) / 2;
+        let rec = &ATLAS[mid * RECORD..(mid + 1) * RECORD];
+        let key = u32::from_le_bytes([rec[0], rec[1], rec[2], rec[3]]);
+
+        match key.cmp(&cp) {
+            std::cmp::Ordering::Less => lo = mid + 1,
+            std::cmp::Ordering::Greater => hi = mid,
+            std::cmp::Ordering::Equal => return Some(&rec[4..]),
+        }
+    }
+
+    None
+}
+
+/// `a` = 0 gives `bg`, 255 gives `fg`.
+fn blend(bg: Rgb, fg: Rgb, a: u8) -> Rgb {
+    let mix =
+        |b: u8, f: u8| ((b as u32 * (255 - a as u32) + f as u32 * a as u32 + 127) / 255) as u8;
+    (mix(bg.0, fg.0), mix(bg.1, fg.1), mix(bg.2, fg.2))
+}
+
+/// Minimal PNG: 8-bit RGB, unfiltered scanlines, stored (uncompressed)
+/// deflate blocks. Large, but trivially correct; ffmpeg recompresses anyway.
+fn encode_rgb(width: u32, height: u32, pixels: &[u8]) -> Vec<u8> {
+    let row = width as usize * 3;
+    let mut raw = Vec::with_capacity((row + 1) * height as usize);
+
+    for line in pixels.chunks(row.max(1)).take(height as usize) {
+        raw.push(0);
+        raw.extend_from_slice(line);
+    }
+
+    let mut out = Vec::with_capacity(raw.len() + raw.len() / 65535 * 5 + 64);
+    out.extend_from_slice(b"\x89PNG\r\n\x1a\n");
+
+    let mut ihdr = Vec::with_capacity(13);
+    ihdr.extend_from_slice(&width.to_be_bytes());
+    ihdr.extend_from_slice(&height.to_be_bytes());
+    ihdr.extend_from_slice(&[8, 2, 0, 0, 0]);
+    write_chunk(&mut out, b"IHDR", &ihdr);
+    write_chunk(&mut out, b"IDAT", &zlib_stored(&raw));
+    write_chunk(&mut out, b"IEND", &[]);
+
+    out
+}
+
+fn write_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = out.len();
+    out.extend_from_slice(kind);
+    out.extend_from_slice(data);
+    let crc = crc32(&out[start..]);
+    out.extend_from_slice(&crc.to_be_bytes());
+}
+
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = 65535;
+
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(MAX_BLOCK).peekable();
+
+    if blocks.peek().is_none() {
+        out.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let len = block.len() as u16;
+        out.push(last as u8);
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+
+    for &byte in data {
+        crc ^= byte as u32;
+
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+
+    for chunk in data.chunks(5552) {
+        for &byte in chunk {
+            a += byte as u32;
+            b += a;
+        }
+
+        a %= 65521;
+        b %= 65521;
+    }
+
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::layout::Rect;
+    use ratatui::style::{Color, Style};
+
+    use super::*;
+
+    #[test]
+    fn atlas_is_sorted_and_covers_ascii() {
+        let keys: Vec<u32> = ATLAS
+            .chunks(RECORD)
+            .map(|rec| u32::from_le_bytes([rec[0], rec[1], rec[2], rec[3]]))
+            .collect();
+
+        assert_eq!(ATLAS.len() % RECORD, 0);
+        assert!(keys.windows(2).all(|w| w[0] < w[1]));
+
+        for ch in '!'..='~' {
+            assert!(glyph(ch).is_some(), "missing {ch:?}");
+        }
+
+        assert!(glyph('─').is_some());
+        assert!(glyph('█').is_some());
+    }
+
+    #[test]
+    fn checksums_match_reference() {
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn encodes_expected_dimensions() {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 3, 2));
+        buf[(0, 0)]
+            .set_symbol("A")
+            .set_style(Style::new().fg(Color::White));
+
+        let png = encode(
+            &buf,
+            &PngOptions {
+                scale: 2,
+                ..PngOptions::default()
+            },
+        );
+
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(u32::from_be_bytes([png[16], png[17], png[18], png[19]]), 48);
+        assert_eq!(u32::from_be_bytes([png[20], png[21], png[22], png[23]]), 64);
+        assert_eq!(&png[png.len() - 8..png.len() - 4], b"IEND");
+    }
+
+    #[test]
+    fn full_block_fills_cell_with_fg() {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 1, 1));
+        buf[(0, 0)]
+            .set_symbol("█")
+            .set_style(Style::new().fg(Color::Rgb(10, 20, 30)));
+
+        let (_, _, pixels) = rasterize(&buf, &PngOptions::default());
+
+        assert!(pixels.chunks(3).all(|px| px == [10, 20, 30]));
+    }
+}
@@ -0,0 +1,299 @@
+use std::fmt::Write;
+use std::io;
+use std::time::Duration;
+
+use ratatui::buffer::Buffer;
+use ratatui::style::Modifier;
+
+use crate::export::{self, FrameSink, Rgb};
+use crate::transition::Transition;
+
+#[derive(Clone)]
+pub struct SvgOptions {
+    pub fps: u32,
+    /// How long the final frame stays up before the animation loops.
+    pub hold: Duration,
+    pub font_family: String,
+    pub font_size: f32,
+    pub cell_width: f32,
+    pub cell_height: f32,
+    /// Substituted for `Color::Reset` and colors without a fixed RGB value.
+    pub default_fg: Rgb,
+    pub default_bg: Rgb,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            fps: 30,
+            hold: Duration::from_secs(1),
+            font_family: "ui-monospace, Menlo, Consolas, monospace".to_string(),
+            font_size: 14.0,
+            cell_width: 8.4,
+            cell_height: 17.0,
+            default_fg: (204, 204, 204),
+            default_bg: (0, 0, 0),
+        }
+    }
+}
+
+/// Render `transition` as a self-contained, looping animated SVG.
+pub fn render(transition: &Transition, options: &SvgOptions) -> String {
+    let mut sink = SvgSink::new(Vec::new(), options.clone());
+
+    // Writing into a Vec cannot fail.
+    let _ = transition.record(&mut sink, options.fps);
+    let _ = sink.finish();
+
+    String::from_utf8(sink.into_inner()).unwrap_or_default()
+}
+
+/// Accumulates frames and writes one animated SVG document on `finish`.
+///
+/// Each frame is a `<g>` with its own `step-end` keyframe making it visible
+/// from its timestamp until the next frame's; the last frame is held for
+/// `hold`. Text stays as text so it scales crisply.
+pub struct SvgSink<W: io::Write> {
+    out: W,
+    options: SvgOptions,
+    size: (u16, u16),
+    frames: Vec<(Duration, String)>,
+}
+
+impl<W: io::Write> SvgSink<W> {
+    pub fn new(out: W, options: SvgOptions) -> Self {
+        Self {
+            out,
+            options,
+            size: (0, 0),
+            frames: Vec::new(),
+        }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
+
+impl<W: io::Write> FrameSink for SvgSink<W> {
+    fn frame(&mut self, _t: f32, buf: &Buffer, timestamp: Duration) -> io::Result<()> {
+        let mut body = String::new();
+        write_frame(&mut body, buf, &self.options);
+        self.size = (buf.area().width, buf.area().height);
+        self.frames.push((timestamp, body));
+        Ok(())
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        let Some(&(first, _)) = self.frames.first() else {
+            return Ok(());
+        };
+
+        let options = &self.options;
+        let last = self.frames.last().map_or(first, |(ts, _)| *ts);
+        let period = (last - first + options.hold).as_secs_f32().max(1e-3);
+        let width = self.size.0 as f32 * options.cell_width;
+        let height = self.size.1 as f32 * options.cell_height;
+
+        let mut out = String::new();
+
+        let _ = writeln!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+        );
+        let _ = writeln!(out, "<style>");
+        let _ = writeln!(
+            out,
+            "text {{ font-family: {}; font-size: {}px; white-space: pre; dominant-baseline: text-before-edge; }}",
+            escape(&options.font_family),
+            options.font_size,
+        );
+        let _ = writeln!(
+            out,
+            "g {{ visibility: hidden; animation: {period}s step-end infinite; }}"
+        );
+
+        for (i, (ts, _)) in self.frames.iter().enumerate() {
+            let start = (*ts - first).as_secs_f32() / period * 100.0;
+            let end = match self.frames.get(i + 1) {
+                Some((next, _)) => (*next - first).as_secs_f32() / period * 100.0,
+                None => 100.0,
+            };
+
+            let _ = writeln!(
+                out,
+                "@keyframes k{i} {{ 0% {{ visibility: hidden; }} {start:.4}% {{ visibility: visible; }} {end:.4}% {{ visibility: hidden; }} }}"
+            );
+        }
+
+        let _ = writeln!(out, "</style>");
+
+        let (r, g, b) = options.default_bg;
+        let _ = writeln!(
+            out,
+            r#"<rect width="100%" height="100%" fill="{}"/>"#,
+            hex(r, g, b)
+        );
+
+        for (i, (_, body)) in self.frames.iter().enumerate() {
+            let _ = writeln!(out, r#"<g style="animation-name:k{i}">"#);
+            out.push_str(body);
+            let _ = writeln!(out, "</g>");
+        }
+
+        out.push_str("</svg>\n");
+
+        self.frames.clear();
+        self.out.write_all(out.as_bytes())?;
+        self.out.flush()
+    }
+}
+
+/// Backgrounds as merged horizontal rects, then text as same-style runs.
+fn write_frame(out: &mut String, buf: &Buffer, options: &SvgOptions) {
+    let area = buf.area();
+
+    for y in area.top()..area.bottom() {
+        let py = (y - area.y) as f32 * options.cell_height;
+
+        let mut x = area.left();
+        while x < area.right() {
+            let (_, bg) = resolve(&buf[(x, y)], options);
+            let start = x;
+
+            while x < area.right() && resolve(&buf[(x, y)], options).1 == bg {
+                x += 1;
+            }
+
+            if bg != options.default_bg {
+                let _ = writeln!(
+                    out,
+                    r#"<rect x="{}" y="{py}" width="{}" height="{}" fill="{}"/>"#,
+                    (start - area.x) as f32 * options.cell_width,
+                    (x - start) as f32 * options.cell_width,
+                    options.cell_height,
+                    hex(bg.0, bg.1, bg.2),
+                );
+            }
+        }
+
+        let mut x = area.left();
+        while x < area.right() {
+            let cell = &buf[(x, y)];
+            let (fg, _) = resolve(cell, options);
+            let modifier = cell.modifier;
+            let start = x;
+            let mut text = String::new();
+
+            while x < area.right() {
+                let next = &buf[(x, y)];
+
+                if resolve(next, options).0 != fg || next.modifier != modifier {
+                    break;
+                }
+
+                text.push_str(next.symbol());
+                x += 1;
+            }
+
+            if text.trim().is_empty() || modifier.contains(Modifier::HIDDEN) {
+                continue;
+            }
+
+            let _ = writeln!(
+                out,
+                r#"<text x="{}" y="{py}" fill="{}"{}>{}</text>"#,
+                (start - area.x) as f32 * options.cell_width,
+                hex(fg.0, fg.1, fg.2),
+                text_attrs(modifier),
+                escape(&text),
+            );
+        }
+    }
+}
+
+fn resolve(cell: &ratatui::buffer::Cell, options: &SvgOptions) -> (Rgb, Rgb) {
+    export::resolve(cell, options.default_fg, options.default_bg)
+}
+
+fn text_attrs(modifier: Modifier) -> String {
+    let mut attrs = String::new();
+
+    if modifier.contains(Modifier::BOLD) {
+        attrs.push_str(r#" font-weight="bold""#);
+    }
+
+    if modifier.contains(Modifier::ITALIC) {
+        attrs.push_str(r#" font-style="italic""#);
+    }
+
+    if modifier.contains(Modifier::UNDERLINED) {
+        attrs.push_str(r#" text-decoration="underline""#);
+    }
+
+    if modifier.contains(Modifier::DIM) {
+        attrs.push_str(r#" opacity="0.6""#);
+    }
+
+    attrs
+}
+
+fn hex(r: u8, g: u8, b: u8) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::layout::Rect;
+    use ratatui::style::{Color, Style};
+
+    use crate::config::MorphConfig;
+
+    use super::*;
+
+    fn make_buffer(sym: &str, fg: Color) -> Buffer {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 3, 1));
+        buf[(0, 0)].set_symbol(sym).set_style(Style::new().fg(fg));
+        buf
+    }
+
+    #[test]
+    fn one_group_per_frame() {
+        let a = make_buffer("A", Color::Red);
+        let b = make_buffer("B", Color::Blue);
+        let config = MorphConfig {
+            duration: Duration::from_millis(100),
+            ..MorphConfig::default()
+        };
+        let transition = Transition::new(&a, &b, &config);
+        let options = SvgOptions {
+            fps: 10,
+            ..SvgOptions::default()
+        };
+
+        let svg = render(&transition, &options);
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches("<g style=").count(), 2);
+        assert!(svg.contains(">A</text>"));
+        assert!(svg.contains(">B</text>"));
+    }
+
+    #[test]
+    fn text_is_escaped() {
+        let a = make_buffer("<", Color::Red);
+        let transition = Transition::new(&a, &a, &MorphConfig::default());
+        let svg = render(&transition, &SvgOptions::default());
+
+        assert!(svg.contains("&lt;"));
+        assert!(!svg.contains("><</text>"));
+    }
+}
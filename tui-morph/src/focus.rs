@@ -0,0 +1,37 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether the terminal has focus, shared between `MorphBackend` and
+/// whatever reads terminal events.
+///
+/// While unfocused, transitions snap straight to their final frame —
+/// including one already in flight, so a clone of the handle can be
+/// flipped from an input thread mid-animation. Feed it from the
+/// terminal's focus reports, e.g. crossterm's `Event::FocusLost` /
+/// `Event::FocusGained` after `EnableFocusChange`.
+#[derive(Debug, Clone)]
+pub struct FocusHandle {
+    focused: Arc<AtomicBool>,
+}
+
+impl FocusHandle {
+    pub fn new() -> Self {
+        Self {
+            focused: Arc::new(AtomicBool::new(true)),
+        }
+    }
+
+    pub fn set_focused(&self, focused: bool) {
+        self.focused.store(focused, Ordering::Relaxed);
+    }
+
+    pub fn is_focused(&self) -> bool {
+        self.focused.load(Ordering::Relaxed)
+    }
+}
+
+impl Default for FocusHandle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
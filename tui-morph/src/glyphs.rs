@@ -0,0 +1,226 @@
+use std::ops::RangeInclusive;
+
+/// Glyph similarity, consulted by the solver's cost function.
+///
+/// Two glyphs either match (cost 0) or mismatch (cost 1, scaled by
+/// `MorphWeights::glyph_mismatch`) unless a class contains both. `new`
+/// starts from built-in classes of visually close shapes (dashes and
+/// rules, `O`/`0`, box-drawing weights, shade blocks, …); classes
+/// registered on top lower the cost further, so e.g. a bullet that
+/// changes from `•` to `◦` is treated as the same element moving.
+#[derive(Debug, Clone)]
+pub struct GlyphTable {
+    classes: Vec<GlyphClass>,
+    shapes: bool,
+}
+
+/// Built-in classes: members and their fraction of a full mismatch.
+const SHAPES: &[(&[&str], f32)] = &[
+    (
+        &[
+            "-", "‐", "‑", "–", "—", "―", "−", "─", "━", "╌", "╍", "┄", "┅", "┈", "┉", "═",
+        ],
+        0.2,
+    ),
+    (
+        &[
+            "|", "¦", "│", "┃", "╎", "╏", "┆", "┇", "┊", "┋", "║", "▏", "▕",
+        ],
+        0.2,
+    ),
+    (&["_", "▁", "⎽"], 0.2),
+    (&["O", "0", "o", "Ο", "○", "◯", "●", "⬤"], 0.3),
+    (&["l", "1", "I", "i", "!"], 0.5),
+    (
+        &["•", "◦", "·", "∙", "⋅", "*", "∗", "⁃", "‣", "▪", "▫"],
+        0.3,
+    ),
+    (&["'", "`", "´", "‘", "’"], 0.3),
+    (&["\"", "“", "”", "„"], 0.3),
+    (&[">", "›", "»", "→", "▶", "▸", "►", "❯"], 0.4),
+    (&["<", "‹", "«", "←", "◀", "◂", "◄", "❮"], 0.4),
+    (&["v", "˅", "↓", "▼", "▾"], 0.4),
+    (&["^", "˄", "↑", "▲", "▴"], 0.4),
+    (&["x", "×", "✕", "✗", "✘"], 0.4),
+    (&["✓", "✔", "√"], 0.3),
+    (&["┌", "╭", "┏", "╔", "┍", "┎", "╒", "╓"], 0.2),
+    (&["┐", "╮", "┓", "╗", "┑", "┒", "╕", "╖"], 0.2),
+    (&["└", "╰", "┗", "╚", "┕", "┖", "╘", "╙"], 0.2),
+    (&["┘", "╯", "┛", "╝", "┙", "┚", "╛", "╜"], 0.2),
+    (&["├", "┣", "╠", "┝", "┠", "╞", "╟"], 0.2),
+    (&["┤", "┫", "╣", "┥", "┨", "╡", "╢"], 0.2),
+    (&["┬", "┳", "╦", "┯", "┰", "╤", "╥"], 0.2),
+    (&["┴", "┻", "╩", "┷", "┸", "╧", "╨"], 0.2),
+    (&["┼", "╋", "╬", "┿", "╂", "╪", "╫"], 0.2),
+    (&["░", "▒", "▓", "█"], 0.3),
+    (&["▁", "▂", "▃", "▄", "▅", "▆", "▇", "█"], 0.3),
+    (&["▏", "▎", "▍", "▌", "▋", "▊", "▉", "█"], 0.3),
+    (&["⠀", "⠁", "⠂", "⠄", "⡀", "⢀", "⠠", "⠐", "⠈"], 0.4),
+];
+
+/// Any two box-drawing characters share strokes.
+const BOX_DRAWING: (RangeInclusive<char>, f32) = ('\u{2500}'..='\u{257f}', 0.6);
+
+/// Any two braille patterns are dot grids.
+const BRAILLE: (RangeInclusive<char>, f32) = ('\u{2800}'..='\u{28ff}', 0.6);
+
+#[derive(Debug, Clone)]
+struct GlyphClass {
+    members: Members,
+    /// Fraction of a full mismatch, `0.0` = identical.
+    cost: f32,
+}
+
+#[derive(Debug, Clone)]
+enum Members {
+    Set(Vec<String>),
+    Range(RangeInclusive<char>),
+}
+
+impl Members {
+    fn contains(&self, glyph: &str) -> bool {
+        match self {
+            Self::Set(set) => set.iter().any(|g| g == glyph),
+            Self::Range(range) => {
+                let mut chars = glyph.chars();
+                matches!((chars.next(), chars.next()), (Some(c), None) if range.contains(&c))
+            }
+        }
+    }
+}
+
+impl Default for GlyphTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GlyphTable {
+    /// The built-in shape classes and nothing else.
+    pub const fn new() -> Self {
+        Self {
+            classes: Vec::new(),
+            shapes: true,
+        }
+    }
+
+    /// No classes at all: every pair of distinct glyphs is a full
+    /// mismatch.
+    pub const fn binary() -> Self {
+        Self {
+            classes: Vec::new(),
+            shapes: false,
+        }
+    }
+
+    /// Treat every glyph in `glyphs` as similar to every other, at `cost`
+    /// (clamped to `[0, 1]`) of a full mismatch.
+    pub fn equivalent<I, S>(mut self, glyphs: I, cost: f32) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.classes.push(GlyphClass {
+            members: Members::Set(glyphs.into_iter().map(Into::into).collect()),
+            cost: cost.clamp(0.0, 1.0),
+        });
+        self
+    }
+
+    /// Treat all single-codepoint glyphs in `range` as similar — useful
+    /// for icon fonts that occupy a private-use block.
+    pub fn codepoint_range(mut self, range: RangeInclusive<char>, cost: f32) -> Self {
+        self.classes.push(GlyphClass {
+            members: Members::Range(range),
+            cost: cost.clamp(0.0, 1.0),
+        });
+        self
+    }
+
+    /// Mismatch between two glyphs in `[0, 1]`. The cheapest class
+    /// containing both wins.
+    pub fn mismatch(&self, a: &str, b: &str) -> f32 {
+        if a == b {
+            return 0.0;
+        }
+
+        let registered = self
+            .classes
+            .iter()
+            .filter(|class| class.members.contains(a) && class.members.contains(b))
+            .map(|class| class.cost)
+            .fold(1.0, f32::min);
+
+        if self.shapes {
+            registered.min(shape_mismatch(a, b))
+        } else {
+            registered
+        }
+    }
+}
+
+fn shape_mismatch(a: &str, b: &str) -> f32 {
+    let in_range = |(range, cost): &(RangeInclusive<char>, f32)| {
+        let range = Members::Range(range.clone());
+        (range.contains(a) && range.contains(b)).then_some(*cost)
+    };
+
+    SHAPES
+        .iter()
+        .filter(|(members, _)| members.contains(&a) && members.contains(&b))
+        .map(|&(_, cost)| cost)
+        .chain(in_range(&BOX_DRAWING))
+        .chain(in_range(&BRAILLE))
+        .fold(1.0, f32::min)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binary_has_no_classes() {
+        let table = GlyphTable::binary();
+
+        assert_eq!(table.mismatch("a", "a"), 0.0);
+        assert_eq!(table.mismatch("a", "b"), 1.0);
+        assert_eq!(table.mismatch("-", "─"), 1.0);
+    }
+
+    #[test]
+    fn shapes_rank_close_glyphs() {
+        let table = GlyphTable::new();
+
+        assert_eq!(table.mismatch("a", "b"), 1.0);
+        assert_eq!(table.mismatch("-", "─"), 0.2);
+        assert_eq!(table.mismatch("O", "0"), 0.3);
+        assert_eq!(table.mismatch("┌", "╔"), 0.2);
+        assert_eq!(table.mismatch("┌", "┘"), 0.6);
+        assert_eq!(table.mismatch("░", "▓"), 0.3);
+    }
+
+    #[test]
+    fn equivalence_class_lowers_cost() {
+        let table = GlyphTable::binary().equivalent(["•", "◦", "*"], 0.1);
+
+        assert_eq!(table.mismatch("•", "*"), 0.1);
+        assert_eq!(table.mismatch("•", "x"), 1.0);
+        assert_eq!(
+            GlyphTable::new()
+                .equivalent(["•", "*"], 0.1)
+                .mismatch("•", "◦"),
+            0.3
+        );
+    }
+
+    #[test]
+    fn cheapest_class_wins() {
+        let table = GlyphTable::binary()
+            .codepoint_range('\u{e000}'..='\u{f8ff}', 0.5)
+            .equivalent(["\u{e0a0}", "\u{e0a1}"], 0.0);
+
+        assert_eq!(table.mismatch("\u{e0a0}", "\u{e0a1}"), 0.0);
+        assert_eq!(table.mismatch("\u{e000}", "\u{e0a1}"), 0.5);
+        assert_eq!(table.mismatch("\u{e000}x", "\u{e0a1}"), 1.0);
+    }
+}
@@ -0,0 +1,126 @@
+use ratatui::buffer::{Buffer, Cell};
+
+/// Kitty unicode placeholder (U+10EEEE). Row/column diacritics follow it
+/// in the same cell, and the fg color encodes the image id.
+pub const KITTY_PLACEHOLDER: char = '\u{10EEEE}';
+
+/// Whether `cell` belongs to an inline image.
+///
+/// Covers skip-flagged cells (Sixel/iTerm2 images span cells the widget
+/// marks as skipped), Kitty placeholders, and cells whose symbol carries a
+/// raw escape sequence (the anchor cell of a Sixel or iTerm2 image).
+pub fn is_placeholder(cell: &Cell) -> bool {
+    let symbol = cell.symbol();
+    cell.skip || symbol.starts_with(KITTY_PLACEHOLDER) || symbol.starts_with('\x1b')
+}
+
+/// Image placeholder cells held out of a transition.
+///
+/// The terminal keeps showing whatever it last drew at these positions
+/// until the final frame, which re-emits the target's cells verbatim.
+#[derive(Debug, Clone, Default)]
+pub struct Passthrough {
+    cells: Vec<(u16, u16, Cell)>,
+}
+
+impl Passthrough {
+    /// Positions that are placeholders in either frame, with the cells
+    /// `next` has there.
+    pub fn detect(prev: &Buffer, next: &Buffer) -> Self {
+        let area = next.area;
+        let mut cells = Vec::new();
+
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                let target = &next[(x, y)];
+                let in_prev = prev.cell((x, y)).is_some_and(is_placeholder);
+
+                if in_prev || is_placeholder(target) {
+                    cells.push((x, y, target.clone()));
+                }
+            }
+        }
+
+        Self { cells }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// `buf` with every held position blanked, for the solver.
+    pub fn mask(&self, buf: &Buffer) -> Buffer {
+        let mut out = buf.clone();
+
+        for (x, y, _) in &self.cells {
+            if let Some(cell) = out.cell_mut((*x, *y)) {
+                cell.reset();
+            }
+        }
+
+        out
+    }
+
+    /// Make held positions in an intermediate `frame` repeat what is on
+    /// screen, flagged so the diff leaves them alone.
+    pub fn hold(&self, frame: &mut Buffer, shown: &Buffer) {
+        for (x, y, _) in &self.cells {
+            if let (Some(cell), Some(on_screen)) = (frame.cell_mut((*x, *y)), shown.cell((*x, *y)))
+            {
+                *cell = on_screen.clone();
+                cell.skip = true;
+            }
+        }
+    }
+
+    /// Put the target's placeholder cells back, for the final frame.
+    pub fn restore(&self, frame: &mut Buffer) {
+        for (x, y, target) in &self.cells {
+            if let Some(cell) = frame.cell_mut((*x, *y)) {
+                *cell = target.clone();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::layout::Rect;
+
+    use super::*;
+
+    fn with_kitty(x: u16) -> Buffer {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 4, 1));
+        buf[(x, 0)].set_symbol("\u{10EEEE}\u{305}\u{305}");
+        buf
+    }
+
+    #[test]
+    fn detects_placeholders_in_either_frame() {
+        let mut sixel = Buffer::empty(Rect::new(0, 0, 4, 1));
+        sixel[(3, 0)].set_skip(true);
+
+        let passthrough = Passthrough::detect(&with_kitty(0), &sixel);
+        let positions: Vec<(u16, u16)> =
+            passthrough.cells.iter().map(|(x, y, _)| (*x, *y)).collect();
+
+        assert_eq!(positions, vec![(0, 0), (3, 0)]);
+    }
+
+    #[test]
+    fn hold_then_restore() {
+        let prev = with_kitty(1);
+        let next = with_kitty(1);
+        let passthrough = Passthrough::detect(&prev, &next);
+
+        assert_eq!(passthrough.mask(&next)[(1, 0)].symbol(), " ");
+
+        let mut frame = Buffer::empty(prev.area);
+        passthrough.hold(&mut frame, &prev);
+        assert!(frame[(1, 0)].skip);
+        assert!(prev.diff(&frame).is_empty());
+
+        passthrough.restore(&mut frame);
+        assert_eq!(frame[(1, 0)], next[(1, 0)]);
+    }
+}
@@ -0,0 +1,175 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::time::SystemTime;
+
+use crate::metrics::MorphMetrics;
+use crate::plan::InterpolationPlan;
+
+/// How much transition history `MorphBackend` keeps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HistoryOptions {
+    /// Transitions remembered; `0` disables the history.
+    pub capacity: usize,
+    /// Also keep each transition's plan, minus its stable cells.
+    pub plans: bool,
+}
+
+impl HistoryOptions {
+    pub const DEFAULT: Self = Self {
+        capacity: 32,
+        plans: false,
+    };
+
+    pub const DISABLED: Self = Self {
+        capacity: 0,
+        plans: false,
+    };
+}
+
+impl Default for HistoryOptions {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// One remembered transition.
+#[derive(Clone)]
+pub struct HistoryEntry {
+    /// When the transition finished.
+    pub at: SystemTime,
+    pub metrics: MorphMetrics,
+    /// Present when `HistoryOptions::plans` was set. Stable cells are
+    /// dropped; `metrics.plan` still counts them.
+    pub plan: Option<InterpolationPlan>,
+}
+
+/// Ring buffer of recent transitions, oldest first, for post-hoc
+/// diagnostics. `Display` prints one line per entry.
+#[derive(Clone, Default)]
+pub struct MorphHistory {
+    entries: VecDeque<HistoryEntry>,
+}
+
+impl MorphHistory {
+    #[cfg_attr(feature = "disabled", allow(dead_code))]
+    pub(crate) fn record(
+        &mut self,
+        options: HistoryOptions,
+        metrics: MorphMetrics,
+        plan: &InterpolationPlan,
+    ) {
+        if options.capacity == 0 {
+            self.entries.clear();
+            return;
+        }
+
+        while self.entries.len() >= options.capacity {
+            self.entries.pop_front();
+        }
+
+        self.entries.push_back(HistoryEntry {
+            at: SystemTime::now(),
+            metrics,
+            plan: options.plans.then(|| InterpolationPlan {
+                stable: Vec::new(),
+                ..plan.clone()
+            }),
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &HistoryEntry> {
+        self.entries.iter()
+    }
+
+    pub fn latest(&self) -> Option<&HistoryEntry> {
+        self.entries.back()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+impl fmt::Display for MorphHistory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in &self.entries {
+            let m = &entry.metrics;
+            let ago = entry.at.elapsed().unwrap_or_default();
+
+            writeln!(
+                f,
+                "#{} {:.1}s ago: solve {:?}, {} frames ({} dropped) in {:?} of {:?}, {:.1}/{} fps, \
+                 {} mutating {} displaced {} appearing {} disappearing",
+                m.transitions,
+                ago.as_secs_f32(),
+                m.solve_time,
+                m.frames,
+                m.dropped_frames,
+                m.elapsed,
+                m.duration,
+                m.achieved_fps(),
+                m.target_fps,
+                m.plan.mutating,
+                m.plan.displaced,
+                m.plan.appearing,
+                m.plan.disappearing,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plan() -> InterpolationPlan {
+        InterpolationPlan {
+            origin: ratatui::layout::Position::ORIGIN,
+            width: 1,
+            height: 1,
+            stable: Vec::new(),
+            mutating: Vec::new(),
+            displaced: Vec::new(),
+            appearing: Vec::new(),
+            disappearing: Vec::new(),
+            skipped: Vec::new(),
+            numbers: Vec::new(),
+            borders: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn keeps_the_newest_entries() {
+        let mut history = MorphHistory::default();
+        let options = HistoryOptions {
+            capacity: 2,
+            plans: true,
+        };
+
+        for n in 1..=3 {
+            let metrics = MorphMetrics {
+                transitions: n,
+                ..Default::default()
+            };
+            history.record(options, metrics, &plan());
+        }
+
+        let kept: Vec<u64> = history.iter().map(|e| e.metrics.transitions).collect();
+        assert_eq!(kept, vec![2, 3]);
+        assert!(history.latest().is_some_and(|e| e.plan.is_some()));
+        assert_eq!(history.to_string().lines().count(), 2);
+
+        history.record(HistoryOptions::DISABLED, MorphMetrics::default(), &plan());
+        assert!(history.is_empty());
+    }
+}
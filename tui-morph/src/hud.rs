@@ -0,0 +1,105 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Paragraph, Widget};
+
+use crate::metrics::MorphMetrics;
+
+/// Compact live readout of `MorphBackend::metrics`, for a screen corner.
+///
+/// Plan line: `~` mutating, `>` displaced, `+` appearing, `-` disappearing.
+pub struct MorphHud<'a> {
+    metrics: &'a MorphMetrics,
+    style: Style,
+}
+
+impl<'a> MorphHud<'a> {
+    pub const WIDTH: u16 = 28;
+    pub const HEIGHT: u16 = 6;
+
+    pub fn new(metrics: &'a MorphMetrics) -> Self {
+        Self {
+            metrics,
+            style: Style::new().fg(Color::Gray).bg(Color::Black),
+        }
+    }
+
+    pub fn style(self, style: Style) -> Self {
+        Self { style, ..self }
+    }
+
+    /// Top-right corner of `area`, sized to fit the HUD.
+    pub fn corner(area: Rect) -> Rect {
+        let width = Self::WIDTH.min(area.width);
+        let height = Self::HEIGHT.min(area.height);
+
+        Rect::new(area.right() - width, area.y, width, height)
+    }
+}
+
+impl Widget for MorphHud<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let m = self.metrics;
+        let p = &m.plan;
+
+        let lines = vec![
+            Line::from(format!(
+                "solve {:>7.2}ms  #{}",
+                m.solve_time.as_secs_f64() * 1000.0,
+                m.transitions
+            )),
+            Line::from(format!(
+                "plan  ~{} >{} +{} -{}",
+                p.mutating, p.displaced, p.appearing, p.disappearing
+            )),
+            Line::from(format!(
+                "fps   {:>5.1} / {}",
+                m.achieved_fps(),
+                m.target_fps
+            )),
+            Line::from(format!(
+                "drop  {} of {}",
+                m.dropped_frames,
+                m.frames + m.dropped_frames
+            )),
+        ];
+
+        Paragraph::new(lines)
+            .block(Block::bordered().title(" morph "))
+            .style(self.style)
+            .render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_metrics() {
+        let metrics = MorphMetrics {
+            frames: 10,
+            dropped_frames: 3,
+            target_fps: 60,
+            ..MorphMetrics::default()
+        };
+        let area = Rect::new(0, 0, MorphHud::WIDTH, MorphHud::HEIGHT);
+        let mut buf = Buffer::empty(area);
+
+        MorphHud::new(&metrics).render(area, &mut buf);
+
+        let text: String = buf.content().iter().map(|c| c.symbol()).collect();
+        assert!(text.contains("drop  3 of 13"), "{text}");
+        assert!(text.contains("/ 60"));
+    }
+
+    #[test]
+    fn corner_hugs_top_right() {
+        let rect = MorphHud::corner(Rect::new(0, 0, 80, 24));
+
+        assert_eq!(rect.right(), 80);
+        assert_eq!(rect.y, 0);
+        assert_eq!(rect.width, MorphHud::WIDTH);
+    }
+}
@@ -1,22 +1,236 @@
+use std::collections::HashMap;
+
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
-use ratatui::style::Style;
+use ratatui::style::{Color, Style};
 
-use crate::oklch::{self, Oklch};
+use crate::boxdraw;
+use crate::modifiers::ModifierRules;
+use crate::oklch::{self, HueMode, Oklch};
 use crate::plan::{ColorPair, InterpolationPlan};
 
 const LEGIBILITY_THRESHOLD: f32 = 0.15;
 
 /// `t` must be in `[0.0, 1.0]`.
 pub fn render(plan: &InterpolationPlan, t: f32) -> Buffer {
-    let area = Rect::new(0, 0, plan.width, plan.height);
+    render_with(plan, t, &RenderOptions::default())
+}
+
+/// Which plan categories animate. A disabled category snaps to its
+/// target on the first frame after `t = 0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Animate {
+    /// In-place glyph and color changes.
+    pub mutating: bool,
+    pub displaced: bool,
+    /// Appearing and disappearing cells.
+    pub orphans: bool,
+}
+
+impl Animate {
+    pub const ALL: Self = Self {
+        mutating: true,
+        displaced: true,
+        orphans: true,
+    };
+
+    /// Progress to render a category at.
+    fn t(enabled: bool, t: f32) -> f32 {
+        if enabled || t <= 0.0 { t } else { 1.0 }
+    }
+}
+
+impl Default for Animate {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+/// Which colors intermediate frames may use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorMode {
+    /// `Grayscale` when the `NO_COLOR` environment variable is set and
+    /// non-empty, `Full` otherwise.
+    #[default]
+    Auto,
+    Full,
+    /// Intermediate colors are gray lightness ramps; only the app's own
+    /// colors appear, and only on the endpoint frames.
+    Grayscale,
+    /// Intermediate colors snap to the nearest of the 16 named ANSI
+    /// colors, for terminals without truecolor.
+    Ansi16,
+}
+
+impl ColorMode {
+    /// Settle `Auto` against the environment.
+    pub fn resolve(self) -> Self {
+        match self {
+            Self::Auto if std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) => {
+                Self::Grayscale
+            }
+            Self::Auto => Self::Full,
+            mode => mode,
+        }
+    }
+}
+
+/// Reading direction of the app's text. Directional motion defaults to
+/// running with it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextDirection {
+    #[default]
+    LeftToRight,
+    /// Hebrew, Arabic.
+    RightToLeft,
+}
+
+impl TextDirection {
+    /// The sweep that reads in this direction.
+    pub fn sweep(self) -> SweepDirection {
+        match self {
+            Self::LeftToRight => SweepDirection::LeftToRight,
+            Self::RightToLeft => SweepDirection::RightToLeft,
+        }
+    }
+}
+
+/// Direction a background sweep travels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SweepDirection {
+    LeftToRight,
+    RightToLeft,
+    TopToBottom,
+    BottomToTop,
+}
+
+/// Paints large background changes as a gradient front crossing the
+/// region instead of fading every cell at once.
+///
+/// A region is every mutating cell sharing the same source and target
+/// background; only regions of at least `min_cells` sweep.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BgSweep {
+    /// `None` runs with the text direction.
+    pub direction: Option<SweepDirection>,
+    /// Width of the gradient front as a fraction of the region, `(0, 1]`.
+    /// `1.0` degenerates to a uniform fade.
+    pub front: f32,
+    pub min_cells: usize,
+}
+
+impl BgSweep {
+    /// Sweeps the way the text reads; also the `Default`.
+    pub const ALONG_TEXT: Self = Self {
+        direction: None,
+        front: 0.3,
+        min_cells: 24,
+    };
+
+    pub const fn new(direction: SweepDirection) -> Self {
+        Self {
+            direction: Some(direction),
+            ..Self::ALONG_TEXT
+        }
+    }
+
+    /// Progress of the cell at `(x, y)` within `region`.
+    fn t(&self, region: Rect, x: u16, y: u16, t: f32, text: TextDirection) -> f32 {
+        let along = |pos: u16, start: u16, len: u16| {
+            if len <= 1 {
+                0.0
+            } else {
+                f32::from(pos - start) / f32::from(len - 1)
+            }
+        };
+
+        let pos = match self.direction.unwrap_or(text.sweep()) {
+            SweepDirection::LeftToRight => along(x, region.x, region.width),
+            SweepDirection::RightToLeft => 1.0 - along(x, region.x, region.width),
+            SweepDirection::TopToBottom => along(y, region.y, region.height),
+            SweepDirection::BottomToTop => 1.0 - along(y, region.y, region.height),
+        };
+
+        let front = self.front.clamp(0.01, 1.0);
+        ((t - pos * (1.0 - front)) / front).clamp(0.0, 1.0)
+    }
+}
+
+impl Default for BgSweep {
+    fn default() -> Self {
+        Self::ALONG_TEXT
+    }
+}
+
+/// Interpolator knobs beyond the plan itself.
+#[derive(Debug, Clone, Default)]
+pub struct RenderOptions {
+    pub modifiers: ModifierRules,
+    pub hue: HueMode,
+    pub animate: Animate,
+    pub color: ColorMode,
+    pub bg_sweep: Option<BgSweep>,
+    /// Direction for a `bg_sweep` that doesn't set its own.
+    pub text_direction: TextDirection,
+}
+
+/// Color settings resolved once per frame.
+#[derive(Clone, Copy)]
+struct Paint {
+    hue: HueMode,
+    mode: ColorMode,
+}
+
+/// Progress per plan category, already eased; see `CategoryEasing`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Progress {
+    /// In-place changes and counting numbers.
+    pub mutating: f32,
+    /// Moving glyphs and stretching outlines.
+    pub displaced: f32,
+    pub orphans: f32,
+}
+
+impl Progress {
+    pub const fn uniform(t: f32) -> Self {
+        Self {
+            mutating: t,
+            displaced: t,
+            orphans: t,
+        }
+    }
+}
+
+/// `render` with non-default options.
+pub fn render_with(plan: &InterpolationPlan, t: f32, options: &RenderOptions) -> Buffer {
+    render_at(plan, Progress::uniform(t), options)
+}
+
+/// `render_with`, each category at its own progress.
+pub fn render_at(plan: &InterpolationPlan, progress: Progress, options: &RenderOptions) -> Buffer {
+    let area = plan.area();
     let mut buf = Buffer::empty(area);
 
+    let animate = options.animate;
+    let mutating_t = Animate::t(animate.mutating, progress.mutating);
+    let displaced_t = Animate::t(animate.displaced, progress.displaced);
+    let orphan_t = Animate::t(animate.orphans, progress.orphans);
+    let paint = Paint {
+        hue: options.hue,
+        mode: options.color.resolve(),
+    };
+
     render_stable(plan, &mut buf);
-    render_mutating(plan, t, &mut buf);
-    render_displaced(plan, t, &mut buf);
-    render_appearing(plan, t, &mut buf);
-    render_disappearing(plan, t, &mut buf);
+    render_mutating(plan, mutating_t, options, paint, &mut buf);
+    render_numbers(plan, mutating_t, &mut buf);
+    render_displaced(plan, displaced_t, options, paint, &mut buf);
+    render_appearing(plan, orphan_t, paint, &mut buf);
+    render_disappearing(plan, orphan_t, paint, &mut buf);
+    render_borders(plan, displaced_t, paint, &mut buf);
+
+    for &(x, y) in &plan.skipped {
+        buf[(x, y)].skip = true;
+    }
 
     buf
 }
@@ -30,54 +244,161 @@ fn render_stable(plan: &InterpolationPlan, buf: &mut Buffer) {
     }
 }
 
-fn render_mutating(plan: &InterpolationPlan, t: f32, buf: &mut Buffer) {
+fn render_mutating(
+    plan: &InterpolationPlan,
+    t: f32,
+    options: &RenderOptions,
+    paint: Paint,
+    buf: &mut Buffer,
+) {
+    let regions = options
+        .bg_sweep
+        .map(|sweep| sweep_regions(plan, sweep.min_cells))
+        .unwrap_or_default();
+
     for cell in &plan.mutating {
-        let fg = lerp_color(&cell.src_fg, &cell.dst_fg, t);
-        let bg = lerp_color(&cell.src_bg, &cell.dst_bg, t);
-        let symbol = pick_symbol(&cell.src_symbol, &cell.dst_symbol, &cell.src_fg, t);
-        let modifier = if t < 0.5 {
-            cell.src_modifier
-        } else {
-            cell.dst_modifier
+        let bg_t = match (
+            options.bg_sweep,
+            regions.get(&(cell.src_bg.raw, cell.dst_bg.raw)),
+        ) {
+            (Some(sweep), Some(region)) => {
+                sweep.t(*region, cell.x, cell.y, t, options.text_direction)
+            }
+            _ => t,
         };
 
+        let fg = lerp_color(&cell.src_fg, &cell.dst_fg, t, paint);
+        let bg = lerp_color(&cell.src_bg, &cell.dst_bg, bg_t, paint);
+        let symbol = pick_symbol(&cell.src_symbol, &cell.dst_symbol, &cell.src_fg, t);
+        let modifier = options
+            .modifiers
+            .resolve(cell.src_modifier, cell.dst_modifier, t, false);
+
         let target = &mut buf[(cell.x, cell.y)];
-        target.set_symbol(&symbol);
+        target.set_symbol(symbol);
         target.set_style(Style::new().fg(fg).bg(bg));
         target.modifier = modifier;
     }
 }
 
-fn render_displaced(plan: &InterpolationPlan, t: f32, buf: &mut Buffer) {
+/// Bounding box of each background change covering at least `min_cells`
+/// mutating cells, keyed by `(src, dst)` background.
+fn sweep_regions(plan: &InterpolationPlan, min_cells: usize) -> HashMap<(Color, Color), Rect> {
+    let mut groups: HashMap<(Color, Color), (Rect, usize)> = HashMap::new();
+
+    for cell in &plan.mutating {
+        let key = (cell.src_bg.raw, cell.dst_bg.raw);
+
+        if key.0 == key.1 {
+            continue;
+        }
+
+        let here = Rect::new(cell.x, cell.y, 1, 1);
+        let (area, count) = groups.entry(key).or_insert((here, 0));
+        *area = area.union(here);
+        *count += 1;
+    }
+
+    groups
+        .into_iter()
+        .filter(|(_, (_, count))| *count >= min_cells.max(1))
+        .map(|(key, (area, _))| (key, area))
+        .collect()
+}
+
+fn render_numbers(plan: &InterpolationPlan, t: f32, buf: &mut Buffer) {
+    for number in &plan.numbers {
+        let text = number.text(t);
+        let width = usize::from(number.width);
+        let len = text.chars().count().min(width);
+        let skip = if number.right_aligned { width - len } else { 0 };
+
+        // Cells the text doesn't reach keep their morphing glyph.
+        for (x, c) in (number.x..).skip(skip).zip(text.chars().take(len)) {
+            buf[(x, number.y)].set_char(c);
+        }
+    }
+}
+
+fn render_borders(plan: &InterpolationPlan, t: f32, paint: Paint, buf: &mut Buffer) {
+    for border in &plan.borders {
+        let (from, to) = (border.from, border.to);
+        let left = lerp_pos(from.x, to.x, t);
+        let top = lerp_pos(from.y, to.y, t);
+        let right = lerp_pos(from.right() - 1, to.right() - 1, t);
+        let bottom = lerp_pos(from.bottom() - 1, to.bottom() - 1, t);
+
+        if right <= left || bottom <= top {
+            continue;
+        }
+
+        let style = if t < 0.5 { &border.src } else { &border.dst };
+        let fg = lerp_color(&border.src.fg, &border.dst.fg, t, paint);
+        let [top_left, top_right, bottom_left, bottom_right] = &style.corners;
+        let mut paint_at = |x: u16, y: u16, symbol: &str| {
+            if plan.area().contains((x, y).into()) {
+                let target = &mut buf[(x, y)];
+                target.set_symbol(symbol);
+                target.fg = fg;
+                target.modifier = style.modifier;
+            }
+        };
+
+        paint_at(left, top, top_left);
+        paint_at(right, top, top_right);
+        paint_at(left, bottom, bottom_left);
+        paint_at(right, bottom, bottom_right);
+
+        for x in left + 1..right {
+            if !style.top_gaps.contains(&(x - left)) {
+                paint_at(x, top, &style.horizontal);
+            }
+            if !style.bottom_gaps.contains(&(x - left)) {
+                paint_at(x, bottom, &style.horizontal);
+            }
+        }
+
+        for y in top + 1..bottom {
+            paint_at(left, y, &style.vertical);
+            paint_at(right, y, &style.vertical);
+        }
+    }
+}
+
+fn render_displaced(
+    plan: &InterpolationPlan,
+    t: f32,
+    options: &RenderOptions,
+    paint: Paint,
+    buf: &mut Buffer,
+) {
     for cell in &plan.displaced {
         let x = lerp_pos(cell.src_x, cell.dst_x, t);
         let y = lerp_pos(cell.src_y, cell.dst_y, t);
 
-        if x >= plan.width || y >= plan.height {
+        if !plan.area().contains((x, y).into()) {
             continue;
         }
 
-        let fg = lerp_color(&cell.src_fg, &cell.dst_fg, t);
-        let bg = lerp_color(&cell.src_bg, &cell.dst_bg, t);
+        let fg = lerp_color(&cell.src_fg, &cell.dst_fg, t, paint);
+        let bg = lerp_color(&cell.src_bg, &cell.dst_bg, t, paint);
         let symbol = pick_symbol(&cell.src_symbol, &cell.dst_symbol, &cell.src_fg, t);
-        let modifier = if t < 0.5 {
-            cell.src_modifier
-        } else {
-            cell.dst_modifier
-        };
+        let modifier = options
+            .modifiers
+            .resolve(cell.src_modifier, cell.dst_modifier, t, true);
 
         let target = &mut buf[(x, y)];
-        target.set_symbol(&symbol);
+        target.set_symbol(symbol);
         target.set_style(Style::new().fg(fg).bg(bg));
         target.modifier = modifier;
     }
 }
 
-fn render_appearing(plan: &InterpolationPlan, t: f32, buf: &mut Buffer) {
+fn render_appearing(plan: &InterpolationPlan, t: f32, paint: Paint, buf: &mut Buffer) {
     for cell in &plan.appearing {
         let factor = t;
-        let fg = fade(&cell.fg, factor);
-        let bg = lerp_color(&cell.counter_bg, &cell.bg, t);
+        let fg = fade(&cell.fg, factor, paint);
+        let bg = lerp_color(&cell.counter_bg, &cell.bg, t, paint);
 
         let visible = cell
             .fg
@@ -95,11 +416,11 @@ fn render_appearing(plan: &InterpolationPlan, t: f32, buf: &mut Buffer) {
     }
 }
 
-fn render_disappearing(plan: &InterpolationPlan, t: f32, buf: &mut Buffer) {
+fn render_disappearing(plan: &InterpolationPlan, t: f32, paint: Paint, buf: &mut Buffer) {
     for cell in &plan.disappearing {
         let factor = 1.0 - t;
-        let fg = fade(&cell.fg, factor);
-        let bg = lerp_color(&cell.bg, &cell.counter_bg, t);
+        let fg = fade(&cell.fg, factor, paint);
+        let bg = lerp_color(&cell.bg, &cell.counter_bg, t, paint);
 
         let visible = cell
             .fg
@@ -117,20 +438,41 @@ fn render_disappearing(plan: &InterpolationPlan, t: f32, buf: &mut Buffer) {
     }
 }
 
-fn lerp_color(src: &ColorPair, dst: &ColorPair, t: f32) -> ratatui::style::Color {
+fn lerp_color(src: &ColorPair, dst: &ColorPair, t: f32, paint: Paint) -> ratatui::style::Color {
     match (src.oklch, dst.oklch) {
-        (Some(a), Some(b)) => oklch::to_color(oklch::lerp(a, b, t)),
+        _ if paint.restricted() && t <= 0.0 => src.raw,
+        _ if paint.restricted() && t >= 1.0 => dst.raw,
+        (Some(a), Some(b)) => paint.color(oklch::lerp_with(a, b, t, paint.hue)),
         _ if t < 0.5 => src.raw,
         _ => dst.raw,
     }
 }
 
+impl Paint {
+    /// Endpoint frames keep the app's raw colors.
+    fn restricted(self) -> bool {
+        matches!(self.mode, ColorMode::Grayscale | ColorMode::Ansi16)
+    }
+
+    fn color(self, lch: Oklch) -> ratatui::style::Color {
+        match self.mode {
+            ColorMode::Grayscale => oklch::to_color(Oklch { c: 0.0, ..lch }),
+            ColorMode::Ansi16 => oklch::nearest_ansi16(lch),
+            ColorMode::Auto | ColorMode::Full => oklch::to_color(lch),
+        }
+    }
+}
+
 /// Crossfade through black: old glyph fades to invisible, new glyph emerges.
 fn pick_symbol<'a>(src: &'a str, dst: &'a str, src_fg: &ColorPair, t: f32) -> &'a str {
     if src == dst {
         return src;
     }
 
+    if let Some(glyph) = boxdraw::between(src, dst, t) {
+        return glyph;
+    }
+
     let threshold = src_fg
         .oklch
         .map(|lch| {
@@ -146,9 +488,10 @@ fn pick_symbol<'a>(src: &'a str, dst: &'a str, src_fg: &ColorPair, t: f32) -> &'
 }
 
 /// Scale lightness by `factor` (0.0 = black, 1.0 = original).
-fn fade(color: &ColorPair, factor: f32) -> ratatui::style::Color {
+fn fade(color: &ColorPair, factor: f32, paint: Paint) -> ratatui::style::Color {
     match color.oklch {
-        Some(lch) => oklch::to_color(Oklch {
+        _ if paint.restricted() && factor >= 1.0 => color.raw,
+        Some(lch) => paint.color(Oklch {
             l: lch.l * factor,
             ..lch
         }),
@@ -187,6 +530,57 @@ mod tests {
         buf
     }
 
+    #[test]
+    fn bg_sweep_moves_left_to_right() {
+        let mut src = Buffer::empty(Rect::new(0, 0, 10, 1));
+        let mut dst = src.clone();
+        src.set_style(src.area, Style::new().bg(Color::Rgb(0, 0, 0)));
+        dst.set_style(dst.area, Style::new().bg(Color::Rgb(255, 255, 255)));
+
+        let plan = solver::diff(&src, &dst, &MorphWeights::CRISP);
+        let options = RenderOptions {
+            bg_sweep: Some(BgSweep {
+                min_cells: 4,
+                ..BgSweep::default()
+            }),
+            ..Default::default()
+        };
+
+        let mid = render_with(&plan, 0.5, &options);
+        assert_eq!(mid[(0, 0)].bg, Color::Rgb(255, 255, 255));
+        assert_eq!(mid[(9, 0)].bg, Color::Rgb(0, 0, 0));
+        assert_eq!(render_with(&plan, 0.0, &options), src);
+        assert_eq!(render_with(&plan, 1.0, &options), dst);
+    }
+
+    #[test]
+    fn bg_sweep_follows_text_direction() {
+        let mut src = Buffer::empty(Rect::new(0, 0, 10, 1));
+        let mut dst = src.clone();
+        src.set_style(src.area, Style::new().bg(Color::Rgb(0, 0, 0)));
+        dst.set_style(dst.area, Style::new().bg(Color::Rgb(255, 255, 255)));
+
+        let plan = solver::diff(&src, &dst, &MorphWeights::CRISP);
+        let sweep = |direction| BgSweep {
+            direction,
+            min_cells: 4,
+            ..BgSweep::default()
+        };
+        let rtl = |sweep| RenderOptions {
+            bg_sweep: Some(sweep),
+            text_direction: TextDirection::RightToLeft,
+            ..Default::default()
+        };
+
+        let mid = render_with(&plan, 0.5, &rtl(sweep(None)));
+        assert_eq!(mid[(0, 0)].bg, Color::Rgb(0, 0, 0));
+        assert_eq!(mid[(9, 0)].bg, Color::Rgb(255, 255, 255));
+
+        // An explicit direction wins.
+        let mid = render_with(&plan, 0.5, &rtl(sweep(Some(SweepDirection::LeftToRight))));
+        assert_eq!(mid[(0, 0)].bg, Color::Rgb(255, 255, 255));
+    }
+
     #[test]
     fn at_zero_matches_source() {
         let src = make_buffer(
@@ -239,6 +633,59 @@ mod tests {
         }
     }
 
+    #[test]
+    fn disabled_category_snaps() {
+        let src = make_buffer(
+            4,
+            1,
+            &[((0, 0), "M", Color::Red), ((3, 0), "A", Color::Red)],
+        );
+        let dst = make_buffer(
+            4,
+            1,
+            &[((2, 0), "M", Color::Red), ((3, 0), "A", Color::Blue)],
+        );
+        let plan = solver::diff(&src, &dst, &MorphWeights::LIQUID);
+        let options = RenderOptions {
+            animate: Animate {
+                displaced: false,
+                ..Animate::ALL
+            },
+            ..RenderOptions::default()
+        };
+
+        assert_eq!(render_with(&plan, 0.0, &options)[(0, 0)].symbol(), "M");
+
+        let mid = render_with(&plan, 0.25, &options);
+        assert_eq!(mid[(2, 0)].symbol(), "M");
+        assert_ne!(mid[(3, 0)].fg, Color::Blue);
+    }
+
+    #[test]
+    fn grayscale_keeps_endpoints_and_drops_chroma() {
+        let src = make_buffer(1, 1, &[((0, 0), "X", Color::Rgb(255, 0, 0))]);
+        let dst = make_buffer(1, 1, &[((0, 0), "X", Color::Rgb(0, 0, 255))]);
+        let plan = solver::diff(&src, &dst, &MorphWeights::LIQUID);
+        let options = RenderOptions {
+            color: ColorMode::Grayscale,
+            ..RenderOptions::default()
+        };
+
+        assert_eq!(
+            render_with(&plan, 0.0, &options)[(0, 0)].fg,
+            Color::Rgb(255, 0, 0)
+        );
+        assert_eq!(
+            render_with(&plan, 1.0, &options)[(0, 0)].fg,
+            Color::Rgb(0, 0, 255)
+        );
+
+        match render_with(&plan, 0.5, &options)[(0, 0)].fg {
+            Color::Rgb(r, g, b) => assert!(r.abs_diff(g) <= 1 && g.abs_diff(b) <= 1),
+            other => panic!("expected gray, got {other:?}"),
+        }
+    }
+
     #[test]
     fn color_interpolation_midpoint() {
         let src = make_buffer(1, 1, &[((0, 0), "X", Color::Rgb(255, 0, 0))]);
@@ -0,0 +1,181 @@
+use std::cell::RefCell;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::{StatefulWidget, Widget};
+
+/// Application-named regions of one frame, e.g. `"item:42"` for a list
+/// entry or `"card:7"` for a kanban card.
+///
+/// A key present in two consecutive frames at different places moves its
+/// region's cells there as a unit before any cell matching, so reordered
+/// list entries, tabs and cards slide instead of morphing in place.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FrameKeys {
+    regions: Vec<(String, Rect)>,
+}
+
+impl FrameKeys {
+    pub const fn new() -> Self {
+        Self {
+            regions: Vec::new(),
+        }
+    }
+
+    /// Register `key` at `area`, replacing an earlier registration.
+    pub fn insert(&mut self, key: impl Into<String>, area: Rect) {
+        let key = key.into();
+        match self.regions.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, existing)) => *existing = area,
+            None => self.regions.push((key, area)),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<Rect> {
+        self.regions
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, area)| *area)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+
+    /// Insert all of `other`'s keys.
+    pub fn merge(&mut self, other: Self) {
+        for (key, area) in other.regions {
+            self.insert(key, area);
+        }
+    }
+
+    /// `(from, to)` for every key in both frames whose region moved, in
+    /// `next`'s registration order. For `SolveOptions::keyed`.
+    pub fn moves(&self, next: &Self) -> Vec<(Rect, Rect)> {
+        next.regions
+            .iter()
+            .filter_map(|(key, to)| Some((self.get(key)?, *to)))
+            .filter(|(from, to)| from.as_position() != to.as_position())
+            .collect()
+    }
+}
+
+/// Keys for the frame being drawn, shared between `MorphBackend` and the
+/// app's draw code. Get one with `MorphBackend::keys`; clones share state,
+/// so a clone can be captured by the `Terminal::draw` closure.
+///
+/// Each flush takes what was registered since the last one.
+#[derive(Debug, Clone, Default)]
+pub struct KeyHandle {
+    keys: Arc<Mutex<FrameKeys>>,
+}
+
+impl KeyHandle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `key` at `area` for the frame being drawn.
+    pub fn insert(&self, key: impl Into<String>, area: Rect) {
+        self.lock().insert(key, area);
+    }
+
+    /// Registered since the last call, including `Morphed` widgets
+    /// rendered on this thread.
+    #[cfg_attr(feature = "disabled", allow(dead_code))]
+    pub(crate) fn take(&self) -> FrameKeys {
+        let mut keys = std::mem::take(&mut *self.lock());
+        keys.merge(take_rendered());
+        keys
+    }
+
+    pub(crate) fn merge(&self, keys: FrameKeys) {
+        self.lock().merge(keys);
+    }
+
+    /// A panic elsewhere can't leave the keys half-written.
+    fn lock(&self) -> MutexGuard<'_, FrameKeys> {
+        self.keys.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+thread_local! {
+    /// Keys of `Morphed` widgets rendered on this thread since the last
+    /// flush.
+    static RENDERED: RefCell<FrameKeys> = const { RefCell::new(FrameKeys::new()) };
+}
+
+pub(crate) fn take_rendered() -> FrameKeys {
+    RENDERED.with_borrow_mut(std::mem::take)
+}
+
+fn register(key: String, area: Rect) {
+    // Nothing takes them under the `disabled` feature.
+    if !cfg!(feature = "disabled") {
+        RENDERED.with_borrow_mut(|keys| keys.insert(key, area));
+    }
+}
+
+/// Renders `widget` and registers its area under `id` for the next flush,
+/// as `KeyHandle::insert` would, without a handle: wrap list entries, tabs
+/// or cards and they slide when reordered.
+///
+/// The registry is per thread, so the widget must be rendered on the
+/// thread that flushes — as `Terminal::draw` does.
+pub struct Morphed<W> {
+    id: String,
+    widget: W,
+}
+
+impl<W> Morphed<W> {
+    pub fn new(id: impl Into<String>, widget: W) -> Self {
+        Self {
+            id: id.into(),
+            widget,
+        }
+    }
+}
+
+impl<W: Widget> Widget for Morphed<W> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        register(self.id, area);
+        self.widget.render(area, buf);
+    }
+}
+
+impl<W: StatefulWidget> StatefulWidget for Morphed<W> {
+    type State = W::State;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        register(self.id, area);
+        self.widget.render(area, buf, state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn moves_pair_keys_that_moved() {
+        let mut prev = FrameKeys::new();
+        prev.insert("item:1", Rect::new(0, 0, 10, 1));
+        prev.insert("item:2", Rect::new(0, 1, 10, 1));
+        prev.insert("gone", Rect::new(0, 2, 10, 1));
+
+        let mut next = FrameKeys::new();
+        next.insert("item:2", Rect::new(0, 0, 10, 1));
+        next.insert("item:1", Rect::new(0, 1, 10, 1));
+        next.insert("new", Rect::new(0, 2, 10, 1));
+
+        assert_eq!(
+            prev.moves(&next),
+            [
+                (Rect::new(0, 1, 10, 1), Rect::new(0, 0, 10, 1)),
+                (Rect::new(0, 0, 10, 1), Rect::new(0, 1, 10, 1)),
+            ]
+        );
+        assert!(prev.moves(&prev).is_empty());
+    }
+}
@@ -1,7 +1,32 @@
+pub mod ambient;
 pub mod backend;
+pub mod boxdraw;
+pub mod cache;
+pub mod classify;
+pub mod clock;
+pub mod compare;
+pub mod config;
+pub mod control;
+pub mod cost;
 pub mod easing;
+pub mod effect;
+pub mod export;
+pub mod focus;
+pub mod glyphs;
+pub mod graphics;
+pub mod history;
+pub mod hud;
 pub mod interpolate;
+pub mod keys;
+pub mod metrics;
+pub mod modifiers;
+pub mod motion;
 pub mod oklch;
 pub mod plan;
+pub mod safety;
 pub mod solver;
+pub mod theme;
+pub mod threaded;
+pub mod transition;
+pub mod updates;
 pub mod weights;
@@ -0,0 +1,46 @@
+use std::time::Duration;
+
+use crate::plan::PlanCounts;
+use crate::solver::SolveStats;
+
+/// Measurements from the most recent transition.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MorphMetrics {
+    /// Wall time spent in the solver.
+    pub solve_time: Duration,
+    /// The base transition's solve; all zero for a cached plan, a queued
+    /// step solved ahead on a worker, or a transition that skips the
+    /// solver, such as a theme switch.
+    pub solve: SolveStats,
+    pub plan: PlanCounts,
+    /// Planned length, after any safety lengthening.
+    pub duration: Duration,
+    /// Actual wall time from first to last interpolated frame.
+    pub elapsed: Duration,
+    pub target_fps: u32,
+    /// Interpolated frames actually flushed.
+    pub frames: u32,
+    /// Frames the schedule called for but that were never flushed.
+    pub dropped_frames: u32,
+    /// Transitions run since the backend was created.
+    pub transitions: u64,
+}
+
+impl MorphMetrics {
+    pub fn achieved_fps(&self) -> f32 {
+        let secs = self.elapsed.as_secs_f32();
+
+        if secs <= 0.0 || self.frames < 2 {
+            return 0.0;
+        }
+
+        (self.frames - 1) as f32 / secs
+    }
+
+    /// Frames a perfect scheduler would flush for `duration` at `fps`,
+    /// both endpoints included.
+    #[cfg_attr(feature = "disabled", allow(dead_code))]
+    pub(crate) fn expected_frames(duration: Duration, fps: u32) -> u32 {
+        (duration.as_secs_f32() * fps as f32).ceil() as u32 + 1
+    }
+}
@@ -0,0 +1,112 @@
+use ratatui::style::Modifier;
+
+/// How one modifier bit changes over a transition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ModifierTransition {
+    /// Hard switch at t = 0.5.
+    #[default]
+    Midpoint,
+    /// Target value from the first frame — for modifiers that must never
+    /// animate, like BLINK.
+    Instant,
+    /// Passes through DIM for the middle third, softening e.g. BOLD.
+    ViaDim,
+    /// Off while a displaced cell is in flight; otherwise `Midpoint`.
+    DropInMotion,
+}
+
+/// Per-modifier overrides. Modifiers without a rule use `Midpoint`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModifierRules {
+    rules: Vec<(Modifier, ModifierTransition)>,
+}
+
+impl ModifierRules {
+    pub const fn new() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    /// Applies to every bit in `modifier`. Later rules for the same bit win.
+    pub fn set(mut self, modifier: Modifier, rule: ModifierTransition) -> Self {
+        for bit in modifier.iter() {
+            self.rules.retain(|(m, _)| *m != bit);
+            self.rules.push((bit, rule));
+        }
+        self
+    }
+
+    pub fn rule(&self, bit: Modifier) -> ModifierTransition {
+        self.rules
+            .iter()
+            .find(|(m, _)| *m == bit)
+            .map_or(ModifierTransition::Midpoint, |(_, rule)| *rule)
+    }
+
+    /// Modifiers shown at eased progress `t`. `moving` is true for
+    /// displaced cells.
+    pub fn resolve(&self, src: Modifier, dst: Modifier, t: f32, moving: bool) -> Modifier {
+        let mut out = Modifier::empty();
+
+        for bit in (src | dst).iter() {
+            let (from, to) = (src.contains(bit), dst.contains(bit));
+            let switched = if t < 0.5 { from } else { to };
+
+            let on = match self.rule(bit) {
+                ModifierTransition::DropInMotion if moving && t > 0.0 && t < 1.0 => false,
+                _ if from == to => from,
+                ModifierTransition::Midpoint | ModifierTransition::DropInMotion => switched,
+                ModifierTransition::Instant => to,
+                ModifierTransition::ViaDim if t < 1.0 / 3.0 => from,
+                ModifierTransition::ViaDim if t < 2.0 / 3.0 => {
+                    out |= Modifier::DIM;
+                    false
+                }
+                ModifierTransition::ViaDim => to,
+            };
+
+            if on {
+                out |= bit;
+            }
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_switches_at_midpoint() {
+        let rules = ModifierRules::new();
+
+        assert_eq!(
+            rules.resolve(Modifier::BOLD, Modifier::empty(), 0.4, false),
+            Modifier::BOLD
+        );
+        assert_eq!(
+            rules.resolve(Modifier::BOLD, Modifier::empty(), 0.6, false),
+            Modifier::empty()
+        );
+    }
+
+    #[test]
+    fn overrides_per_modifier() {
+        let rules = ModifierRules::new()
+            .set(Modifier::SLOW_BLINK, ModifierTransition::Instant)
+            .set(Modifier::BOLD, ModifierTransition::ViaDim)
+            .set(Modifier::ITALIC, ModifierTransition::DropInMotion);
+
+        let blink = rules.resolve(Modifier::empty(), Modifier::SLOW_BLINK, 0.0, false);
+        assert_eq!(blink, Modifier::SLOW_BLINK);
+
+        let bold = rules.resolve(Modifier::BOLD, Modifier::empty(), 0.5, false);
+        assert_eq!(bold, Modifier::DIM);
+
+        let italic = Modifier::ITALIC;
+        assert_eq!(rules.resolve(italic, italic, 0.5, true), Modifier::empty());
+        assert_eq!(rules.resolve(italic, italic, 0.5, false), italic);
+        assert_eq!(rules.resolve(italic, italic, 1.0, true), italic);
+    }
+}
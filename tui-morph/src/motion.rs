@@ -0,0 +1,161 @@
+use std::time::Duration;
+
+use ratatui::buffer::Buffer;
+
+use crate::easing;
+use crate::plan::{ColorPair, DisplacedCell, InterpolationPlan, OrphanCell};
+
+/// Caps on how fast displaced cells may travel. A cell whose move would
+/// exceed either cap fades out at its source and in at its destination
+/// instead — very fast flight across a large terminal reads as flicker.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MotionLimit {
+    /// Cells per second, at the easing curve's steepest point.
+    pub max_velocity: Option<f32>,
+    /// Cells per second², at the easing curve's sharpest bend.
+    pub max_acceleration: Option<f32>,
+}
+
+impl MotionLimit {
+    /// No caps.
+    pub const NONE: Self = Self {
+        max_velocity: None,
+        max_acceleration: None,
+    };
+
+    fn exceeded(&self, distance: f32, slope: f32, bend: f32, secs: f32) -> bool {
+        if secs <= 0.0 {
+            return distance > 0.0
+                && (self.max_velocity.is_some() || self.max_acceleration.is_some());
+        }
+
+        let velocity = distance * slope / secs;
+        let acceleration = distance * bend / (secs * secs);
+
+        self.max_velocity.is_some_and(|cap| velocity > cap)
+            || self.max_acceleration.is_some_and(|cap| acceleration > cap)
+    }
+}
+
+/// Demote displaced cells in `plan` that break `limit` to orphan pairs.
+/// `src`/`dst` supply the counter backgrounds the orphans fade against.
+pub fn apply(
+    plan: &mut InterpolationPlan,
+    limit: &MotionLimit,
+    duration: Duration,
+    easing: fn(f32) -> f32,
+    src: &Buffer,
+    dst: &Buffer,
+) {
+    if limit.max_velocity.is_none() && limit.max_acceleration.is_none() {
+        return;
+    }
+
+    let slope = easing::peak_slope(easing);
+    let bend = easing::peak_acceleration(easing);
+    let secs = duration.as_secs_f32();
+
+    let (kept, demoted): (Vec<DisplacedCell>, Vec<DisplacedCell>) =
+        std::mem::take(&mut plan.displaced)
+            .into_iter()
+            .partition(|c| !limit.exceeded(distance(c), slope, bend, secs));
+
+    plan.displaced = kept;
+
+    for cell in demoted {
+        plan.disappearing.push(OrphanCell {
+            x: cell.src_x,
+            y: cell.src_y,
+            symbol: cell.src_symbol,
+            fg: cell.src_fg,
+            bg: cell.src_bg,
+            counter_bg: ColorPair::from_color(dst[(cell.src_x, cell.src_y)].bg),
+            modifier: cell.src_modifier,
+        });
+
+        plan.appearing.push(OrphanCell {
+            x: cell.dst_x,
+            y: cell.dst_y,
+            symbol: cell.dst_symbol,
+            fg: cell.dst_fg,
+            bg: cell.dst_bg,
+            counter_bg: ColorPair::from_color(src[(cell.dst_x, cell.dst_y)].bg),
+            modifier: cell.dst_modifier,
+        });
+    }
+}
+
+fn distance(cell: &DisplacedCell) -> f32 {
+    let dx = cell.dst_x as f32 - cell.src_x as f32;
+    let dy = cell.dst_y as f32 - cell.src_y as f32;
+    (dx * dx + dy * dy).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::layout::Rect;
+    use ratatui::style::{Color, Style};
+
+    use crate::solver;
+    use crate::weights::MorphWeights;
+
+    use super::*;
+
+    fn pair(dst_x: u16) -> (Buffer, Buffer) {
+        let mut a = Buffer::empty(Rect::new(0, 0, 40, 1));
+        let mut b = a.clone();
+        a[(0, 0)]
+            .set_symbol("M")
+            .set_style(Style::new().fg(Color::Red));
+        b[(dst_x, 0)]
+            .set_symbol("M")
+            .set_style(Style::new().fg(Color::Red));
+        (a, b)
+    }
+
+    #[test]
+    fn fast_moves_become_orphans() {
+        let (a, b) = pair(6);
+        let mut plan = solver::diff(&a, &b, &MorphWeights::LIQUID);
+        assert_eq!(plan.displaced.len(), 1);
+
+        let limit = MotionLimit {
+            max_velocity: Some(10.0),
+            max_acceleration: None,
+        };
+        apply(
+            &mut plan,
+            &limit,
+            Duration::from_millis(200),
+            easing::linear,
+            &a,
+            &b,
+        );
+
+        assert!(plan.displaced.is_empty());
+        assert_eq!(plan.disappearing.len(), 1);
+        assert_eq!(plan.appearing.len(), 1);
+        assert_eq!(plan.appearing[0].x, 6);
+    }
+
+    #[test]
+    fn slow_moves_are_kept() {
+        let (a, b) = pair(1);
+        let mut plan = solver::diff(&a, &b, &MorphWeights::LIQUID);
+
+        let limit = MotionLimit {
+            max_velocity: Some(10.0),
+            max_acceleration: Some(1000.0),
+        };
+        apply(
+            &mut plan,
+            &limit,
+            Duration::from_millis(200),
+            easing::ease_in_out,
+            &a,
+            &b,
+        );
+
+        assert_eq!(plan.displaced.len(), 1);
+    }
+}
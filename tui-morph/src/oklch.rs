@@ -36,6 +36,7 @@ fn linear_to_srgb(c: f32) -> f32 {
     }
 }
 
+#[allow(clippy::excessive_precision)]
 fn linear_rgb_to_oklab(rgb: LinRgb) -> Oklab {
     let LinRgb { r, g, b } = rgb;
 
@@ -54,6 +55,7 @@ fn linear_rgb_to_oklab(rgb: LinRgb) -> Oklab {
     }
 }
 
+#[allow(clippy::excessive_precision)]
 fn oklab_to_linear_rgb(lab: Oklab) -> LinRgb {
     let Oklab { l, a, b } = lab;
 
@@ -126,32 +128,103 @@ pub fn lerp(a: Oklch, b: Oklch, t: f32) -> Oklch {
     Oklch { l, c, h }
 }
 
+/// How hue travels between two colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HueMode {
+    /// Shortest arc around the hue circle.
+    #[default]
+    Shortest,
+    /// Destination hue throughout; only lightness and chroma move. Avoids
+    /// off-brand intermediates like muddy purple between blue and red.
+    Locked,
+}
+
+/// Below this chroma a color is effectively gray and its hue meaningless.
+const ACHROMATIC: f32 = 0.02;
+
+pub fn lerp_with(a: Oklch, b: Oklch, t: f32, hue: HueMode) -> Oklch {
+    match hue {
+        HueMode::Shortest => lerp(a, b, t),
+        HueMode::Locked => Oklch {
+            l: a.l + (b.l - a.l) * t,
+            c: a.c + (b.c - a.c) * t,
+            h: if b.c < ACHROMATIC { a.h } else { b.h },
+        },
+    }
+}
+
 /// Convert a ratatui Color to Oklch, if it has a concrete RGB representation.
 pub fn from_color(color: ratatui::style::Color) -> Option<Oklch> {
+    to_srgb(color).map(|(r, g, b)| srgb_to_oklch(r, g, b))
+}
+
+/// Concrete sRGB for a ratatui Color. `None` for Reset and Indexed, whose
+/// appearance depends on the terminal's palette.
+pub fn to_srgb(color: ratatui::style::Color) -> Option<(u8, u8, u8)> {
     use ratatui::style::Color;
 
     match color {
-        Color::Rgb(r, g, b) => Some(srgb_to_oklch(r, g, b)),
-        Color::Black => Some(srgb_to_oklch(0, 0, 0)),
-        Color::Red => Some(srgb_to_oklch(128, 0, 0)),
-        Color::Green => Some(srgb_to_oklch(0, 128, 0)),
-        Color::Yellow => Some(srgb_to_oklch(128, 128, 0)),
-        Color::Blue => Some(srgb_to_oklch(0, 0, 128)),
-        Color::Magenta => Some(srgb_to_oklch(128, 0, 128)),
-        Color::Cyan => Some(srgb_to_oklch(0, 128, 128)),
-        Color::Gray => Some(srgb_to_oklch(192, 192, 192)),
-        Color::DarkGray => Some(srgb_to_oklch(128, 128, 128)),
-        Color::LightRed => Some(srgb_to_oklch(255, 0, 0)),
-        Color::LightGreen => Some(srgb_to_oklch(0, 255, 0)),
-        Color::LightYellow => Some(srgb_to_oklch(255, 255, 0)),
-        Color::LightBlue => Some(srgb_to_oklch(0, 0, 255)),
-        Color::LightMagenta => Some(srgb_to_oklch(255, 0, 255)),
-        Color::LightCyan => Some(srgb_to_oklch(0, 255, 255)),
-        Color::White => Some(srgb_to_oklch(255, 255, 255)),
+        Color::Rgb(r, g, b) => Some((r, g, b)),
+        Color::Black => Some((0, 0, 0)),
+        Color::Red => Some((128, 0, 0)),
+        Color::Green => Some((0, 128, 0)),
+        Color::Yellow => Some((128, 128, 0)),
+        Color::Blue => Some((0, 0, 128)),
+        Color::Magenta => Some((128, 0, 128)),
+        Color::Cyan => Some((0, 128, 128)),
+        Color::Gray => Some((192, 192, 192)),
+        Color::DarkGray => Some((128, 128, 128)),
+        Color::LightRed => Some((255, 0, 0)),
+        Color::LightGreen => Some((0, 255, 0)),
+        Color::LightYellow => Some((255, 255, 0)),
+        Color::LightBlue => Some((0, 0, 255)),
+        Color::LightMagenta => Some((255, 0, 255)),
+        Color::LightCyan => Some((0, 255, 255)),
+        Color::White => Some((255, 255, 255)),
         Color::Reset | Color::Indexed(_) => None,
     }
 }
 
+/// The 16 named colors, in the sRGB approximations `to_srgb` uses.
+const ANSI16: [ratatui::style::Color; 16] = {
+    use ratatui::style::Color::*;
+    [
+        Black,
+        Red,
+        Green,
+        Yellow,
+        Blue,
+        Magenta,
+        Cyan,
+        Gray,
+        DarkGray,
+        LightRed,
+        LightGreen,
+        LightYellow,
+        LightBlue,
+        LightMagenta,
+        LightCyan,
+        White,
+    ]
+};
+
+/// Perceptually closest named ANSI color.
+pub fn nearest_ansi16(lch: Oklch) -> ratatui::style::Color {
+    let mut best = (f32::INFINITY, ratatui::style::Color::White);
+
+    for color in ANSI16 {
+        if let Some(candidate) = from_color(color) {
+            let d = distance(lch, candidate);
+
+            if d < best.0 {
+                best = (d, color);
+            }
+        }
+    }
+
+    best.1
+}
+
 pub fn to_color(lch: Oklch) -> ratatui::style::Color {
     let (r, g, b) = oklch_to_srgb(lch);
     ratatui::style::Color::Rgb(r, g, b)
@@ -186,6 +259,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn nearest_ansi16_picks_obvious_matches() {
+        use ratatui::style::Color;
+
+        assert_eq!(nearest_ansi16(srgb_to_oklch(250, 5, 5)), Color::LightRed);
+        assert_eq!(nearest_ansi16(srgb_to_oklch(10, 10, 10)), Color::Black);
+    }
+
+    #[test]
+    fn locked_hue_holds_destination() {
+        let red = srgb_to_oklch(255, 0, 0);
+        let blue = srgb_to_oklch(0, 0, 255);
+
+        for t in [0.25, 0.5, 0.75] {
+            assert_eq!(lerp_with(red, blue, t, HueMode::Locked).h, blue.h);
+        }
+
+        let gray = srgb_to_oklch(128, 128, 128);
+        assert_eq!(lerp_with(red, gray, 0.5, HueMode::Locked).h, red.h);
+    }
+
     #[test]
     fn round_trip_primaries() {
         assert_round_trip(255, 0, 0);
@@ -1,9 +1,14 @@
+use ratatui::layout::{Position, Rect};
 use ratatui::style::{Color, Modifier};
 
 use crate::oklch::Oklch;
 
 /// Frozen diff artifact between two frames. Produced by the solver, consumed by the interpolator.
+#[derive(Clone)]
 pub struct InterpolationPlan {
+    /// Top-left of the frames' area. Cell positions are absolute, as in
+    /// the buffers the plan was solved from.
+    pub origin: Position,
     pub width: u16,
     pub height: u16,
     pub stable: Vec<StableCell>,
@@ -11,8 +16,266 @@ pub struct InterpolationPlan {
     pub displaced: Vec<DisplacedCell>,
     pub appearing: Vec<OrphanCell>,
     pub disappearing: Vec<OrphanCell>,
+    /// Cells flagged `Cell::skip` in either frame, e.g. protected regions
+    /// or images. They take no part in the morph, and rendered frames flag
+    /// them too, so a diff never writes over them.
+    pub skipped: Vec<(u16, u16)>,
+    /// Numbers that changed in place; their digits are redrawn from the
+    /// interpolated value over the cells beneath.
+    pub numbers: Vec<NumberTween>,
+    /// Box outlines that moved or resized; drawn over everything else.
+    pub borders: Vec<BorderTween>,
 }
 
+impl InterpolationPlan {
+    /// The frames' area.
+    pub fn area(&self) -> Rect {
+        Rect::new(self.origin.x, self.origin.y, self.width, self.height)
+    }
+
+    /// Longest euclidean distance, in cells, that any displaced cell travels.
+    pub fn max_displacement(&self) -> f32 {
+        self.displaced
+            .iter()
+            .map(|c| {
+                let dx = c.dst_x as f32 - c.src_x as f32;
+                let dy = c.dst_y as f32 - c.src_y as f32;
+                (dx * dx + dy * dy).sqrt()
+            })
+            .fold(0.0, f32::max)
+    }
+
+    /// The part of the plan inside `rect`, re-based so `rect`'s top-left is
+    /// the origin. Displaced cells crossing the edge become orphans on the
+    /// side that stays inside.
+    pub fn subplan(&self, rect: Rect) -> Self {
+        let rect = rect.intersection(self.area());
+        let inside = |x: u16, y: u16| rect.contains((x, y).into());
+        let (ox, oy) = (rect.x, rect.y);
+
+        let mut sub = Self {
+            origin: Position::ORIGIN,
+            width: rect.width,
+            height: rect.height,
+            stable: Vec::new(),
+            mutating: Vec::new(),
+            displaced: Vec::new(),
+            appearing: Vec::new(),
+            disappearing: Vec::new(),
+            skipped: Vec::new(),
+            numbers: Vec::new(),
+            borders: Vec::new(),
+        };
+
+        for &(x, y) in self.skipped.iter().filter(|&&(x, y)| inside(x, y)) {
+            sub.skipped.push((x - ox, y - oy));
+        }
+
+        for number in &self.numbers {
+            let span = Rect::new(number.x, number.y, number.width, 1);
+            if rect.intersection(span) == span {
+                sub.numbers.push(NumberTween {
+                    x: number.x - ox,
+                    y: number.y - oy,
+                    ..number.clone()
+                });
+            }
+        }
+
+        for border in &self.borders {
+            let rebase = |r: Rect| Rect::new(r.x - ox, r.y - oy, r.width, r.height);
+            if rect.union(border.from) == rect && rect.union(border.to) == rect {
+                sub.borders.push(BorderTween {
+                    from: rebase(border.from),
+                    to: rebase(border.to),
+                    ..border.clone()
+                });
+            }
+        }
+
+        for cell in self.stable.iter().filter(|c| inside(c.x, c.y)) {
+            sub.stable.push(StableCell {
+                x: cell.x - ox,
+                y: cell.y - oy,
+                ..cell.clone()
+            });
+        }
+
+        for cell in self.mutating.iter().filter(|c| inside(c.x, c.y)) {
+            sub.mutating.push(MutatingCell {
+                x: cell.x - ox,
+                y: cell.y - oy,
+                ..cell.clone()
+            });
+        }
+
+        for (cells, out) in [
+            (&self.appearing, &mut sub.appearing),
+            (&self.disappearing, &mut sub.disappearing),
+        ] {
+            for cell in cells.iter().filter(|c| inside(c.x, c.y)) {
+                out.push(OrphanCell {
+                    x: cell.x - ox,
+                    y: cell.y - oy,
+                    ..cell.clone()
+                });
+            }
+        }
+
+        for cell in &self.displaced {
+            match (
+                inside(cell.src_x, cell.src_y),
+                inside(cell.dst_x, cell.dst_y),
+            ) {
+                (true, true) => sub.displaced.push(DisplacedCell {
+                    src_x: cell.src_x - ox,
+                    src_y: cell.src_y - oy,
+                    dst_x: cell.dst_x - ox,
+                    dst_y: cell.dst_y - oy,
+                    ..cell.clone()
+                }),
+                (true, false) => sub.disappearing.push(OrphanCell {
+                    x: cell.src_x - ox,
+                    y: cell.src_y - oy,
+                    symbol: cell.src_symbol.clone(),
+                    fg: cell.src_fg,
+                    bg: cell.src_bg,
+                    counter_bg: self
+                        .bg_at(cell.src_x, cell.src_y)
+                        .map_or(cell.src_bg, |c| c.dst_bg),
+                    modifier: cell.src_modifier,
+                }),
+                (false, true) => sub.appearing.push(OrphanCell {
+                    x: cell.dst_x - ox,
+                    y: cell.dst_y - oy,
+                    symbol: cell.dst_symbol.clone(),
+                    fg: cell.dst_fg,
+                    bg: cell.dst_bg,
+                    counter_bg: self
+                        .bg_at(cell.dst_x, cell.dst_y)
+                        .map_or(cell.dst_bg, |c| c.src_bg),
+                    modifier: cell.dst_modifier,
+                }),
+                (false, false) => {}
+            }
+        }
+
+        sub
+    }
+
+    /// The background entry the solver emits under an unmatched glyph.
+    fn bg_at(&self, x: u16, y: u16) -> Option<&MutatingCell> {
+        self.mutating.iter().find(|c| c.x == x && c.y == y)
+    }
+
+    pub fn counts(&self) -> PlanCounts {
+        PlanCounts {
+            stable: self.stable.len(),
+            mutating: self.mutating.len(),
+            displaced: self.displaced.len(),
+            appearing: self.appearing.len(),
+            disappearing: self.disappearing.len(),
+        }
+    }
+}
+
+/// Per-category cell counts of a plan.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PlanCounts {
+    pub stable: usize,
+    pub mutating: usize,
+    pub displaced: usize,
+    pub appearing: usize,
+    pub disappearing: usize,
+}
+
+impl PlanCounts {
+    /// Cells that change at all.
+    pub fn changed(&self) -> usize {
+        self.mutating + self.displaced + self.appearing + self.disappearing
+    }
+}
+
+/// A number whose value changed in place, e.g. a gauge's `87%` → `43%`.
+/// The cells of `x..x + width` mutate as usual; the number's text is
+/// drawn over them, counting from `from` to `to`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NumberTween {
+    /// Left edge of the span either number occupies.
+    pub x: u16,
+    pub y: u16,
+    pub width: u16,
+    pub from: f64,
+    pub to: f64,
+    /// Columns the source and target text take, from the shared edge.
+    pub src_width: u16,
+    pub dst_width: u16,
+    /// Digits after the point in the source and target text.
+    pub src_decimals: u8,
+    pub dst_decimals: u8,
+    /// The two numbers share their last column rather than their first,
+    /// so the text is drawn against the span's right edge.
+    pub right_aligned: bool,
+}
+
+impl NumberTween {
+    /// The text shown at `t`: the source's exactly at `0`, the target's at
+    /// `1`, switching to the target's precision and extent halfway. Padded
+    /// with spaces to the extent, away from the shared edge.
+    pub fn text(&self, t: f32) -> String {
+        let value = self.from + (self.to - self.from) * f64::from(t.clamp(0.0, 1.0));
+        let (decimals, width) = if t < 0.5 {
+            (self.src_decimals, self.src_width)
+        } else {
+            (self.dst_decimals, self.dst_width)
+        };
+        let text = format_number(value, decimals);
+        let width = usize::from(width);
+
+        if self.right_aligned {
+            format!("{text:>width$}")
+        } else {
+            format!("{text:<width$}")
+        }
+    }
+}
+
+/// A box outline, such as a `Block` border, going from `from` to `to`:
+/// its corners slide along and its lines stretch or shrink, instead of each
+/// line glyph fading on its own. The cells beneath morph as if blank.
+#[derive(Clone)]
+pub struct BorderTween {
+    pub from: Rect,
+    pub to: Rect,
+    pub src: BorderStyle,
+    pub dst: BorderStyle,
+}
+
+/// Glyphs and color of one end of a `BorderTween`.
+#[derive(Clone)]
+pub struct BorderStyle {
+    /// Top-left, top-right, bottom-left, bottom-right.
+    pub corners: [String; 4],
+    pub horizontal: String,
+    pub vertical: String,
+    pub fg: ColorPair,
+    pub modifier: Modifier,
+    /// Offsets from the left edge of top and bottom edge cells left to
+    /// what is beneath, such as a title.
+    pub top_gaps: Vec<u16>,
+    pub bottom_gaps: Vec<u16>,
+}
+
+/// `value` with `decimals` digits after the point, without a sign on zero.
+pub(crate) fn format_number(value: f64, decimals: u8) -> String {
+    let text = format!("{value:.0$}", usize::from(decimals));
+    match text.strip_prefix('-') {
+        Some(digits) if digits.chars().all(|c| c == '0' || c == '.') => digits.to_string(),
+        _ => text,
+    }
+}
+
+#[derive(Clone)]
 pub struct StableCell {
     pub x: u16,
     pub y: u16,
@@ -22,6 +285,7 @@ pub struct StableCell {
     pub modifier: Modifier,
 }
 
+#[derive(Clone)]
 pub struct MutatingCell {
     pub x: u16,
     pub y: u16,
@@ -35,6 +299,7 @@ pub struct MutatingCell {
     pub dst_modifier: Modifier,
 }
 
+#[derive(Clone)]
 pub struct DisplacedCell {
     pub src_x: u16,
     pub src_y: u16,
@@ -54,6 +319,7 @@ pub struct DisplacedCell {
 ///
 /// `counter_bg`: the background at this position in the *other* frame,
 /// so orphan bg can interpolate toward it instead of fading to black.
+#[derive(Clone)]
 pub struct OrphanCell {
     pub x: u16,
     pub y: u16,
@@ -79,3 +345,42 @@ impl ColorPair {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use ratatui::buffer::Buffer;
+    use ratatui::style::Style;
+
+    use crate::solver;
+    use crate::weights::MorphWeights;
+
+    use super::*;
+
+    fn row(cells: &[(u16, &str)]) -> Buffer {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 8, 2));
+
+        for &(x, sym) in cells {
+            buf[(x, 0)]
+                .set_symbol(sym)
+                .set_style(Style::new().fg(Color::Red));
+        }
+
+        buf
+    }
+
+    #[test]
+    fn subplan_rebases_and_clips() {
+        let src = row(&[(1, "M"), (5, "S")]);
+        let dst = row(&[(6, "M"), (5, "S")]);
+        let plan = solver::diff(&src, &dst, &MorphWeights::LIQUID);
+        assert_eq!(plan.displaced.len(), 1);
+
+        let sub = plan.subplan(Rect::new(4, 0, 4, 1));
+        assert_eq!((sub.width, sub.height), (4, 1));
+        assert!(sub.displaced.is_empty());
+        assert_eq!(sub.stable.len() + sub.mutating.len(), 4);
+        assert!(sub.stable.iter().any(|c| c.x == 1 && c.symbol == "S"));
+        assert_eq!(sub.appearing.len(), 1);
+        assert_eq!(sub.appearing[0].x, 2);
+    }
+}
@@ -0,0 +1,195 @@
+use std::time::Duration;
+
+use crate::easing;
+use crate::oklch::Oklch;
+use crate::plan::{ColorPair, InterpolationPlan};
+
+/// Photosensitivity guard: bounds how fast any cell's lightness may change
+/// during a transition, and how often a full flash may occur.
+#[derive(Debug, Clone, Copy)]
+pub struct FlashLimit {
+    /// Largest Oklch lightness change any cell may undergo between two
+    /// consecutive interpolation frames.
+    pub max_step: f32,
+
+    /// Lightness swing at or above which a transition counts as a flash.
+    pub flash_delta: f32,
+
+    /// Upper bound on flashes per second. `0` leaves flashes unbounded.
+    pub max_flashes_per_second: f32,
+}
+
+impl FlashLimit {
+    /// Three flashes per second (WCAG 2.3.1), gentle per-frame ramps.
+    pub const WCAG: Self = Self {
+        max_step: 0.05,
+        flash_delta: 0.1,
+        max_flashes_per_second: 3.0,
+    };
+
+    /// Smallest `max_step` `MorphConfigBuilder::build` accepts; finer steps
+    /// would stretch a full flash over thousands of frames.
+    pub const MIN_STEP: f32 = 0.001;
+
+    /// Smallest nonzero `max_flashes_per_second` `build` accepts: one
+    /// flash a minute.
+    pub const MIN_RATE: f32 = 1.0 / 60.0;
+
+    /// The first out-of-range field: a non-finite value, a `max_step`
+    /// below `MIN_STEP`, or a nonzero rate below `MIN_RATE`.
+    pub fn invalid(&self) -> Option<f32> {
+        let rate = self.max_flashes_per_second;
+        [
+            (self.max_step, self.max_step >= Self::MIN_STEP),
+            (self.flash_delta, self.flash_delta >= 0.0),
+            (rate, rate == 0.0 || rate >= Self::MIN_RATE),
+        ]
+        .into_iter()
+        .find(|(value, ok)| !(value.is_finite() && *ok))
+        .map(|(value, _)| value)
+    }
+}
+
+impl Default for FlashLimit {
+    fn default() -> Self {
+        Self::WCAG
+    }
+}
+
+/// Largest lightness swing of any single cell across the whole transition.
+pub fn peak_lightness_delta(plan: &InterpolationPlan) -> f32 {
+    let mut peak = 0.0f32;
+
+    for cell in &plan.mutating {
+        peak = peak.max(pair_delta(&cell.src_fg, &cell.dst_fg));
+        peak = peak.max(pair_delta(&cell.src_bg, &cell.dst_bg));
+    }
+
+    for cell in &plan.displaced {
+        peak = peak.max(pair_delta(&cell.src_fg, &cell.dst_fg));
+        peak = peak.max(pair_delta(&cell.src_bg, &cell.dst_bg));
+    }
+
+    for cell in plan.appearing.iter().chain(&plan.disappearing) {
+        peak = peak.max(cell.fg.oklch.map(|lch| lch.l).unwrap_or(0.0));
+        peak = peak.max(pair_delta(&cell.bg, &cell.counter_bg));
+    }
+
+    peak
+}
+
+fn pair_delta(a: &ColorPair, b: &ColorPair) -> f32 {
+    match (a.oklch, b.oklch) {
+        (Some(Oklch { l: la, .. }), Some(Oklch { l: lb, .. })) => (la - lb).abs(),
+        _ => 0.0,
+    }
+}
+
+/// Shortest duration at which `plan` stays within `limit`.
+///
+/// Never shorter than `requested`. Accounts for the easing curve's
+/// steepest section, where the per-frame change is largest.
+pub fn safe_duration(
+    plan: &InterpolationPlan,
+    limit: &FlashLimit,
+    requested: Duration,
+    easing: fn(f32) -> f32,
+    fps: u32,
+) -> Duration {
+    let delta = peak_lightness_delta(plan);
+
+    if delta <= 0.0 || fps == 0 || limit.max_step <= 0.0 {
+        return requested;
+    }
+
+    let frames = (delta * easing::peak_slope(easing) / limit.max_step).ceil();
+    let secs = |secs: f32| Duration::try_from_secs_f32(secs).unwrap_or(Duration::MAX);
+    let mut duration = requested.max(secs(frames / fps as f32));
+
+    if delta >= limit.flash_delta && limit.max_flashes_per_second > 0.0 {
+        duration = duration.max(secs(1.0 / limit.max_flashes_per_second));
+    }
+
+    duration
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::buffer::Buffer;
+    use ratatui::layout::Rect;
+    use ratatui::style::{Color, Style};
+
+    use crate::easing;
+    use crate::solver;
+    use crate::weights::MorphWeights;
+
+    use super::*;
+
+    fn filled(bg: Color) -> Buffer {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 4, 2));
+        buf.set_style(buf.area, Style::new().bg(bg));
+        buf
+    }
+
+    #[test]
+    fn identical_frames_keep_requested_duration() {
+        let a = filled(Color::Rgb(40, 40, 40));
+        let plan = solver::diff(&a, &a, &MorphWeights::FADE);
+        let requested = Duration::from_millis(200);
+
+        let got = safe_duration(&plan, &FlashLimit::WCAG, requested, easing::linear, 60);
+        assert_eq!(got, requested);
+    }
+
+    #[test]
+    fn black_to_white_is_lengthened() {
+        let a = filled(Color::Rgb(0, 0, 0));
+        let b = filled(Color::Rgb(255, 255, 255));
+        let plan = solver::diff(&a, &b, &MorphWeights::FADE);
+
+        assert!(peak_lightness_delta(&plan) > 0.99);
+
+        let got = safe_duration(
+            &plan,
+            &FlashLimit::WCAG,
+            Duration::from_millis(50),
+            easing::linear,
+            60,
+        );
+
+        // 1.0 / 0.05 = 20 frames at 60fps, and at least a third of a second.
+        assert!(got >= Duration::from_millis(333), "got {got:?}");
+    }
+
+    #[test]
+    fn steeper_easing_needs_longer() {
+        let a = filled(Color::Rgb(0, 0, 0));
+        let b = filled(Color::Rgb(255, 255, 255));
+        let plan = solver::diff(&a, &b, &MorphWeights::FADE);
+        let limit = FlashLimit {
+            max_flashes_per_second: 0.0,
+            ..FlashLimit::WCAG
+        };
+
+        let linear = safe_duration(&plan, &limit, Duration::ZERO, easing::linear, 60);
+        let eased = safe_duration(&plan, &limit, Duration::ZERO, easing::ease_in_out, 60);
+
+        assert!(eased > linear, "{eased:?} <= {linear:?}");
+    }
+
+    #[test]
+    fn unbuildable_limit_saturates() {
+        let a = filled(Color::Rgb(0, 0, 0));
+        let b = filled(Color::Rgb(255, 255, 255));
+        let plan = solver::diff(&a, &b, &MorphWeights::FADE);
+        let limit = FlashLimit {
+            max_step: 1e-40,
+            max_flashes_per_second: 1e-40,
+            ..FlashLimit::WCAG
+        };
+        assert!(limit.invalid().is_some());
+
+        let got = safe_duration(&plan, &limit, Duration::ZERO, easing::linear, 60);
+        assert_eq!(got, Duration::MAX);
+    }
+}
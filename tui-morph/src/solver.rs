@@ -1,14 +1,290 @@
-use ratatui::buffer::Buffer;
-
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use ratatui::buffer::{Buffer, Cell};
+use ratatui::layout::Rect;
+use ratatui::style::Color;
+
+use crate::boxdraw::{self, Outline};
+use crate::classify::{BlankCells, CellClass, CellClassifier, default_class};
+use crate::cost::{CostFunction, MatchCell};
+use crate::glyphs::GlyphTable;
 use crate::oklch;
 use crate::plan::{
-    ColorPair, DisplacedCell, InterpolationPlan, MutatingCell, OrphanCell, StableCell,
+    BorderStyle, BorderTween, ColorPair, DisplacedCell, InterpolationPlan, MutatingCell,
+    NumberTween, OrphanCell, StableCell, format_number,
 };
 use crate::weights::MorphWeights;
 
+/// How unmatched glyphs are paired across frames.
+///
+/// Costs are rounded to `COST_QUANTUM` before matching, so last-bit float
+/// differences between platforms can't change the result. Among pairings
+/// of equal cost, every strategy prefers the smaller squared displacement,
+/// then sources in row-major order onto destinations in row-major order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MatchStrategy {
+    /// Picks one of the others per transition by the number of unmatched
+    /// cells; see `AdaptiveThresholds`.
+    #[default]
+    Adaptive,
+    /// Optimal assignment. O(n³) in the number of unmatched cells.
+    Hungarian,
+    /// Each source glyph takes the nearest unclaimed destination with the
+    /// same symbol. Near-linear, but ignores `GlyphTable` equivalences.
+    Greedy,
+    /// Bertsekas' auction: within a hair of `Hungarian`'s assignment and
+    /// usually much faster on large, spread-out diffs.
+    Auction,
+    /// No assignment — every unmatched glyph fades out or in where it stands.
+    Crossfade,
+}
+
+/// Whether unmatched glyphs travel, independent of the cost weights.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Displacement {
+    /// A glyph travels when that is cheaper than fading out and back in.
+    #[default]
+    Costed,
+    /// Every glyph the strategy can pair travels, however far or unlike;
+    /// `Adaptive` and `Crossfade` fall back to `Hungarian`, then `Greedy`
+    /// on large diffs. For demos.
+    Always,
+    /// Nothing travels: no matching, no moved rows, regions or outlines;
+    /// every change fades in place.
+    Never,
+}
+
+/// Problem-size cutoffs for `MatchStrategy::Adaptive`, compared against the
+/// larger of the two unmatched sets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdaptiveThresholds {
+    /// Up to this many: `Hungarian`.
+    pub exact: usize,
+    /// Up to this many: `Greedy`. Beyond: `Crossfade`.
+    pub greedy: usize,
+}
+
+impl AdaptiveThresholds {
+    pub const DEFAULT: Self = Self {
+        exact: 300,
+        greedy: 4000,
+    };
+
+    pub fn pick(&self, unmatched: usize) -> MatchStrategy {
+        if unmatched <= self.exact {
+            MatchStrategy::Hungarian
+        } else if unmatched <= self.greedy {
+            MatchStrategy::Greedy
+        } else {
+            MatchStrategy::Crossfade
+        }
+    }
+}
+
+impl Default for AdaptiveThresholds {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Where a source of a different size sits within the target's area,
+/// before it is cropped or blank-padded to match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResizeAnchor {
+    #[default]
+    TopLeft,
+    Center,
+    /// For bottom-anchored content such as logs and inline viewports.
+    BottomLeft,
+}
+
+/// Solver knobs beyond the cost weights.
+#[derive(Debug, Clone)]
+pub struct SolveOptions {
+    pub strategy: MatchStrategy,
+    pub displacement: Displacement,
+    pub adaptive: AdaptiveThresholds,
+    /// Largest cost matrix `Hungarian` and `Auction` may build, in source
+    /// × target glyphs. Past it, glyphs in row-major order beyond the first
+    /// that fit fade out and in instead of being paired, bounding the
+    /// solve on full-screen changes.
+    pub max_pairs: usize,
+    pub glyphs: GlyphTable,
+    /// Used when source and target areas differ.
+    pub anchor: ResizeAnchor,
+    /// Regions that snap to the target instead of morphing, e.g. a
+    /// status bar or a busy log pane.
+    pub exclude: Vec<Rect>,
+    /// Move words and blocks of text that reappear unchanged as a unit,
+    /// rather than matching their glyphs one by one.
+    pub components: bool,
+    /// Move runs of same-style text on a row ("words") as units, pairing
+    /// each with the nearest similar run; letters that differ morph on
+    /// the way.
+    pub words: bool,
+    /// Adjusts the cost of each candidate glyph pairing.
+    pub cost: Option<Arc<dyn CostFunction>>,
+    /// Overrides how changed positions are classified.
+    pub classifier: Option<Arc<dyn CellClassifier>>,
+    /// Which cells are empty rather than content.
+    pub blank: BlankCells,
+    /// Detect scrolling: whole rows that reappear unchanged higher or
+    /// lower move as rows, before any cell matching. A frame that only
+    /// appends lines to a log (everything between unchanged header and
+    /// footer rows shifts up) skips matching altogether: the lines pushed
+    /// out fade, and the new ones fade in.
+    pub scroll: bool,
+    /// Regions the app tracks by key, as `(from, to)`: each source
+    /// region's cells move to the target region as a unit, before any
+    /// other matching. See `FrameKeys::moves`.
+    pub keyed: Vec<(Rect, Rect)>,
+    /// Numbers that change in place (`87%` → `43%`) count through the
+    /// values between instead of morphing their digits.
+    pub numbers: bool,
+    /// Box outlines that move or resize, such as `Block` borders, slide
+    /// their corners and stretch their lines instead of fading glyph by
+    /// glyph.
+    pub borders: bool,
+}
+
+impl Default for SolveOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SolveOptions {
+    /// Default `max_pairs`: a 1024 × 1024 matrix, 4 MiB of costs.
+    pub const MAX_PAIRS: usize = 1 << 20;
+
+    pub const fn new() -> Self {
+        Self {
+            strategy: MatchStrategy::Adaptive,
+            displacement: Displacement::Costed,
+            adaptive: AdaptiveThresholds::DEFAULT,
+            max_pairs: SolveOptions::MAX_PAIRS,
+            glyphs: GlyphTable::new(),
+            anchor: ResizeAnchor::TopLeft,
+            exclude: Vec::new(),
+            components: false,
+            words: false,
+            cost: None,
+            classifier: None,
+            blank: BlankCells::new(),
+            scroll: false,
+            keyed: Vec::new(),
+            numbers: false,
+            borders: false,
+        }
+    }
+
+    /// The strategy actually used for `unmatched` cells.
+    pub fn resolve(&self, unmatched: usize) -> MatchStrategy {
+        match (self.displacement, self.strategy) {
+            (Displacement::Never, _) => MatchStrategy::Crossfade,
+            (Displacement::Always, MatchStrategy::Adaptive | MatchStrategy::Crossfade) => {
+                if unmatched <= self.adaptive.exact {
+                    MatchStrategy::Hungarian
+                } else {
+                    MatchStrategy::Greedy
+                }
+            }
+            (_, MatchStrategy::Adaptive) => self.adaptive.pick(unmatched),
+            (_, strategy) => strategy,
+        }
+    }
+}
+
 pub fn diff(src: &Buffer, dst: &Buffer, weights: &MorphWeights) -> InterpolationPlan {
-    let area = src.area();
-    assert_eq!(area, dst.area(), "buffers must have the same dimensions");
+    diff_with(src, dst, weights, &SolveOptions::default())
+}
+
+pub fn diff_with(
+    src: &Buffer,
+    dst: &Buffer,
+    weights: &MorphWeights,
+    options: &SolveOptions,
+) -> InterpolationPlan {
+    diff_in(src, dst, weights, options, &mut SolveScratch::default())
+}
+
+/// `diff_with`, also reporting where the solve spent its time.
+pub fn diff_with_stats(
+    src: &Buffer,
+    dst: &Buffer,
+    weights: &MorphWeights,
+    options: &SolveOptions,
+) -> (InterpolationPlan, SolveStats) {
+    let mut scratch = SolveScratch::default();
+    let plan = diff_in(src, dst, weights, options, &mut scratch);
+    (plan, scratch.stats)
+}
+
+/// `diff_with`, reusing `scratch`'s allocations.
+pub fn diff_in(
+    src: &Buffer,
+    dst: &Buffer,
+    weights: &MorphWeights,
+    options: &SolveOptions,
+    scratch: &mut SolveScratch,
+) -> InterpolationPlan {
+    solve(src, dst, weights, options, scratch, None)
+}
+
+/// Re-solve an interrupted transition toward a new target, from `src`
+/// (usually the frame on screen when `plan` was cut short) to `dst`.
+/// Cells `plan` held stable that both frames still show unchanged keep
+/// that classification without being compared or classified again; the
+/// rest is solved as by `diff_with`. With a `SolveOptions::classifier`
+/// set, every cell is classified again and the result is `diff_with`'s.
+pub fn update(
+    plan: &InterpolationPlan,
+    src: &Buffer,
+    dst: &Buffer,
+    weights: &MorphWeights,
+    options: &SolveOptions,
+) -> InterpolationPlan {
+    update_in(
+        plan,
+        src,
+        dst,
+        weights,
+        options,
+        &mut SolveScratch::default(),
+    )
+}
+
+/// `update`, reusing `scratch`'s allocations.
+pub fn update_in(
+    plan: &InterpolationPlan,
+    src: &Buffer,
+    dst: &Buffer,
+    weights: &MorphWeights,
+    options: &SolveOptions,
+    scratch: &mut SolveScratch,
+) -> InterpolationPlan {
+    solve(src, dst, weights, options, scratch, Some(plan))
+}
+
+fn solve(
+    src: &Buffer,
+    dst: &Buffer,
+    weights: &MorphWeights,
+    options: &SolveOptions,
+    scratch: &mut SolveScratch,
+    prior: Option<&InterpolationPlan>,
+) -> InterpolationPlan {
+    let started = Instant::now();
+    let area = dst.area;
+    let refitted;
+    let src = if src.area == area {
+        src
+    } else {
+        refitted = refit(src, area, options.anchor);
+        &refitted
+    };
 
     let width = area.width;
     let height = area.height;
@@ -17,55 +293,249 @@ pub fn diff(src: &Buffer, dst: &Buffer, weights: &MorphWeights) -> Interpolation
     let mut mutating = Vec::new();
     let mut src_unmatched: Vec<(u16, u16, CellSnapshot, ColorPair)> = Vec::new();
     let mut dst_unmatched: Vec<(u16, u16, CellSnapshot, ColorPair)> = Vec::new();
+    let mut skipped = Vec::new();
+
+    // Positions whose content moved away / was moved onto by a keyed
+    // region or a scrolled row.
+    let index =
+        |x: u16, y: u16| usize::from(y - area.y) * usize::from(width) + usize::from(x - area.x);
+    let excluded = |x: u16, y: u16| options.exclude.iter().any(|r| r.contains((x, y).into()));
+    let mut leaves = vec![false; usize::from(width) * usize::from(height)];
+    let mut arrives = leaves.clone();
+    let mut moved = Vec::new();
+
+    // Stable in `prior` and still the same in both frames.
+    let mut kept = vec![false; leaves.len()];
+    let same = |cell: &Cell, stable: &StableCell| {
+        cell.symbol() == stable.symbol
+            && cell.fg == stable.fg
+            && cell.bg == stable.bg
+            && cell.modifier == stable.modifier
+            && !cell.skip
+    };
+    // A classifier may disagree with the last plan, so it sees every cell.
+    let reusable = |plan: &&InterpolationPlan| plan.area() == area && options.classifier.is_none();
+    if let Some(plan) = prior.filter(reusable) {
+        for stable in &plan.stable {
+            let pos = (stable.x, stable.y);
+            if same(&src[pos], stable) && same(&dst[pos], stable) {
+                kept[index(stable.x, stable.y)] = true;
+            }
+        }
+    }
+
+    let travel = options.displacement != Displacement::Never;
+    let keyed: &[(Rect, Rect)] = if travel { &options.keyed } else { &[] };
+
+    for &(from, to) in keyed {
+        let (w, h) = (from.width.min(to.width), from.height.min(to.height));
+
+        for oy in 0..h {
+            for ox in 0..w {
+                let (s, d) = (
+                    (from.x.saturating_add(ox), from.y.saturating_add(oy)),
+                    (to.x.saturating_add(ox), to.y.saturating_add(oy)),
+                );
+                if !area.contains(s.into()) || !area.contains(d.into()) {
+                    continue;
+                }
+
+                let (sc, dc) = (&src[s], &dst[d]);
+                if leaves[index(s.0, s.1)]
+                    || arrives[index(d.0, d.1)]
+                    || sc.skip
+                    || dc.skip
+                    || excluded(s.0, s.1)
+                    || excluded(d.0, d.1)
+                {
+                    continue;
+                }
+                leaves[index(s.0, s.1)] = true;
+                arrives[index(d.0, d.1)] = true;
+
+                // The whole region moves, panel background included.
+                let visible =
+                    |cell: &Cell| !options.blank.is_blank(cell) || cell.bg != Color::Reset;
+                if visible(sc) || visible(dc) {
+                    let bg = ColorPair::from_color(sc.bg);
+                    moved.push(displaced_from(
+                        &(s.0, s.1, CellSnapshot::from_cell(sc), bg),
+                        &(d.0, d.1, CellSnapshot::from_cell(dc), bg),
+                    ));
+                }
+            }
+        }
+    }
+
+    let appended = travel && options.scroll && options.keyed.is_empty();
+    let appended = appended.then(|| appended_rows(src, dst, options)).flatten();
+
+    if travel && options.scroll {
+        let rows = match &appended {
+            Some(rows) => rows.clone(),
+            None => moved_rows(src, dst, options),
+        };
+
+        for (sy, dy) in rows {
+            let (from, to) = (index(area.x, sy), index(area.x, dy));
+            let row = usize::from(width);
+            if leaves[from..from + row].contains(&true) || arrives[to..to + row].contains(&true) {
+                continue;
+            }
+            leaves[from..from + row].fill(true);
+            arrives[to..to + row].fill(true);
+
+            for x in area.x..area.x + width {
+                let sc = &src[(x, sy)];
+                if !options.blank.is_blank(sc) {
+                    let bg = ColorPair::from_color(sc.bg);
+                    moved.push(displaced_from(
+                        &(x, sy, CellSnapshot::from_cell(sc), bg),
+                        &(x, dy, CellSnapshot::from_cell(&dst[(x, dy)]), bg),
+                    ));
+                }
+            }
+        }
+    }
+
+    let mut numbers = Vec::new();
+    let mut counting = vec![false; leaves.len()];
+
+    if options.numbers {
+        for number in match_numbers(src, dst) {
+            let y = number.y;
+            let span = number.x..number.x + number.width;
+            let free = span.clone().all(|x| {
+                let i = index(x, y);
+                !leaves[i]
+                    && !arrives[i]
+                    && !src[(x, y)].skip
+                    && !dst[(x, y)].skip
+                    && !excluded(x, y)
+            });
+
+            if free {
+                span.for_each(|x| counting[index(x, y)] = true);
+                numbers.push(number);
+            }
+        }
+    }
+
+    let mut borders = Vec::new();
+    // Line glyphs of moving outlines; the cells morph as if blank.
+    let mut src_lines = vec![false; leaves.len()];
+    let mut dst_lines = src_lines.clone();
+
+    if travel && options.borders {
+        let free = |(x, y): (u16, u16)| {
+            let i = index(x, y);
+            !leaves[i]
+                && !arrives[i]
+                && !counting[i]
+                && !src[(x, y)].skip
+                && !dst[(x, y)].skip
+                && !excluded(x, y)
+        };
+
+        for (from, to) in match_outlines(src, dst) {
+            if from.lines().chain(to.lines()).all(free) {
+                from.lines()
+                    .for_each(|(x, y)| src_lines[index(x, y)] = true);
+                to.lines().for_each(|(x, y)| dst_lines[index(x, y)] = true);
+                borders.push(BorderTween {
+                    from: from.area,
+                    to: to.area,
+                    src: border_style(src, &from),
+                    dst: border_style(dst, &to),
+                });
+            }
+        }
+    }
 
     for y in area.y..area.y + height {
         for x in area.x..area.x + width {
-            let sc = &src[(x, y)];
-            let dc = &dst[(x, y)];
+            let (src_blank, dst_blank);
+            let sc = if src_lines[index(x, y)] {
+                src_blank = blanked(&src[(x, y)]);
+                &src_blank
+            } else {
+                &src[(x, y)]
+            };
+            let dc = if dst_lines[index(x, y)] {
+                dst_blank = blanked(&dst[(x, y)]);
+                &dst_blank
+            } else {
+                &dst[(x, y)]
+            };
 
-            let same_symbol = sc.symbol() == dc.symbol();
-            let same_fg = sc.fg == dc.fg;
-            let same_bg = sc.bg == dc.bg;
-            let same_modifier = sc.modifier == dc.modifier;
+            if sc.skip || dc.skip {
+                skipped.push((x, y));
+                continue;
+            }
 
-            if same_symbol && same_fg && same_bg && same_modifier {
+            if excluded(x, y) {
                 stable.push(StableCell {
                     x,
                     y,
-                    symbol: sc.symbol().to_string(),
-                    fg: sc.fg,
-                    bg: sc.bg,
-                    modifier: sc.modifier,
+                    symbol: dc.symbol().to_string(),
+                    fg: dc.fg,
+                    bg: dc.bg,
+                    modifier: dc.modifier,
                 });
-            } else if !has_glyph(sc) && has_glyph(dc) {
-                dst_unmatched.push((
-                    x,
-                    y,
-                    CellSnapshot::from_cell(dc),
-                    ColorPair::from_color(sc.bg),
-                ));
-                bg_entry(&mut mutating, x, y, sc, dc);
-            } else if has_glyph(sc) && !has_glyph(dc) {
-                src_unmatched.push((
-                    x,
-                    y,
-                    CellSnapshot::from_cell(sc),
-                    ColorPair::from_color(dc.bg),
-                ));
+                continue;
+            }
+
+            let (left, arrived) = (leaves[index(x, y)], arrives[index(x, y)]);
+            if left || arrived {
+                // A moved region or row covers its glyphs; whatever else
+                // was or will be here is matched as usual.
+                if !left && !options.blank.is_blank(sc) {
+                    let bg = ColorPair::from_color(dc.bg);
+                    src_unmatched.push((x, y, CellSnapshot::from_cell(sc), bg));
+                }
+                if !arrived && !options.blank.is_blank(dc) {
+                    let bg = ColorPair::from_color(sc.bg);
+                    dst_unmatched.push((x, y, CellSnapshot::from_cell(dc), bg));
+                }
                 bg_entry(&mut mutating, x, y, sc, dc);
-            } else if !has_glyph(sc) && same_bg {
-                // Both blank, same bg, minor style difference — snap.
-                stable.push(StableCell {
+                continue;
+            }
+
+            let i = index(x, y);
+            let class = if counting[i] {
+                // Redrawn by the number's tween; kept out of matching.
+                CellClass::Mutating
+            } else if kept[i] && !src_lines[i] && !dst_lines[i] {
+                CellClass::Stable
+            } else {
+                let default = default_class(sc, dc, &options.blank);
+                match &options.classifier {
+                    Some(classifier) => classifier.classify(sc, dc, default),
+                    None => default,
+                }
+            };
+
+            match class {
+                CellClass::Stable => stable.push(StableCell {
                     x,
                     y,
                     symbol: dc.symbol().to_string(),
                     fg: dc.fg,
                     bg: dc.bg,
                     modifier: dc.modifier,
-                });
-            } else {
-                // Both have glyphs, or both blank with different bg.
-                mutating.push(MutatingCell {
+                }),
+                CellClass::Unmatched => {
+                    if !options.blank.is_blank(sc) {
+                        let bg = ColorPair::from_color(dc.bg);
+                        src_unmatched.push((x, y, CellSnapshot::from_cell(sc), bg));
+                    }
+                    if !options.blank.is_blank(dc) {
+                        let bg = ColorPair::from_color(sc.bg);
+                        dst_unmatched.push((x, y, CellSnapshot::from_cell(dc), bg));
+                    }
+                    bg_entry(&mut mutating, x, y, sc, dc);
+                }
+                CellClass::Mutating => mutating.push(MutatingCell {
                     x,
                     y,
                     src_symbol: sc.symbol().to_string(),
@@ -76,15 +546,65 @@ pub fn diff(src: &Buffer, dst: &Buffer, weights: &MorphWeights) -> Interpolation
                     dst_bg: ColorPair::from_color(dc.bg),
                     src_modifier: sc.modifier,
                     dst_modifier: dc.modifier,
-                });
+                }),
             }
         }
     }
 
-    let (displaced, appearing, disappearing) =
-        solve_unmatched(&src_unmatched, &dst_unmatched, weights);
+    let classified = Instant::now();
+    let mut blocks = Vec::new();
+    if travel && options.components && appended.is_none() {
+        blocks = match_components(&mut src_unmatched, &mut dst_unmatched, weights);
+    }
+    if travel && options.words && appended.is_none() {
+        blocks.extend(match_words(&mut src_unmatched, &mut dst_unmatched, weights));
+    }
+
+    let unmatched = src_unmatched.len().max(dst_unmatched.len());
+    let strategy = match appended {
+        Some(_) if options.displacement == Displacement::Costed => MatchStrategy::Crossfade,
+        _ => options.resolve(unmatched),
+    };
+    let (n, m) = match strategy {
+        MatchStrategy::Hungarian | MatchStrategy::Auction => {
+            capped(src_unmatched.len(), dst_unmatched.len(), options.max_pairs)
+        }
+        _ => (src_unmatched.len(), dst_unmatched.len()),
+    };
+    let (mut displaced, mut appearing, mut disappearing) = match strategy {
+        MatchStrategy::Crossfade => (Vec::new(), orphans(&dst_unmatched), orphans(&src_unmatched)),
+        strategy => solve_unmatched(
+            &src_unmatched[..n],
+            &dst_unmatched[..m],
+            weights,
+            options,
+            strategy,
+            scratch,
+        ),
+    };
+    if strategy != MatchStrategy::Crossfade {
+        appearing.extend(orphans(&dst_unmatched[m..]));
+        disappearing.extend(orphans(&src_unmatched[n..]));
+    }
+    displaced.extend(blocks);
+    displaced.extend(moved);
+
+    let matrix = match strategy {
+        MatchStrategy::Hungarian | MatchStrategy::Auction if n > 0 && m > 0 => (n, m),
+        _ => (0, 0),
+    };
+    scratch.stats = SolveStats {
+        cells: usize::from(width) * usize::from(height),
+        src_unmatched: src_unmatched.len(),
+        dst_unmatched: dst_unmatched.len(),
+        strategy,
+        matrix,
+        classify_time: classified - started,
+        assign_time: classified.elapsed(),
+    };
 
     InterpolationPlan {
+        origin: area.as_position(),
         width,
         height,
         stable,
@@ -92,12 +612,107 @@ pub fn diff(src: &Buffer, dst: &Buffer, weights: &MorphWeights) -> Interpolation
         displaced,
         appearing,
         disappearing,
+        skipped,
+        numbers,
+        borders,
+    }
+}
+
+/// `cell` without its glyph, background kept.
+fn blanked(cell: &Cell) -> Cell {
+    let mut blank = Cell::default();
+    blank.bg = cell.bg;
+    blank
+}
+
+/// Box outlines of the target, each paired with the source outline it
+/// overlaps most. Outlines that stayed put are left to ordinary matching.
+fn match_outlines(src: &Buffer, dst: &Buffer) -> Vec<(Outline, Outline)> {
+    let (mut sources, mut targets) = (boxdraw::outlines(src), boxdraw::outlines(dst));
+    let stayed: Vec<Rect> = sources
+        .iter()
+        .filter(|s| targets.iter().any(|d| d.area == s.area))
+        .map(|s| s.area)
+        .collect();
+    sources.retain(|s| !stayed.contains(&s.area));
+    targets.retain(|d| !stayed.contains(&d.area));
+
+    let mut pairs = Vec::new();
+    for to in targets {
+        let overlap = |s: &Outline| {
+            let shared = s.area.intersection(to.area);
+            u32::from(shared.width) * u32::from(shared.height)
+        };
+        let best = (0..sources.len())
+            .filter(|&i| overlap(&sources[i]) > 0)
+            .max_by_key(|&i| overlap(&sources[i]));
+
+        if let Some(i) = best {
+            pairs.push((sources.swap_remove(i), to));
+        }
+    }
+
+    pairs
+}
+
+fn border_style(buf: &Buffer, outline: &Outline) -> BorderStyle {
+    let area = outline.area;
+    let (right, bottom) = (area.right() - 1, area.bottom() - 1);
+    let corner = &buf[(area.x, area.y)];
+    let symbol = |at: Option<(u16, u16)>, fallback: &str| {
+        at.map_or(fallback.to_string(), |at| buf[at].symbol().to_string())
+    };
+    let horizontal = (area.x + 1..right).find(|&x| !outline.top_gaps.contains(&(x - area.x)));
+    let vertical = (area.y + 1 < bottom).then_some((area.x, area.y + 1));
+
+    BorderStyle {
+        corners: [
+            (area.x, area.y),
+            (right, area.y),
+            (area.x, bottom),
+            (right, bottom),
+        ]
+        .map(|at| buf[at].symbol().to_string()),
+        horizontal: symbol(horizontal.map(|x| (x, area.y)), "─"),
+        vertical: symbol(vertical, "│"),
+        fg: ColorPair::from_color(corner.fg),
+        modifier: corner.modifier,
+        top_gaps: outline.top_gaps.clone(),
+        bottom_gaps: outline.bottom_gaps.clone(),
     }
 }
 
-fn has_glyph(cell: &ratatui::buffer::Cell) -> bool {
-    let sym = cell.symbol();
-    sym != " " && !sym.is_empty()
+/// `buf` moved into `area` at `anchor`, cropped where it overhangs and
+/// blank where it falls short.
+pub fn refit(buf: &Buffer, area: Rect, anchor: ResizeAnchor) -> Buffer {
+    let from = buf.area;
+    let (w, h) = (i32::from(from.width), i32::from(from.height));
+    let (aw, ah) = (i32::from(area.width), i32::from(area.height));
+
+    // Position of `from`'s top-left corner within `area`.
+    let (left, top) = match anchor {
+        ResizeAnchor::TopLeft => (0, 0),
+        ResizeAnchor::Center => ((aw - w) / 2, (ah - h) / 2),
+        ResizeAnchor::BottomLeft => (0, ah - h),
+    };
+
+    let mut out = Buffer::empty(area);
+
+    for y in 0..ah {
+        for x in 0..aw {
+            let (sx, sy) = (x - left, y - top);
+
+            if !(0..w).contains(&sx) || !(0..h).contains(&sy) {
+                continue;
+            }
+
+            // In range by the checks above.
+            let src = (from.x + sx as u16, from.y + sy as u16);
+            out[(area.x + x as u16, area.y + y as u16)] = buf[src].clone();
+        }
+    }
+
+    out
 }
 
 /// Background-only mutating entry. Ensures bg interpolates at positions
@@ -141,6 +756,17 @@ impl CellSnapshot {
             modifier: cell.modifier,
         }
     }
+
+    fn view(&self, x: u16, y: u16) -> MatchCell<'_> {
+        MatchCell {
+            x,
+            y,
+            symbol: &self.symbol,
+            fg: self.fg.raw,
+            bg: self.bg.raw,
+            modifier: self.modifier,
+        }
+    }
 }
 
 fn orphan_from(x: u16, y: u16, snap: &CellSnapshot, counter_bg: ColorPair) -> OrphanCell {
@@ -155,47 +781,64 @@ fn orphan_from(x: u16, y: u16, snap: &CellSnapshot, counter_bg: ColorPair) -> Or
     }
 }
 
+fn orphans(cells: &[(u16, u16, CellSnapshot, ColorPair)]) -> Vec<OrphanCell> {
+    cells
+        .iter()
+        .map(|(x, y, s, cbg)| orphan_from(*x, *y, s, *cbg))
+        .collect()
+}
+
 fn solve_unmatched(
     src: &[(u16, u16, CellSnapshot, ColorPair)],
     dst: &[(u16, u16, CellSnapshot, ColorPair)],
     weights: &MorphWeights,
+    options: &SolveOptions,
+    strategy: MatchStrategy,
+    scratch: &mut SolveScratch,
 ) -> (Vec<DisplacedCell>, Vec<OrphanCell>, Vec<OrphanCell>) {
-    if src.is_empty() && dst.is_empty() {
-        return (Vec::new(), Vec::new(), Vec::new());
-    }
-
-    if src.is_empty() {
-        let appearing = dst
-            .iter()
-            .map(|(x, y, s, cbg)| orphan_from(*x, *y, s, *cbg))
-            .collect();
-        return (Vec::new(), appearing, Vec::new());
-    }
-
-    if dst.is_empty() {
-        let disappearing = src
-            .iter()
-            .map(|(x, y, s, cbg)| orphan_from(*x, *y, s, *cbg))
-            .collect();
-        return (Vec::new(), Vec::new(), disappearing);
+    if src.is_empty() || dst.is_empty() {
+        return (Vec::new(), orphans(dst), orphans(src));
     }
 
-    let n = src.len();
     let m = dst.len();
-    let mut cost = vec![vec![0.0f32; m]; n];
+    let cost = |i: usize, j: usize| {
+        let (sx, sy, ss, _) = &src[i];
+        let (dx, dy, ds, _) = &dst[j];
+        let base = cell_cost((*sx, *sy), ss, (*dx, *dy), ds, weights, &options.glyphs);
+
+        quantize(match &options.cost {
+            Some(custom) => custom.cost(&ss.view(*sx, *sy), &ds.view(*dx, *dy), base),
+            None => base,
+        })
+    };
 
-    for (i, (sx, sy, ss, _)) in src.iter().enumerate() {
-        for (j, (dx, dy, ds, _)) in dst.iter().enumerate() {
-            cost[i][j] = cell_cost(*sx, *sy, ss, *dx, *dy, ds, weights);
+    // Above this cost, fade out + fade in is cheaper than displacement.
+    let threshold = match options.displacement {
+        Displacement::Always => f32::INFINITY,
+        _ => {
+            weights.glyph_mismatch * weights.glyph * 2.0
+                + weights.spatial * 100.0
+                + weights.color * 0.5
+                + weights.background * 0.5
         }
-    }
+    };
 
-    // Above this cost, fade out + fade in is cheaper than displacement.
-    let threshold = weights.glyph_mismatch * weights.glyph * 2.0
-        + weights.spatial * 100.0
-        + weights.color * 0.5;
+    let assignment = match strategy {
+        MatchStrategy::Greedy => greedy(src, dst, cost),
+        _ => {
+            let mut costs = std::mem::take(&mut scratch.costs);
+            cost_matrix(&mut costs, src.len(), m, &cost);
 
-    let assignment = hungarian(&cost, n, m);
+            let mut assignment = if strategy == MatchStrategy::Auction {
+                auction(&costs, src.len(), m)
+            } else {
+                hungarian(&costs, src.len(), m, scratch)
+            };
+            settle_ties(&mut assignment, src, dst, &costs, threshold);
+            scratch.costs = costs;
+            assignment
+        }
+    };
 
     let mut displaced = Vec::new();
     let mut appearing = Vec::new();
@@ -204,25 +847,8 @@ fn solve_unmatched(
 
     for (i, matched_j) in assignment.iter().enumerate() {
         match matched_j {
-            Some(j) if cost[i][*j] <= threshold => {
-                let (sx, sy, ss, _) = &src[i];
-                let (dx, dy, ds, _) = &dst[*j];
-
-                displaced.push(DisplacedCell {
-                    src_x: *sx,
-                    src_y: *sy,
-                    dst_x: *dx,
-                    dst_y: *dy,
-                    src_symbol: ss.symbol.clone(),
-                    dst_symbol: ds.symbol.clone(),
-                    src_fg: ss.fg,
-                    dst_fg: ds.fg,
-                    src_bg: ss.bg,
-                    dst_bg: ds.bg,
-                    src_modifier: ss.modifier,
-                    dst_modifier: ds.modifier,
-                });
-
+            Some(j) if cost(i, *j) <= threshold => {
+                displaced.push(displaced_from(&src[i], &dst[*j]));
                 dst_matched[*j] = true;
             }
 
@@ -242,14 +868,600 @@ fn solve_unmatched(
     (displaced, appearing, disappearing)
 }
 
+/// How many of `n` sources and `m` targets to pair so `n × m` stays within
+/// `cap`: the smaller side whole if it fits, the larger filling the rest.
+fn capped(n: usize, m: usize, cap: usize) -> (usize, usize) {
+    if n.saturating_mul(m) <= cap {
+        return (n, m);
+    }
+
+    let small = n.min(m).min(cap.isqrt());
+    let large = n.max(m).min(cap.checked_div(small).unwrap_or(0));
+    if n <= m {
+        (small, large)
+    } else {
+        (large, small)
+    }
+}
+
+/// Fill `costs` row-major with every source's costs, across threads with
+/// the `rayon` feature.
+fn cost_matrix(
+    costs: &mut Vec<f32>,
+    n: usize,
+    m: usize,
+    cost: &(impl Fn(usize, usize) -> f32 + Sync),
+) {
+    reset(costs, n * m, 0.0);
+
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+
+        costs.par_chunks_mut(m).enumerate().for_each(|(i, row)| {
+            for (j, c) in row.iter_mut().enumerate() {
+                *c = cost(i, j);
+            }
+        });
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    for (i, row) in costs.chunks_mut(m).enumerate() {
+        for (j, c) in row.iter_mut().enumerate() {
+            *c = cost(i, j);
+        }
+    }
+}
+
+/// Numbers whose value changed in place: a number in the source and one
+/// on the same row of the target that share their last column (or, failing
+/// that, their first).
+fn match_numbers(src: &Buffer, dst: &Buffer) -> Vec<NumberTween> {
+    let area = dst.area;
+    if area.is_empty() {
+        return Vec::new();
+    }
+
+    let width = usize::from(area.width);
+    let rows = src.content.chunks(width).zip(dst.content.chunks(width));
+    let mut numbers = Vec::new();
+
+    for (y, (src_row, dst_row)) in (area.y..).zip(rows) {
+        let mut src_runs = number_runs(src_row);
+
+        for (start, end, to, dst_decimals) in number_runs(dst_row) {
+            let paired = src_runs
+                .iter()
+                .position(|run| run.1 == end)
+                .or_else(|| src_runs.iter().position(|run| run.0 == start));
+            let Some(i) = paired else {
+                continue;
+            };
+
+            let (src_start, src_end, from, src_decimals) = src_runs.swap_remove(i);
+            if from == to && src_decimals == dst_decimals {
+                continue;
+            }
+
+            // Within the row, so within `u16`.
+            let left = start.min(src_start);
+            numbers.push(NumberTween {
+                x: area.x + left as u16,
+                y,
+                width: (end.max(src_end) - left) as u16,
+                from,
+                to,
+                src_width: (src_end - src_start) as u16,
+                dst_width: (end - start) as u16,
+                src_decimals,
+                dst_decimals,
+                right_aligned: src_end == end,
+            });
+        }
+    }
+
+    numbers
+}
+
+/// `(start, end, value, decimals)` of each number on a row: `-?\d+(\.\d+)?`,
+/// not following a letter, written the way `format_number` would write it
+/// (so `007` and `1e5` are left alone).
+fn number_runs(row: &[Cell]) -> Vec<(usize, usize, f64, u8)> {
+    let chars: Vec<Option<char>> = row
+        .iter()
+        .map(|cell| {
+            let mut chars = cell.symbol().chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Some(c),
+                _ => None,
+            }
+        })
+        .collect();
+    let at = |i: usize| chars.get(i).copied().flatten();
+    let digit = |i: usize| at(i).is_some_and(|c| c.is_ascii_digit());
+
+    let mut runs = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let start = i;
+        let minus = at(i) == Some('-') && digit(i + 1);
+        let attached = i > 0 && at(i - 1).is_some_and(|c| c.is_alphanumeric() || c == '.');
+
+        if attached || !(minus || digit(i)) {
+            i += 1;
+            continue;
+        }
+
+        i += usize::from(minus);
+        while digit(i) {
+            i += 1;
+        }
+
+        let mut decimals = 0u8;
+        if at(i) == Some('.') && digit(i + 1) {
+            i += 1;
+            while digit(i) {
+                i += 1;
+                decimals = decimals.saturating_add(1);
+            }
+        }
+
+        let text: String = chars[start..i].iter().flatten().collect();
+        if let Ok(value) = text.parse::<f64>()
+            && format_number(value, decimals) == text
+        {
+            runs.push((start, i, value, decimals));
+        }
+    }
+
+    runs
+}
+
+/// If `dst` is `src` with lines appended to a log, the rows that shifted,
+/// as `(src_y, dst_y)`: between the leading and trailing rows that didn't
+/// change, every target row is the source row `k` below it, for the
+/// smallest such `k`, except the last `k`, which are new.
+fn appended_rows(src: &Buffer, dst: &Buffer, options: &SolveOptions) -> Option<Vec<(u16, u16)>> {
+    let area = dst.area;
+    if area.is_empty() {
+        return None;
+    }
+
+    let src_rows: Vec<&[Cell]> = src.content.chunks(usize::from(area.width)).collect();
+    let dst_rows: Vec<&[Cell]> = dst.content.chunks(usize::from(area.width)).collect();
+    let changed = |&y: &usize| src_rows[y] != dst_rows[y];
+    let top = (0..dst_rows.len()).find(changed)?;
+    let bottom = (0..dst_rows.len()).rfind(changed)? + 1;
+
+    // Within the area, so within `u16`.
+    let band = Rect::new(
+        area.x,
+        area.y + top as u16,
+        area.width,
+        (bottom - top) as u16,
+    );
+    let plain = |row: &[Cell]| !row.iter().any(|cell| cell.skip);
+    if options.exclude.iter().any(|r| r.intersects(band))
+        || !src_rows[top..bottom].iter().all(|row| plain(row))
+        || !dst_rows[top..bottom].iter().all(|row| plain(row))
+    {
+        return None;
+    }
+
+    let k =
+        (1..bottom - top).find(|&k| (top..bottom - k).all(|y| dst_rows[y] == src_rows[y + k]))?;
+    let content = (top..bottom - k).any(|y| dst_rows[y].iter().any(|c| !options.blank.is_blank(c)));
+
+    content.then(|| {
+        (top..bottom - k)
+            .map(|y| (area.y + (y + k) as u16, area.y + y as u16))
+            .collect()
+    })
+}
+
+/// Rows that moved intact, as `(src_y, dst_y)`: the longest common
+/// subsequence of the two frames' rows, counting only rows with glyphs and
+/// nothing skipped or excluded, less those that stayed put.
+fn moved_rows(src: &Buffer, dst: &Buffer, options: &SolveOptions) -> Vec<(u16, u16)> {
+    let area = dst.area;
+    if area.is_empty() {
+        return Vec::new();
+    }
+
+    let ys: Vec<u16> = (area.y..area.bottom()).collect();
+    let movable = |(i, cells): (usize, &[Cell])| {
+        let line = Rect::new(area.x, ys[i], area.width, 1);
+        cells.iter().any(|cell| !options.blank.is_blank(cell))
+            && !cells.iter().any(|cell| cell.skip)
+            && !options.exclude.iter().any(|r| r.intersects(line))
+    };
+    let src_rows: Vec<&[Cell]> = src.content.chunks(usize::from(area.width)).collect();
+    let dst_rows: Vec<&[Cell]> = dst.content.chunks(usize::from(area.width)).collect();
+    let src_movable: Vec<bool> = src_rows.iter().copied().enumerate().map(movable).collect();
+    let dst_movable: Vec<bool> = dst_rows.iter().copied().enumerate().map(movable).collect();
+    let same = |i: usize, j: usize| src_movable[i] && dst_movable[j] && src_rows[i] == dst_rows[j];
+
+    // lcs[i][j]: length of the LCS of src rows i.. and dst rows j..
+    let h = ys.len();
+    let mut lcs = vec![vec![0u16; h + 1]; h + 1];
+    for i in (0..h).rev() {
+        for j in (0..h).rev() {
+            lcs[i][j] = if same(i, j) {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut moved = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < h && j < h {
+        if same(i, j) {
+            if i != j {
+                moved.push((ys[i], ys[j]));
+            }
+            (i, j) = (i + 1, j + 1);
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+
+    moved
+}
+
+fn displaced_from(
+    (sx, sy, ss, _): &(u16, u16, CellSnapshot, ColorPair),
+    (dx, dy, ds, _): &(u16, u16, CellSnapshot, ColorPair),
+) -> DisplacedCell {
+    DisplacedCell {
+        src_x: *sx,
+        src_y: *sy,
+        dst_x: *dx,
+        dst_y: *dy,
+        src_symbol: ss.symbol.clone(),
+        dst_symbol: ds.symbol.clone(),
+        src_fg: ss.fg,
+        dst_fg: ds.fg,
+        src_bg: ss.bg,
+        dst_bg: ds.bg,
+        src_modifier: ss.modifier,
+        dst_modifier: ds.modifier,
+    }
+}
+
+/// Move multi-cell blocks that appear unchanged on both sides as a unit,
+/// removing their cells from `src` and `dst`. Blocks are 4-connected
+/// groups of unmatched glyphs (a word, a panel's text); a source block
+/// pairs with the nearest unclaimed destination block of the same shape
+/// and symbols, cheapest first.
+fn match_components(
+    src: &mut Vec<(u16, u16, CellSnapshot, ColorPair)>,
+    dst: &mut Vec<(u16, u16, CellSnapshot, ColorPair)>,
+    weights: &MorphWeights,
+) -> Vec<DisplacedCell> {
+    let src_blocks = components(src);
+    let dst_blocks = components(dst);
+
+    let mut pairs = Vec::new();
+    for (a, from) in src_blocks.iter().enumerate() {
+        for (b, to) in dst_blocks.iter().enumerate() {
+            if from.len() > 1 && same_shape(src, from, dst, to) {
+                let (sx, sy, ..) = &src[from[0]];
+                let (dx, dy, ..) = &dst[to[0]];
+                let (dx, dy) = (
+                    f32::from(*dx) - f32::from(*sx),
+                    f32::from(*dy) - f32::from(*sy),
+                );
+                let color: f32 = from
+                    .iter()
+                    .zip(to)
+                    .map(|(&i, &j)| match (src[i].2.fg.oklch, dst[j].2.fg.oklch) {
+                        (Some(a), Some(b)) => oklch::distance(a, b),
+                        _ => 0.5,
+                    })
+                    .sum();
+                let cost = weights.spatial * (dx * dx + dy * dy)
+                    + weights.color * color / from.len() as f32;
+                pairs.push((cost, a, b));
+            }
+        }
+    }
+    pairs.sort_by(|x, y| x.0.total_cmp(&y.0));
+
+    let mut src_taken = vec![false; src.len()];
+    let mut dst_taken = vec![false; dst.len()];
+    let (mut src_claimed, mut dst_claimed) =
+        (vec![false; src_blocks.len()], vec![false; dst_blocks.len()]);
+    let mut displaced = Vec::new();
+
+    for (_, a, b) in pairs {
+        if src_claimed[a] || dst_claimed[b] {
+            continue;
+        }
+        (src_claimed[a], dst_claimed[b]) = (true, true);
+
+        for (&i, &j) in src_blocks[a].iter().zip(&dst_blocks[b]) {
+            displaced.push(displaced_from(&src[i], &dst[j]));
+            (src_taken[i], dst_taken[j]) = (true, true);
+        }
+    }
+
+    remove_taken(src, src_taken);
+    remove_taken(dst, dst_taken);
+    displaced
+}
+
+/// Pair runs of same-style glyphs on a row across frames, removing the
+/// paired cells from `src` and `dst`. A source run goes to the nearest
+/// unclaimed destination run sharing at least half its letters at the
+/// same offsets, cheapest first; cells pair up from the left, and any
+/// overhang of the longer run is left to cell matching.
+fn match_words(
+    src: &mut Vec<(u16, u16, CellSnapshot, ColorPair)>,
+    dst: &mut Vec<(u16, u16, CellSnapshot, ColorPair)>,
+    weights: &MorphWeights,
+) -> Vec<DisplacedCell> {
+    let src_runs = runs(src);
+    let dst_runs = runs(dst);
+
+    let mut pairs = Vec::new();
+    for (a, from) in src_runs.iter().enumerate() {
+        for (b, to) in dst_runs.iter().enumerate() {
+            let shared = from
+                .clone()
+                .zip(to.clone())
+                .filter(|&(i, j)| src[i].2.symbol == dst[j].2.symbol)
+                .count();
+            let longest = from.len().max(to.len());
+
+            if 2 * shared >= longest {
+                let (sx, sy, ..) = &src[from.start];
+                let (dx, dy, ..) = &dst[to.start];
+                let (dx, dy) = (
+                    f32::from(*dx) - f32::from(*sx),
+                    f32::from(*dy) - f32::from(*sy),
+                );
+                let mismatch = 1.0 - shared as f32 / longest as f32;
+                let cost = weights.spatial * (dx * dx + dy * dy)
+                    + weights.glyph * weights.glyph_mismatch * mismatch;
+                pairs.push((cost, a, b));
+            }
+        }
+    }
+    pairs.sort_by(|x, y| x.0.total_cmp(&y.0));
+
+    let mut src_taken = vec![false; src.len()];
+    let mut dst_taken = vec![false; dst.len()];
+    let (mut src_claimed, mut dst_claimed) =
+        (vec![false; src_runs.len()], vec![false; dst_runs.len()]);
+    let mut displaced = Vec::new();
+
+    for (_, a, b) in pairs {
+        if src_claimed[a] || dst_claimed[b] {
+            continue;
+        }
+        (src_claimed[a], dst_claimed[b]) = (true, true);
+
+        for (i, j) in src_runs[a].clone().zip(dst_runs[b].clone()) {
+            displaced.push(displaced_from(&src[i], &dst[j]));
+            (src_taken[i], dst_taken[j]) = (true, true);
+        }
+    }
+
+    remove_taken(src, src_taken);
+    remove_taken(dst, dst_taken);
+    displaced
+}
+
+/// Horizontally adjacent, same-style runs of two or more `cells`, as index
+/// ranges; `cells` is row-major.
+fn runs(cells: &[(u16, u16, CellSnapshot, ColorPair)]) -> Vec<std::ops::Range<usize>> {
+    let continues = |(px, py, ps, _): &(u16, u16, CellSnapshot, ColorPair),
+                     (x, y, s, _): &(u16, u16, CellSnapshot, ColorPair)| {
+        *py == *y
+            && px.checked_add(1) == Some(*x)
+            && ps.fg.raw == s.fg.raw
+            && ps.bg.raw == s.bg.raw
+            && ps.modifier == s.modifier
+    };
+
+    let mut runs = Vec::new();
+    let mut start = 0;
+
+    for i in 1..=cells.len() {
+        if i == cells.len() || !continues(&cells[i - 1], &cells[i]) {
+            if i - start > 1 {
+                runs.push(start..i);
+            }
+            start = i;
+        }
+    }
+
+    runs
+}
+
+fn remove_taken<T>(cells: &mut Vec<T>, taken: Vec<bool>) {
+    let mut taken = taken.into_iter();
+    cells.retain(|_| !taken.next().unwrap_or(false));
+}
+
+/// 4-connected groups of `cells`, as indices in row-major order.
+fn components(cells: &[(u16, u16, CellSnapshot, ColorPair)]) -> Vec<Vec<usize>> {
+    let at: HashMap<(u16, u16), usize> = cells
+        .iter()
+        .enumerate()
+        .map(|(i, (x, y, ..))| ((*x, *y), i))
+        .collect();
+    let mut seen = vec![false; cells.len()];
+    let mut groups = Vec::new();
+
+    for start in 0..cells.len() {
+        if std::mem::replace(&mut seen[start], true) {
+            continue;
+        }
+
+        let mut group = vec![start];
+        let mut next = 0;
+
+        while let Some(&i) = group.get(next) {
+            next += 1;
+            let (x, y, ..) = cells[i];
+            let neighbours = [
+                x.checked_sub(1).map(|x| (x, y)),
+                x.checked_add(1).map(|x| (x, y)),
+                y.checked_sub(1).map(|y| (x, y)),
+                y.checked_add(1).map(|y| (x, y)),
+            ];
+
+            for pos in neighbours.into_iter().flatten() {
+                if let Some(&j) = at.get(&pos)
+                    && !std::mem::replace(&mut seen[j], true)
+                {
+                    group.push(j);
+                }
+            }
+        }
+
+        group.sort_unstable();
+        groups.push(group);
+    }
+
+    groups
+}
+
+/// Same symbols at the same offsets from each block's first cell.
+fn same_shape(
+    src: &[(u16, u16, CellSnapshot, ColorPair)],
+    from: &[usize],
+    dst: &[(u16, u16, CellSnapshot, ColorPair)],
+    to: &[usize],
+) -> bool {
+    let offset = |cells: &[(u16, u16, CellSnapshot, ColorPair)], anchor: usize, i: usize| {
+        let (ax, ay, ..) = cells[anchor];
+        let (x, y, ..) = cells[i];
+        (i32::from(x) - i32::from(ax), i32::from(y) - i32::from(ay))
+    };
+
+    from.len() == to.len()
+        && from.iter().zip(to).all(|(&i, &j)| {
+            src[i].2.symbol == dst[j].2.symbol && offset(src, from[0], i) == offset(dst, to[0], j)
+        })
+}
+
+/// Destinations bucketed by symbol; each source, in row-major order, claims
+/// the cheapest unclaimed destination in its bucket.
+fn greedy(
+    src: &[(u16, u16, CellSnapshot, ColorPair)],
+    dst: &[(u16, u16, CellSnapshot, ColorPair)],
+    cost: impl Fn(usize, usize) -> f32,
+) -> Vec<Option<usize>> {
+    let mut buckets: HashMap<&str, Vec<usize>> = HashMap::new();
+
+    for (j, (_, _, snap, _)) in dst.iter().enumerate() {
+        buckets.entry(snap.symbol.as_str()).or_default().push(j);
+    }
+
+    src.iter()
+        .enumerate()
+        .map(|(i, (_, _, snap, _))| {
+            let bucket = buckets.get_mut(snap.symbol.as_str())?;
+            let (k, _) = bucket.iter().enumerate().min_by(|a, b| {
+                cost(i, *a.1)
+                    .total_cmp(&cost(i, *b.1))
+                    .then(distance(&src[i], &dst[*a.1]).cmp(&distance(&src[i], &dst[*b.1])))
+                    .then(a.1.cmp(b.1))
+            })?;
+            Some(bucket.swap_remove(k))
+        })
+        .collect()
+}
+
+/// Granularity of match costs; see `MatchStrategy`.
+pub const COST_QUANTUM: f32 = 1.0 / 1024.0;
+
+fn quantize(cost: f32) -> f32 {
+    let rounded = (cost / COST_QUANTUM).round() * COST_QUANTUM;
+    if rounded.is_finite() { rounded } else { cost }
+}
+
+/// Squared displacement between an unmatched source and destination.
+fn distance(
+    (sx, sy, ..): &(u16, u16, CellSnapshot, ColorPair),
+    (dx, dy, ..): &(u16, u16, CellSnapshot, ColorPair),
+) -> u32 {
+    let (dx, dy) = (u32::from(sx.abs_diff(*dx)), u32::from(sy.abs_diff(*dy)));
+    dx * dx + dy * dy
+}
+
+/// Passes `settle_ties` makes over each cost bucket.
+const SETTLE_PASSES: usize = 4;
+
+/// Hungarian and auction land on an arbitrary optimum. Among matches of
+/// equal cost, swap the targets of two whenever both still cost the same
+/// after the swap and it moves less, or moves as much and uncrosses them.
+/// At most `SETTLE_PASSES` passes, so a pathological bucket can't stall
+/// the solve.
+fn settle_ties(
+    assignment: &mut [Option<usize>],
+    src: &[(u16, u16, CellSnapshot, ColorPair)],
+    dst: &[(u16, u16, CellSnapshot, ColorPair)],
+    costs: &[f32],
+    threshold: f32,
+) {
+    let m = dst.len();
+    let cost = |i: usize, j: usize| costs[i * m + j];
+
+    let mut buckets: HashMap<u32, Vec<usize>> = HashMap::new();
+    for (i, matched) in assignment.iter().enumerate() {
+        if let Some(j) = *matched
+            && cost(i, j) <= threshold
+        {
+            buckets.entry(cost(i, j).to_bits()).or_default().push(i);
+        }
+    }
+
+    // A swap keeps both matches in their bucket, so buckets settle
+    // independently and in any order.
+    for (bits, bucket) in buckets {
+        let same = |i: usize, j: usize| cost(i, j).to_bits() == bits;
+
+        for _ in 0..SETTLE_PASSES {
+            let mut changed = false;
+            for (k, &a) in bucket.iter().enumerate() {
+                for &b in &bucket[k + 1..] {
+                    let (Some(j), Some(l)) = (assignment[a], assignment[b]) else {
+                        continue;
+                    };
+                    if !(same(a, l) && same(b, j)) {
+                        continue;
+                    }
+
+                    let kept = distance(&src[a], &dst[j]) + distance(&src[b], &dst[l]);
+                    let swapped = distance(&src[a], &dst[l]) + distance(&src[b], &dst[j]);
+                    if swapped < kept || (swapped == kept && l < j) {
+                        assignment.swap(a, b);
+                        changed = true;
+                    }
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+    }
+}
+
 fn cell_cost(
-    sx: u16,
-    sy: u16,
+    (sx, sy): (u16, u16),
     ss: &CellSnapshot,
-    dx: u16,
-    dy: u16,
+    (dx, dy): (u16, u16),
     ds: &CellSnapshot,
     w: &MorphWeights,
+    glyphs: &GlyphTable,
 ) -> f32 {
     let spatial = {
         let dx_f = (dx as f32) - (sx as f32);
@@ -257,41 +1469,97 @@ fn cell_cost(
         dx_f * dx_f + dy_f * dy_f
     };
 
-    let glyph = if ss.symbol == ds.symbol {
-        0.0
-    } else {
-        w.glyph_mismatch
-    };
+    let glyph = w.glyph_mismatch * glyphs.mismatch(&ss.symbol, &ds.symbol);
 
     let color = match (ss.fg.oklch, ds.fg.oklch) {
         (Some(a), Some(b)) => oklch::distance(a, b),
         _ => 0.5,
     };
 
-    w.spatial * spatial + w.glyph * glyph + w.color * color
+    let background = match (ss.bg.oklch, ds.bg.oklch) {
+        (Some(a), Some(b)) => oklch::distance(a, b),
+        _ if ss.bg.raw == ds.bg.raw => 0.0,
+        _ => 0.5,
+    };
+
+    w.spatial * spatial + w.glyph * glyph + w.color * color + w.background * background
 }
 
-/// Pads to square internally — the algorithm requires it.
-fn hungarian(cost: &[Vec<f32>], n: usize, m: usize) -> Vec<Option<usize>> {
-    let size = n.max(m);
-    let mut c = vec![vec![0.0f32; size]; size];
+/// Where one solve spent its time, for tuning weights and options on real
+/// frames. See `diff_with_stats` and `SolveScratch::stats`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SolveStats {
+    /// Positions compared: the target's area.
+    pub cells: usize,
+    /// Glyphs left to pair after moved rows, regions and words.
+    pub src_unmatched: usize,
+    pub dst_unmatched: usize,
+    /// What `Adaptive` resolved to, or the configured strategy.
+    pub strategy: MatchStrategy,
+    /// Cost matrix rows × columns; `(0, 0)` when none was built.
+    pub matrix: (usize, usize),
+    /// Refitting, the row, region, number and outline passes, and
+    /// classifying every cell.
+    pub classify_time: Duration,
+    /// Pairing unmatched glyphs, words and components included.
+    pub assign_time: Duration,
+}
 
-    for (i, row) in cost.iter().enumerate() {
-        for (j, &val) in row.iter().enumerate() {
-            c[i][j] = val;
-        }
+/// Reusable solver allocations: the cost matrix and the assignment
+/// working space. `MorphBackend` keeps one across transitions so rapid
+/// redraws don't reallocate them; see `diff_in`.
+#[derive(Debug, Clone, Default)]
+pub struct SolveScratch {
+    pub(crate) stats: SolveStats,
+    /// Row-major `n × m` costs.
+    costs: Vec<f32>,
+    u: Vec<f32>,
+    v: Vec<f32>,
+    min_v: Vec<f32>,
+    assignment: Vec<usize>,
+    way: Vec<usize>,
+    used: Vec<bool>,
+}
+
+impl SolveScratch {
+    /// The most recent solve through this scratch.
+    pub fn stats(&self) -> SolveStats {
+        self.stats
     }
+}
+
+/// `buf` cleared and refilled with `len` copies of `value`, keeping its
+/// allocation.
+fn reset<T: Clone>(buf: &mut Vec<T>, len: usize, value: T) {
+    buf.clear();
+    buf.resize(len, value);
+}
 
-    let mut u = vec![0.0f32; size + 1];
-    let mut v = vec![0.0f32; size + 1];
-    let mut assignment = vec![0usize; size + 1];
-    let mut way = vec![0usize; size + 1];
+/// Pads to square internally — the algorithm requires it; the padding
+/// costs nothing and is never stored.
+fn hungarian(cost: &[f32], n: usize, m: usize, scratch: &mut SolveScratch) -> Vec<Option<usize>> {
+    let size = n.max(m);
+    let c = |i: usize, j: usize| if i < n && j < m { cost[i * m + j] } else { 0.0 };
+
+    let SolveScratch {
+        u,
+        v,
+        min_v,
+        assignment,
+        way,
+        used,
+        ..
+    } = scratch;
+    reset(u, size + 1, 0.0);
+    reset(v, size + 1, 0.0);
+    reset(assignment, size + 1, 0);
+    reset(way, size + 1, 0);
 
     for i in 1..=size {
         assignment[0] = i;
         let mut j0 = 0usize;
-        let mut min_v = vec![f32::INFINITY; size + 1];
-        let mut used = vec![false; size + 1];
+        reset(min_v, size + 1, f32::INFINITY);
+        reset(used, size + 1, false);
 
         loop {
             used[j0] = true;
@@ -304,7 +1572,7 @@ fn hungarian(cost: &[Vec<f32>], n: usize, m: usize) -> Vec<Option<usize>> {
                     continue;
                 }
 
-                let cur = c[i0 - 1][j - 1] - u[i0] - v[j];
+                let cur = c(i0 - 1, j - 1) - u[i0] - v[j];
 
                 if cur < min_v[j] {
                     min_v[j] = cur;
@@ -346,10 +1614,8 @@ fn hungarian(cost: &[Vec<f32>], n: usize, m: usize) -> Vec<Option<usize>> {
 
     let mut result = vec![None; n];
 
-    for j in 1..=size {
-        let i = assignment[j];
-
-        if i >= 1 && i <= n && j >= 1 && j <= m {
+    for (j, &i) in assignment.iter().enumerate().skip(1) {
+        if i >= 1 && i <= n && j <= m {
             result[i - 1] = Some(j - 1);
         }
     }
@@ -357,11 +1623,75 @@ fn hungarian(cost: &[Vec<f32>], n: usize, m: usize) -> Vec<Option<usize>> {
     result
 }
 
+/// Unassigned sources bid for their best destination, raising its price by
+/// their margin over the runner-up; `eps` shrinks between rounds until the
+/// total cost is within a thousandth of the cost spread of optimal. Pads
+/// to square like `hungarian`.
+fn auction(cost: &[f32], n: usize, m: usize) -> Vec<Option<usize>> {
+    let size = n.max(m);
+    let benefit = |i: usize, j: usize| {
+        if i < n && j < m {
+            -f64::from(cost[i * m + j])
+        } else {
+            0.0
+        }
+    };
+
+    let spread = cost
+        .iter()
+        .fold(1.0f64, |acc, &c| acc.max(f64::from(c).abs()));
+    let final_eps = spread / (1000.0 * size as f64);
+    let mut eps = spread / 4.0;
+    let mut prices = vec![0.0f64; size];
+    let mut assigned: Vec<Option<usize>>;
+
+    loop {
+        let mut owner: Vec<Option<usize>> = vec![None; size];
+        assigned = vec![None; size];
+        let mut bidders: Vec<usize> = (0..size).rev().collect();
+
+        while let Some(i) = bidders.pop() {
+            let (mut best, mut best_value, mut second_value) = (0, f64::MIN, f64::MIN);
+
+            for (j, price) in prices.iter().enumerate() {
+                let value = benefit(i, j) - price;
+
+                if value > best_value {
+                    (best, second_value, best_value) = (j, best_value, value);
+                } else if value > second_value {
+                    second_value = value;
+                }
+            }
+
+            let margin = if size > 1 {
+                best_value - second_value
+            } else {
+                0.0
+            };
+            prices[best] += margin + eps;
+
+            if let Some(outbid) = owner[best].replace(i) {
+                assigned[outbid] = None;
+                bidders.push(outbid);
+            }
+            assigned[i] = Some(best);
+        }
+
+        if eps <= final_eps {
+            break;
+        }
+        eps = (eps / 5.0).max(final_eps);
+    }
+
+    assigned.truncate(n);
+    assigned.into_iter().map(|j| j.filter(|&j| j < m)).collect()
+}
+
 #[cfg(test)]
 mod tests {
-    use ratatui::buffer::Buffer;
+    use ratatui::buffer::{Buffer, Cell};
     use ratatui::layout::Rect;
-    use ratatui::style::{Color, Style};
+    use ratatui::style::{Color, Modifier, Style};
 
     use super::*;
 
@@ -378,6 +1708,44 @@ mod tests {
         buf
     }
 
+    #[test]
+    fn excluded_regions_snap() {
+        let a = make_buffer(
+            4,
+            1,
+            &[((0, 0), "A", Color::Red), ((3, 0), "1", Color::Red)],
+        );
+        let b = make_buffer(
+            4,
+            1,
+            &[((1, 0), "A", Color::Red), ((3, 0), "2", Color::Red)],
+        );
+        let options = SolveOptions {
+            exclude: vec![Rect::new(3, 0, 1, 1)],
+            ..SolveOptions::new()
+        };
+
+        let plan = diff_with(&a, &b, &MorphWeights::LIQUID, &options);
+        assert_eq!(plan.displaced.len(), 1);
+        assert!(plan.mutating.iter().all(|c| c.x != 3));
+        assert!(plan.stable.iter().any(|c| c.x == 3 && c.symbol == "2"));
+    }
+
+    #[test]
+    fn skipped_cells_sit_out() {
+        let a = make_buffer(3, 1, &[((0, 0), "A", Color::Red)]);
+        let mut b = make_buffer(3, 1, &[((2, 0), "A", Color::Red)]);
+        b[(1, 0)].set_symbol("#").set_skip(true);
+
+        let plan = diff(&a, &b, &MorphWeights::LIQUID);
+        assert_eq!(plan.skipped, vec![(1, 0)]);
+        assert!(plan.mutating.iter().all(|c| c.x != 1));
+
+        let frame = crate::interpolate::render(&plan, 0.5);
+        assert!(frame[(1, 0)].skip);
+        assert!(!frame[(0, 0)].skip);
+    }
+
     #[test]
     fn identical_buffers_all_stable() {
         let a = make_buffer(
@@ -441,26 +1809,743 @@ mod tests {
         assert_eq!(plan.displaced[0].dst_x, 2);
     }
 
+    #[test]
+    fn crossfade_never_displaces() {
+        let a = make_buffer(3, 1, &[((0, 0), "M", Color::Red)]);
+        let b = make_buffer(3, 1, &[((2, 0), "M", Color::Red)]);
+        let options = SolveOptions {
+            strategy: MatchStrategy::Crossfade,
+            ..SolveOptions::default()
+        };
+        let plan = diff_with(&a, &b, &MorphWeights::LIQUID, &options);
+
+        assert!(plan.displaced.is_empty());
+        assert_eq!(plan.disappearing.len(), 1);
+        assert_eq!(plan.appearing.len(), 1);
+    }
+
+    #[test]
+    fn displacement_modes_override_cost() {
+        let a = make_buffer(80, 1, &[((0, 0), "A", Color::Red)]);
+        let b = make_buffer(80, 1, &[((79, 0), "~", Color::Blue)]);
+        let solve = |displacement| {
+            let options = SolveOptions {
+                displacement,
+                ..SolveOptions::default()
+            };
+            diff_with(&a, &b, &MorphWeights::CRISP, &options)
+        };
+
+        assert!(solve(Displacement::Costed).displaced.is_empty());
+        assert_eq!(solve(Displacement::Always).displaced.len(), 1);
+
+        let near = make_buffer(80, 1, &[((1, 0), "A", Color::Red)]);
+        let options = SolveOptions {
+            displacement: Displacement::Never,
+            strategy: MatchStrategy::Hungarian,
+            components: true,
+            ..SolveOptions::default()
+        };
+        let plan = diff_with(&a, &near, &MorphWeights::CRISP, &options);
+        assert!(plan.displaced.is_empty());
+        assert_eq!(plan.appearing.len(), 1);
+    }
+
+    #[test]
+    fn update_reuses_stable_cells() {
+        let frame = |text: &str| {
+            let mut buf = Buffer::empty(Rect::new(0, 0, 12, 2));
+            buf.set_string(0, 0, "header", Style::default());
+            buf.set_string(0, 1, text, Style::default());
+            buf
+        };
+        let options = SolveOptions::default();
+
+        let first = diff_with(
+            &frame("loading"),
+            &frame("ready"),
+            &MorphWeights::LIQUID,
+            &options,
+        );
+        let plan = update(
+            &first,
+            &frame("rea"),
+            &frame("done"),
+            &MorphWeights::LIQUID,
+            &options,
+        );
+
+        let fresh = diff_with(
+            &frame("rea"),
+            &frame("done"),
+            &MorphWeights::LIQUID,
+            &options,
+        );
+        assert!(plan.diff(&fresh).is_empty());
+        assert_eq!(plan.stable.len(), fresh.stable.len());
+    }
+
+    #[test]
+    fn update_defers_to_classifier() {
+        let src = make_buffer(3, 1, &[((1, 0), "x", Color::Red)]);
+        let options = SolveOptions {
+            // Flash every unchanged glyph.
+            classifier: Some(Arc::new(|s: &Cell, d: &Cell, class| {
+                if s == d && s.symbol() != " " {
+                    CellClass::Mutating
+                } else {
+                    class
+                }
+            })),
+            ..SolveOptions::default()
+        };
+        let prior = diff_with(&src, &src, &MorphWeights::LIQUID, &SolveOptions::default());
+        assert_eq!(prior.stable.len(), 3);
+
+        let plan = update(&prior, &src, &src, &MorphWeights::LIQUID, &options);
+        let fresh = diff_with(&src, &src, &MorphWeights::LIQUID, &options);
+        assert_eq!(fresh.mutating.len(), 1);
+        assert!(plan.diff(&fresh).is_empty());
+        assert_eq!(plan.mutating.len(), 1);
+    }
+
+    #[test]
+    fn stats_count_the_solve() {
+        let src = make_buffer(
+            6,
+            2,
+            &[((0, 0), "A", Color::Red), ((1, 0), "B", Color::Red)],
+        );
+        let dst = make_buffer(
+            6,
+            2,
+            &[
+                ((4, 1), "B", Color::Red),
+                ((5, 1), "A", Color::Red),
+                ((0, 1), "C", Color::Red),
+            ],
+        );
+        let opts = SolveOptions {
+            strategy: MatchStrategy::Hungarian,
+            ..Default::default()
+        };
+
+        let (_, stats) = diff_with_stats(&src, &dst, &MorphWeights::LIQUID, &opts);
+        assert_eq!(stats.cells, 12);
+        assert_eq!((stats.src_unmatched, stats.dst_unmatched), (2, 3));
+        assert_eq!(stats.strategy, MatchStrategy::Hungarian);
+        assert_eq!(stats.matrix, (2, 3));
+
+        let opts = SolveOptions::default();
+        let (_, stats) = diff_with_stats(&src, &dst, &MorphWeights::LIQUID, &opts);
+        assert_eq!(stats.strategy, MatchStrategy::Hungarian);
+        let (_, stats) = diff_with_stats(&src, &src, &MorphWeights::LIQUID, &opts);
+        assert_eq!(stats.matrix, (0, 0));
+    }
+
+    #[test]
+    fn pairs_past_the_cap_fade() {
+        let row = |x: u16| {
+            [
+                ((x, 0), "A", Color::Red),
+                ((x + 1, 0), "B", Color::Red),
+                ((x + 2, 0), "C", Color::Red),
+            ]
+        };
+        let src = make_buffer(8, 1, &row(0));
+        let dst = make_buffer(8, 1, &row(5));
+        let opts = SolveOptions {
+            strategy: MatchStrategy::Hungarian,
+            max_pairs: 4,
+            ..Default::default()
+        };
+
+        let (plan, stats) = diff_with_stats(&src, &dst, &MorphWeights::LIQUID, &opts);
+        assert_eq!(stats.matrix, (2, 2));
+        assert_eq!(plan.displaced.len(), 2);
+        assert_eq!((plan.appearing.len(), plan.disappearing.len()), (1, 1));
+
+        assert_eq!(capped(2, 1000, 100), (2, 50));
+        assert_eq!(capped(1000, 1000, 100), (10, 10));
+        assert_eq!(capped(5, 5, 0), (0, 0));
+    }
+
+    #[test]
+    fn greedy_matches_same_symbol() {
+        let src = make_buffer(
+            6,
+            1,
+            &[((0, 0), "A", Color::Red), ((1, 0), "B", Color::Red)],
+        );
+        let dst = make_buffer(
+            6,
+            1,
+            &[((4, 0), "B", Color::Red), ((5, 0), "A", Color::Red)],
+        );
+        let opts = SolveOptions {
+            strategy: MatchStrategy::Greedy,
+            ..Default::default()
+        };
+
+        let plan = diff_with(&src, &dst, &MorphWeights::LIQUID, &opts);
+        let mut moves: Vec<_> = plan.displaced.iter().map(|d| (d.src_x, d.dst_x)).collect();
+        moves.sort();
+        assert_eq!(moves, vec![(0, 5), (1, 4)]);
+    }
+
+    #[test]
+    fn components_move_words_together() {
+        let src = make_buffer(
+            8,
+            1,
+            &[((0, 0), "a", Color::Red), ((1, 0), "b", Color::Red)],
+        );
+        let dst = make_buffer(
+            8,
+            1,
+            &[
+                ((2, 0), "a", Color::Red),
+                ((5, 0), "a", Color::Red),
+                ((6, 0), "b", Color::Red),
+            ],
+        );
+        let moves = |components| {
+            let opts = SolveOptions {
+                components,
+                ..Default::default()
+            };
+            let plan = diff_with(&src, &dst, &MorphWeights::LIQUID, &opts);
+            let mut moves: Vec<_> = plan.displaced.iter().map(|d| (d.src_x, d.dst_x)).collect();
+            moves.sort();
+            moves
+        };
+
+        // Cell by cell, "a" splits off to the nearer lone "a".
+        assert_eq!(moves(false), vec![(0, 2), (1, 5)]);
+        assert_eq!(moves(true), vec![(0, 5), (1, 6)]);
+    }
+
+    #[test]
+    fn words_move_as_units() {
+        let src = Buffer::with_lines(["hello   ", "        "]);
+        let dst = Buffer::with_lines(["        ", "   hallo"]);
+        let opts = SolveOptions {
+            words: true,
+            strategy: MatchStrategy::Crossfade,
+            ..Default::default()
+        };
+
+        let plan = diff_with(&src, &dst, &MorphWeights::CRISP, &opts);
+        let moves: Vec<_> = plan
+            .displaced
+            .iter()
+            .map(|d| (d.src_x, d.dst_x, d.dst_symbol.as_str()))
+            .collect();
+
+        assert_eq!(
+            moves,
+            vec![
+                (0, 3, "h"),
+                (1, 4, "a"),
+                (2, 5, "l"),
+                (3, 6, "l"),
+                (4, 7, "o")
+            ]
+        );
+        assert!(plan.appearing.is_empty() && plan.disappearing.is_empty());
+    }
+
+    #[test]
+    fn scrolled_rows_move_whole() {
+        let src = Buffer::with_lines(["aa", "bb", "cc"]);
+        let dst = Buffer::with_lines(["bb", "cc", "dd"]);
+        let opts = SolveOptions {
+            scroll: true,
+            strategy: MatchStrategy::Crossfade,
+            ..Default::default()
+        };
+
+        let plan = diff_with(&src, &dst, &MorphWeights::CRISP, &opts);
+        let mut moves: Vec<_> = plan
+            .displaced
+            .iter()
+            .map(|d| (d.src_x, d.src_y, d.dst_y))
+            .collect();
+        moves.sort();
+
+        assert_eq!(moves, vec![(0, 1, 0), (0, 2, 1), (1, 1, 0), (1, 2, 1)]);
+        assert_eq!(plan.disappearing.len(), 2);
+        assert_eq!(plan.appearing.len(), 2);
+        assert_eq!(crate::interpolate::render(&plan, 0.0), src);
+        assert_eq!(crate::interpolate::render(&plan, 1.0), dst);
+    }
+
+    #[test]
+    fn custom_cost_can_forbid_a_move() {
+        let src = make_buffer(6, 1, &[((0, 0), "7", Color::Red)]);
+        let dst = make_buffer(6, 1, &[((4, 0), "7", Color::Red)]);
+        let digits_stay: Arc<dyn CostFunction> =
+            Arc::new(|from: &MatchCell, to: &MatchCell, base| {
+                if from.symbol.chars().all(|c| c.is_ascii_digit()) && from.x != to.x {
+                    f32::MAX
+                } else {
+                    base
+                }
+            });
+
+        let plain = diff_with(&src, &dst, &MorphWeights::LIQUID, &SolveOptions::default());
+        let opts = SolveOptions {
+            cost: Some(digits_stay),
+            ..Default::default()
+        };
+        let custom = diff_with(&src, &dst, &MorphWeights::LIQUID, &opts);
+
+        assert_eq!(plain.displaced.len(), 1);
+        assert!(custom.displaced.is_empty());
+        assert_eq!(custom.appearing.len(), 1);
+    }
+
+    #[test]
+    fn classifier_can_snap_modifier_changes() {
+        let src = make_buffer(3, 1, &[((1, 0), "x", Color::Red)]);
+        let mut dst = src.clone();
+        dst[(1, 0)].set_style(Style::new().add_modifier(Modifier::BOLD));
+        let snap_modifiers: Arc<dyn CellClassifier> = Arc::new(|s: &Cell, d: &Cell, default| {
+            if s.symbol() == d.symbol() && s.fg == d.fg && s.bg == d.bg {
+                CellClass::Stable
+            } else {
+                default
+            }
+        });
+
+        let plain = diff_with(&src, &dst, &MorphWeights::LIQUID, &SolveOptions::default());
+        let opts = SolveOptions {
+            classifier: Some(snap_modifiers),
+            ..Default::default()
+        };
+        let custom = diff_with(&src, &dst, &MorphWeights::LIQUID, &opts);
+
+        assert_eq!(plain.mutating.len(), 1);
+        assert!(custom.mutating.is_empty());
+        assert_eq!(custom.stable.len(), 3);
+        assert!(
+            custom
+                .stable
+                .iter()
+                .any(|c| c.x == 1 && c.modifier == Modifier::BOLD)
+        );
+    }
+
+    #[test]
+    fn colored_blanks_can_mutate() {
+        let mut src = make_buffer(3, 1, &[]);
+        src[(1, 0)].set_bg(Color::Blue);
+        let dst = make_buffer(3, 1, &[((1, 0), "x", Color::White)]);
+
+        let plain = diff_with(&src, &dst, &MorphWeights::LIQUID, &SolveOptions::default());
+        let opts = SolveOptions {
+            blank: BlankCells {
+                backgrounds: true,
+                ..BlankCells::new()
+            },
+            ..Default::default()
+        };
+        let panel = diff_with(&src, &dst, &MorphWeights::LIQUID, &opts);
+
+        assert_eq!(plain.appearing.len(), 1);
+        assert!(panel.appearing.is_empty());
+        assert_eq!(panel.mutating.len(), 1);
+        assert_eq!(panel.mutating[0].dst_symbol, "x");
+    }
+
+    #[test]
+    fn adaptive_picks_by_size() {
+        let opts = SolveOptions {
+            adaptive: AdaptiveThresholds {
+                exact: 2,
+                greedy: 5,
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(opts.resolve(2), MatchStrategy::Hungarian);
+        assert_eq!(opts.resolve(5), MatchStrategy::Greedy);
+        assert_eq!(opts.resolve(6), MatchStrategy::Crossfade);
+    }
+
+    #[test]
+    fn different_sizes_refit_at_anchor() {
+        let src = make_buffer(2, 1, &[((0, 0), "A", Color::Red)]);
+        let dst = make_buffer(4, 2, &[((0, 1), "A", Color::Red)]);
+
+        let top = diff(&src, &dst, &MorphWeights::LIQUID);
+        assert_eq!((top.width, top.height), (4, 2));
+        assert_eq!(top.displaced.len(), 1);
+
+        let opts = SolveOptions {
+            anchor: ResizeAnchor::BottomLeft,
+            ..Default::default()
+        };
+        let bottom = diff_with(&src, &dst, &MorphWeights::LIQUID, &opts);
+        assert!(bottom.displaced.is_empty());
+        assert!(bottom.stable.iter().any(|c| (c.x, c.y) == (0, 1)));
+    }
+
+    #[test]
+    fn glyph_table_lowers_cost() {
+        let bullet = CellSnapshot::from_cell(&ratatui::buffer::Cell::new("•"));
+        let circle = CellSnapshot::from_cell(&ratatui::buffer::Cell::new("◦"));
+        let w = MorphWeights::CRISP;
+
+        let plain = cell_cost((0, 0), &bullet, (3, 0), &circle, &w, &GlyphTable::binary());
+        let table = GlyphTable::binary().equivalent(["•", "◦"], 0.0);
+        let similar = cell_cost((0, 0), &bullet, (3, 0), &circle, &w, &table);
+
+        assert!((plain - similar - w.glyph * w.glyph_mismatch).abs() < 1e-4);
+    }
+
+    #[test]
+    fn background_breaks_ties() {
+        let mut src = make_buffer(
+            6,
+            1,
+            &[((2, 0), "x", Color::White), ((4, 0), "x", Color::White)],
+        );
+        src[(2, 0)].set_bg(Color::Rgb(0, 0, 200));
+        let dst = make_buffer(6, 1, &[((3, 0), "x", Color::White)]);
+
+        let result = diff_with(&src, &dst, &MorphWeights::LIQUID, &SolveOptions::default());
+
+        assert_eq!(result.displaced.len(), 1);
+        assert_eq!(
+            (result.displaced[0].src_x, result.displaced[0].dst_x),
+            (4, 3)
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_cost_matrix_matches_serial() {
+        let src = make_buffer(
+            8,
+            3,
+            &[
+                ((0, 0), "A", Color::Red),
+                ((1, 0), "B", Color::Green),
+                ((0, 1), "C", Color::Blue),
+                ((1, 1), "D", Color::Red),
+            ],
+        );
+        let dst = make_buffer(
+            8,
+            3,
+            &[
+                ((5, 1), "A", Color::Red),
+                ((6, 1), "B", Color::Green),
+                ((5, 2), "C", Color::Blue),
+                ((6, 2), "D", Color::Red),
+            ],
+        );
+
+        let (n, m) = (7, 5);
+        let cost = |i: usize, j: usize| (i * 31 + j * 7) as f32 / 3.0;
+        let mut parallel = Vec::new();
+        cost_matrix(&mut parallel, n, m, &cost);
+        let serial: Vec<f32> = (0..n)
+            .flat_map(|i| (0..m).map(move |j| cost(i, j)))
+            .collect();
+        assert_eq!(parallel, serial);
+
+        let opts = SolveOptions {
+            strategy: MatchStrategy::Hungarian,
+            words: false,
+            components: false,
+            ..Default::default()
+        };
+        let plan = diff_with(&src, &dst, &MorphWeights::LIQUID, &opts);
+        let mut moves: Vec<_> = plan
+            .displaced
+            .iter()
+            .map(|c| ((c.src_x, c.src_y), (c.dst_x, c.dst_y)))
+            .collect();
+        moves.sort();
+        assert_eq!(
+            moves,
+            [
+                ((0, 0), (5, 1)),
+                ((0, 1), (5, 2)),
+                ((1, 0), (6, 1)),
+                ((1, 1), (6, 2)),
+            ]
+        );
+    }
+
+    #[test]
+    fn large_tie_buckets_settle_reproducibly() {
+        let cells = |offset: u16| {
+            (0..4u16)
+                .flat_map(|y| (0..40u16).map(move |x| ((x * 2 + offset, y), "a", Color::Red)))
+                .collect::<Vec<_>>()
+        };
+        let src = make_buffer(82, 4, &cells(0));
+        let dst = make_buffer(82, 4, &cells(1));
+        let flat: Arc<dyn CostFunction> = Arc::new(|_: &MatchCell, _: &MatchCell, _| 1.0);
+        let opts = SolveOptions {
+            strategy: MatchStrategy::Hungarian,
+            cost: Some(flat),
+            ..Default::default()
+        };
+
+        let first = diff_with(&src, &dst, &MorphWeights::LIQUID, &opts);
+        let again = diff_with(&src, &dst, &MorphWeights::LIQUID, &opts);
+        assert_eq!(first.displaced.len(), 160);
+        assert!(first.diff(&again).is_empty());
+    }
+
+    #[test]
+    fn ties_break_by_distance_then_order() {
+        let src = make_buffer(
+            3,
+            3,
+            &[((0, 0), "a", Color::Red), ((2, 2), "a", Color::Red)],
+        );
+        let dst = make_buffer(
+            3,
+            3,
+            &[((2, 0), "a", Color::Red), ((0, 2), "a", Color::Red)],
+        );
+        let row = |xs: [u16; 3]| xs.map(|x| ((x, 0), "a", Color::Red));
+        let far = make_buffer(8, 1, &row([0, 3, 6]));
+        let near = make_buffer(8, 1, &row([1, 4, 7]));
+        let flat: Arc<dyn CostFunction> = Arc::new(|_: &MatchCell, _: &MatchCell, _| 1.0);
+
+        for strategy in [
+            MatchStrategy::Hungarian,
+            MatchStrategy::Auction,
+            MatchStrategy::Greedy,
+        ] {
+            let opts = SolveOptions {
+                strategy,
+                ..Default::default()
+            };
+            let moves = |plan: InterpolationPlan| {
+                let mut moves: Vec<_> = plan
+                    .displaced
+                    .iter()
+                    .map(|c| ((c.src_x, c.src_y), (c.dst_x, c.dst_y)))
+                    .collect();
+                moves.sort();
+                moves
+            };
+
+            let crossed = diff_with(&src, &dst, &MorphWeights::LIQUID, &opts);
+            assert_eq!(
+                moves(crossed),
+                [((0, 0), (2, 0)), ((2, 2), (0, 2))],
+                "{strategy:?}"
+            );
+
+            let opts = SolveOptions {
+                cost: Some(Arc::clone(&flat)),
+                ..opts
+            };
+            let shifted = diff_with(&far, &near, &MorphWeights::LIQUID, &opts);
+            assert_eq!(
+                moves(shifted),
+                [((0, 0), (1, 0)), ((3, 0), (4, 0)), ((6, 0), (7, 0))],
+                "{strategy:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn keyed_regions_move_whole() {
+        let paint = |rows: [&str; 2]| {
+            let mut buf = Buffer::empty(Rect::new(0, 0, 4, 2));
+            for (y, text) in (0..).zip(rows) {
+                buf.set_string(0, y, text, Style::default());
+            }
+            buf
+        };
+        let src = paint(["abc", "xyz"]);
+        let dst = paint(["xyz", "abc"]);
+
+        let plain = diff_with(&src, &dst, &MorphWeights::CRISP, &SolveOptions::default());
+        assert!(plain.displaced.is_empty());
+
+        let opts = SolveOptions {
+            keyed: vec![
+                (Rect::new(0, 0, 4, 1), Rect::new(0, 1, 4, 1)),
+                (Rect::new(0, 1, 4, 1), Rect::new(0, 0, 4, 1)),
+            ],
+            ..Default::default()
+        };
+        let keyed = diff_with(&src, &dst, &MorphWeights::CRISP, &opts);
+
+        assert_eq!(keyed.displaced.len(), 6);
+        for cell in &keyed.displaced {
+            assert_eq!(cell.src_x, cell.dst_x);
+            assert_eq!(cell.src_symbol, cell.dst_symbol);
+        }
+    }
+
+    #[test]
+    fn numbers_count_between_values() {
+        let row = |text: &str| {
+            let mut buf = Buffer::empty(Rect::new(0, 0, 8, 1));
+            buf.set_string(0, 0, text, Style::default());
+            buf
+        };
+        let opts = SolveOptions {
+            numbers: true,
+            ..Default::default()
+        };
+        let text = |plan: &InterpolationPlan, t: f32| {
+            let frame = crate::interpolate::render(plan, t);
+            let text: String = frame.content.iter().map(|c| c.symbol()).collect();
+            text.trim_end().to_string()
+        };
+
+        let gauge = diff_with(
+            &row("CPU 87%"),
+            &row("CPU 43%"),
+            &MorphWeights::CRISP,
+            &opts,
+        );
+        assert_eq!(gauge.numbers.len(), 1);
+        assert_eq!(text(&gauge, 0.0), "CPU 87%");
+        assert_eq!(text(&gauge, 0.5), "CPU 65%");
+        assert_eq!(text(&gauge, 1.0), "CPU 43%");
+
+        let price = diff_with(&row("$-1.50"), &row("$12.25"), &MorphWeights::CRISP, &opts);
+        assert_eq!(price.numbers[0].from, -1.5);
+        assert_eq!(text(&price, 0.5), "$ 5.38");
+
+        let grown = diff_with(
+            &row("9% done"),
+            &row("10% done"),
+            &MorphWeights::CRISP,
+            &opts,
+        );
+        assert_eq!(text(&grown, 0.0), "9% done");
+        assert_eq!(text(&grown, 1.0), "10% done");
+
+        let plain = diff_with(
+            &row("v2 x007"),
+            &row("v3 x008"),
+            &MorphWeights::CRISP,
+            &opts,
+        );
+        assert!(plain.numbers.is_empty());
+    }
+
+    #[test]
+    fn borders_stretch() {
+        use ratatui::widgets::{Block, Widget};
+
+        let area = Rect::new(0, 0, 12, 5);
+        let frame = |block: Rect| {
+            let mut buf = Buffer::empty(area);
+            Block::bordered().title("Log").render(block, &mut buf);
+            buf
+        };
+        let src = frame(Rect::new(0, 0, 6, 3));
+        let dst = frame(Rect::new(0, 0, 10, 4));
+        let symbols = |buf: &Buffer| -> Vec<String> {
+            buf.content.iter().map(|c| c.symbol().to_string()).collect()
+        };
+
+        let plain = diff_with(&src, &dst, &MorphWeights::CRISP, &SolveOptions::default());
+        assert!(plain.borders.is_empty());
+
+        let opts = SolveOptions {
+            borders: true,
+            ..Default::default()
+        };
+        let plan = diff_with(&src, &dst, &MorphWeights::CRISP, &opts);
+        assert_eq!(plan.borders.len(), 1);
+        assert_eq!(
+            symbols(&crate::interpolate::render(&plan, 0.0)),
+            symbols(&src)
+        );
+        assert_eq!(
+            symbols(&crate::interpolate::render(&plan, 1.0)),
+            symbols(&dst)
+        );
+
+        let mid = crate::interpolate::render(&plan, 0.5);
+        assert_eq!(mid[(7, 0)].symbol(), "┐");
+        assert_eq!(mid[(1, 0)].symbol(), "L");
+        assert_eq!(mid[(9, 0)].symbol(), " ");
+    }
+
+    #[test]
+    fn appended_log_lines_skip_matching() {
+        let frame = |lines: [&str; 4]| {
+            let mut buf = Buffer::empty(Rect::new(0, 0, 8, 5));
+            for (y, line) in (0..).zip(lines) {
+                buf.set_string(0, y, line, Style::default());
+            }
+            buf.set_string(0, 4, "status", Style::default());
+            buf
+        };
+        let src = frame(["one", "two", "three", "four"]);
+        let dst = frame(["two", "three", "four", "five"]);
+        let opts = SolveOptions {
+            scroll: true,
+            ..Default::default()
+        };
+
+        let plan = diff_with(&src, &dst, &MorphWeights::LIQUID, &opts);
+
+        assert_eq!(plan.displaced.len(), 3 + 5 + 4);
+        assert!(plan.displaced.iter().all(|c| c.src_y == c.dst_y + 1));
+        assert_eq!(plan.appearing.len(), 4);
+        assert_eq!(plan.disappearing.len(), 3);
+        assert!(plan.stable.iter().filter(|c| c.y == 4).count() == 8);
+    }
+
     #[test]
     fn hungarian_identity() {
-        let cost = vec![vec![0.0, 1.0], vec![1.0, 0.0]];
-        let result = hungarian(&cost, 2, 2);
+        let cost = [0.0, 1.0, 1.0, 0.0];
+        let result = hungarian(&cost, 2, 2, &mut SolveScratch::default());
 
         assert_eq!(result, vec![Some(0), Some(1)]);
     }
 
     #[test]
     fn hungarian_swap() {
-        let cost = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
-        let result = hungarian(&cost, 2, 2);
+        let cost = [1.0, 0.0, 0.0, 1.0];
+        let result = hungarian(&cost, 2, 2, &mut SolveScratch::default());
 
         assert_eq!(result, vec![Some(1), Some(0)]);
     }
 
+    #[test]
+    fn auction_matches_hungarian() {
+        let cost: Vec<f32> = (0..6)
+            .flat_map(|i| {
+                (0..4).map(move |j| ((i * 7 + j * 3) % 5) as f32 + (i as f32 - j as f32).abs())
+            })
+            .collect();
+        let total = |assignment: &[Option<usize>]| -> f32 {
+            assignment
+                .iter()
+                .enumerate()
+                .filter_map(|(i, j)| j.map(|j| cost[i * 4 + j]))
+                .sum()
+        };
+
+        let auctioned = auction(&cost, 6, 4);
+        assert_eq!(auctioned.iter().flatten().count(), 4);
+        let exact = hungarian(&cost, 6, 4, &mut SolveScratch::default());
+        assert!((total(&auctioned) - total(&exact)).abs() < 0.1);
+    }
+
     #[test]
     fn hungarian_rectangular() {
-        let cost = vec![vec![10.0, 1.0], vec![1.0, 10.0], vec![5.0, 5.0]];
-        let result = hungarian(&cost, 3, 2);
+        let cost = [10.0, 1.0, 1.0, 10.0, 5.0, 5.0];
+        let result = hungarian(&cost, 3, 2, &mut SolveScratch::default());
 
         assert_eq!(result[0], Some(1));
         assert_eq!(result[1], Some(0));
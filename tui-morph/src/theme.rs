@@ -0,0 +1,160 @@
+use ratatui::buffer::Buffer;
+use ratatui::style::Color;
+
+use crate::config::MorphConfig;
+use crate::plan::{ColorPair, InterpolationPlan, MutatingCell, StableCell};
+use crate::transition::Transition;
+
+/// Old-theme → new-theme color mapping for whole-palette switches.
+///
+/// Layout is unchanged by a theme switch, so the plan is built directly:
+/// every cell stays in place and its colors travel along the mapping in
+/// Oklch. No solver pass.
+#[derive(Debug, Clone, Default)]
+pub struct ThemeMap {
+    pairs: Vec<(Color, Color)>,
+}
+
+impl ThemeMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Later mappings for the same `from` color win.
+    pub fn map(mut self, from: Color, to: Color) -> Self {
+        self.pairs.retain(|(f, _)| *f != from);
+        self.pairs.push((from, to));
+        self
+    }
+
+    /// Unmapped colors pass through.
+    pub fn apply(&self, color: Color) -> Color {
+        self.pairs
+            .iter()
+            .find(|(from, _)| *from == color)
+            .map_or(color, |(_, to)| *to)
+    }
+
+    /// `buf` with every color mapped.
+    pub fn recolor(&self, buf: &Buffer) -> Buffer {
+        let mut out = buf.clone();
+
+        for cell in out.content.iter_mut() {
+            cell.fg = self.apply(cell.fg);
+            cell.bg = self.apply(cell.bg);
+        }
+
+        out
+    }
+
+    /// Plan from `buf` to `recolor(buf)`: mutating where a color maps,
+    /// stable elsewhere.
+    pub fn plan(&self, buf: &Buffer) -> InterpolationPlan {
+        let area = buf.area();
+        let mut stable = Vec::new();
+        let mut mutating = Vec::new();
+        let mut skipped = Vec::new();
+
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                let cell = &buf[(x, y)];
+
+                if cell.skip {
+                    skipped.push((x, y));
+                    continue;
+                }
+
+                let fg = self.apply(cell.fg);
+                let bg = self.apply(cell.bg);
+
+                if fg == cell.fg && bg == cell.bg {
+                    stable.push(StableCell {
+                        x,
+                        y,
+                        symbol: cell.symbol().to_string(),
+                        fg,
+                        bg,
+                        modifier: cell.modifier,
+                    });
+                } else {
+                    mutating.push(MutatingCell {
+                        x,
+                        y,
+                        src_symbol: cell.symbol().to_string(),
+                        dst_symbol: cell.symbol().to_string(),
+                        src_fg: ColorPair::from_color(cell.fg),
+                        dst_fg: ColorPair::from_color(fg),
+                        src_bg: ColorPair::from_color(cell.bg),
+                        dst_bg: ColorPair::from_color(bg),
+                        src_modifier: cell.modifier,
+                        dst_modifier: cell.modifier,
+                    });
+                }
+            }
+        }
+
+        InterpolationPlan {
+            origin: area.as_position(),
+            width: area.width,
+            height: area.height,
+            stable,
+            mutating,
+            displaced: Vec::new(),
+            appearing: Vec::new(),
+            disappearing: Vec::new(),
+            skipped,
+            numbers: Vec::new(),
+            borders: Vec::new(),
+        }
+    }
+
+    pub fn transition(&self, buf: &Buffer, config: &MorphConfig) -> Transition {
+        Transition::from_plan(buf, &self.recolor(buf), self.plan(buf), config)
+    }
+}
+
+impl FromIterator<(Color, Color)> for ThemeMap {
+    fn from_iter<I: IntoIterator<Item = (Color, Color)>>(iter: I) -> Self {
+        iter.into_iter()
+            .fold(Self::new(), |map, (from, to)| map.map(from, to))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::layout::Rect;
+    use ratatui::style::Style;
+
+    use crate::interpolate;
+
+    use super::*;
+
+    const DARK_BG: Color = Color::Rgb(20, 20, 30);
+    const LIGHT_BG: Color = Color::Rgb(240, 240, 235);
+
+    fn themed(bg: Color) -> Buffer {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 4, 1));
+        buf.set_string(0, 0, "ab", Style::new().fg(Color::Red).bg(bg));
+        buf
+    }
+
+    #[test]
+    fn unmapped_cells_are_stable() {
+        let map = ThemeMap::new().map(DARK_BG, LIGHT_BG);
+        let plan = map.plan(&themed(DARK_BG));
+
+        assert_eq!(plan.mutating.len(), 2);
+        assert_eq!(plan.stable.len(), 2);
+    }
+
+    #[test]
+    fn endpoints_match_recolor() {
+        let map: ThemeMap = [(DARK_BG, LIGHT_BG)].into_iter().collect();
+        let src = themed(DARK_BG);
+        let plan = map.plan(&src);
+
+        assert_eq!(interpolate::render(&plan, 1.0)[(0, 0)].bg, LIGHT_BG);
+        assert_eq!(interpolate::render(&plan, 0.0)[(0, 0)].bg, DARK_BG);
+        assert_eq!(map.recolor(&src), themed(LIGHT_BG));
+    }
+}
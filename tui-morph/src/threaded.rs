@@ -0,0 +1,312 @@
+use std::io;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+
+use ratatui::Terminal;
+use ratatui::backend::{Backend, ClearType, WindowSize};
+use ratatui::buffer::Cell;
+use ratatui::layout::{Position, Size};
+
+use crate::backend::MorphBackend;
+use crate::config::TickMode;
+use crate::keys::{self, FrameKeys};
+
+/// Backend calls replayed on the render thread, in order.
+enum Command {
+    Draw(Vec<(u16, u16, Cell)>),
+    /// With the keys of `Morphed` widgets rendered on the app's thread.
+    Flush(FrameKeys),
+    HideCursor,
+    ShowCursor,
+    SetCursor(Position),
+    GetCursor(Sender<io::Result<Position>>),
+    Clear,
+    ClearRegion(ClearType),
+    AppendLines(u16),
+    Size(Sender<io::Result<Size>>),
+    WindowSize(Sender<io::Result<WindowSize>>),
+    Settle(Sender<io::Result<()>>),
+}
+
+/// A `MorphBackend` running on its own thread.
+///
+/// Every backend call is forwarded over a channel; `flush` returns as soon
+/// as the frame is queued, and the render thread plays transitions in the
+/// background (as with `TickMode::Manual`, a draw mid-transition
+/// retargets it). Calls that need an answer, like `size`, wait for the
+/// render thread to reach them.
+///
+/// An I/O error on the render thread stops it; the next call returns that
+/// error.
+pub struct ThreadedMorphBackend {
+    commands: Option<Sender<Command>>,
+    worker: Option<JoinHandle<io::Result<()>>>,
+}
+
+impl ThreadedMorphBackend {
+    /// Start the render thread. `setup` runs on it and builds the
+    /// `MorphBackend`, so effects, sinks and hooks that are not `Send` can
+    /// be attached there. Its tick mode is forced to `Manual`.
+    pub fn spawn<B, F>(inner: B, setup: F) -> io::Result<Self>
+    where
+        B: Backend + Send + 'static,
+        F: FnOnce(B) -> io::Result<MorphBackend<B>> + Send + 'static,
+    {
+        let (commands, receiver) = mpsc::channel();
+        let (ready, started) = mpsc::channel();
+
+        let worker = thread::spawn(move || {
+            let mut morph = match setup(inner) {
+                Ok(morph) => morph,
+                Err(err) => {
+                    let _ = ready.send(Err(io::Error::new(err.kind(), err.to_string())));
+                    return Err(err);
+                }
+            };
+
+            morph.config_mut().tick = TickMode::Manual;
+            let _ = ready.send(Ok(()));
+            run(morph, receiver)
+        });
+
+        started
+            .recv()
+            .map_err(|_| io::Error::other("morph render thread failed to start"))??;
+
+        Ok(Self {
+            commands: Some(commands),
+            worker: Some(worker),
+        })
+    }
+
+    pub fn wrap<B, F>(inner: B, setup: F) -> io::Result<Terminal<Self>>
+    where
+        B: Backend + Send + 'static,
+        F: FnOnce(B) -> io::Result<MorphBackend<B>> + Send + 'static,
+    {
+        Terminal::new(Self::spawn(inner, setup)?)
+    }
+
+    /// Block until every queued frame has been shown and the last
+    /// transition has finished, e.g. before leaving the alternate screen.
+    pub fn settle(&mut self) -> io::Result<()> {
+        self.request(Command::Settle)?
+    }
+
+    fn send(&mut self, command: Command) -> io::Result<()> {
+        let sent = self
+            .commands
+            .as_ref()
+            .is_some_and(|commands| commands.send(command).is_ok());
+
+        if sent { Ok(()) } else { Err(self.stopped()) }
+    }
+
+    fn request<T>(&self, command: impl FnOnce(Sender<T>) -> Command) -> io::Result<T> {
+        let (reply, answer) = mpsc::channel();
+        let commands = self.commands.as_ref().ok_or_else(gone)?;
+
+        commands.send(command(reply)).map_err(|_| gone())?;
+        answer.recv().map_err(|_| gone())
+    }
+
+    /// The render thread's error, once it has exited.
+    fn stopped(&mut self) -> io::Error {
+        self.commands = None;
+
+        match self.worker.take().map(JoinHandle::join) {
+            Some(Ok(Err(err))) => err,
+            Some(Err(panic)) => std::panic::resume_unwind(panic),
+            _ => gone(),
+        }
+    }
+}
+
+fn gone() -> io::Error {
+    io::Error::other("morph render thread has stopped")
+}
+
+impl Drop for ThreadedMorphBackend {
+    /// Lets the render thread finish its current transition, then joins it.
+    fn drop(&mut self) {
+        self.commands = None;
+
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+fn run<B: Backend>(mut morph: MorphBackend<B>, commands: Receiver<Command>) -> io::Result<()> {
+    loop {
+        let command = match morph.next_frame_in() {
+            Some(wait) => match commands.recv_timeout(wait) {
+                Ok(command) => Some(command),
+                Err(RecvTimeoutError::Timeout) => None,
+                Err(RecvTimeoutError::Disconnected) => return settle(&mut morph),
+            },
+            None => match commands.recv() {
+                Ok(command) => Some(command),
+                Err(_) => return Ok(()),
+            },
+        };
+
+        if let Some(command) = command {
+            apply(&mut morph, command)?;
+        }
+
+        morph.tick()?;
+    }
+}
+
+fn apply<B: Backend>(morph: &mut MorphBackend<B>, command: Command) -> io::Result<()> {
+    match command {
+        Command::Draw(cells) => morph.draw(cells.iter().map(|(x, y, cell)| (*x, *y, cell)))?,
+        Command::Flush(rendered) => {
+            morph.keys().merge(rendered);
+            morph.flush()?;
+        }
+        Command::HideCursor => morph.hide_cursor()?,
+        Command::ShowCursor => morph.show_cursor()?,
+        Command::SetCursor(position) => morph.set_cursor_position(position)?,
+        Command::Clear => morph.clear()?,
+        Command::ClearRegion(clear_type) => morph.clear_region(clear_type)?,
+        Command::AppendLines(n) => morph.append_lines(n)?,
+        Command::GetCursor(reply) => {
+            let _ = reply.send(morph.get_cursor_position());
+        }
+        Command::Size(reply) => {
+            let _ = reply.send(morph.size());
+        }
+        Command::WindowSize(reply) => {
+            let _ = reply.send(morph.window_size());
+        }
+        Command::Settle(reply) => {
+            let _ = reply.send(settle(morph));
+        }
+    }
+
+    Ok(())
+}
+
+fn settle<B: Backend>(morph: &mut MorphBackend<B>) -> io::Result<()> {
+    while morph.tick()? {
+        if let Some(wait) = morph.next_frame_in() {
+            thread::sleep(wait);
+        }
+    }
+
+    Ok(())
+}
+
+impl Backend for ThreadedMorphBackend {
+    fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        let cells = content.map(|(x, y, cell)| (x, y, cell.clone())).collect();
+        self.send(Command::Draw(cells))
+    }
+
+    fn append_lines(&mut self, n: u16) -> io::Result<()> {
+        self.send(Command::AppendLines(n))
+    }
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        self.send(Command::HideCursor)
+    }
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        self.send(Command::ShowCursor)
+    }
+
+    fn get_cursor_position(&mut self) -> io::Result<Position> {
+        self.request(Command::GetCursor)?
+    }
+
+    fn set_cursor_position<P: Into<Position>>(&mut self, position: P) -> io::Result<()> {
+        self.send(Command::SetCursor(position.into()))
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        self.send(Command::Clear)
+    }
+
+    fn clear_region(&mut self, clear_type: ClearType) -> io::Result<()> {
+        self.send(Command::ClearRegion(clear_type))
+    }
+
+    fn size(&self) -> io::Result<Size> {
+        self.request(Command::Size)?
+    }
+
+    fn window_size(&mut self) -> io::Result<WindowSize> {
+        self.request(Command::WindowSize)?
+    }
+
+    /// Queues the frame and returns; the render thread animates it.
+    fn flush(&mut self) -> io::Result<()> {
+        self.send(Command::Flush(keys::take_rendered()))
+    }
+}
+
+// The test checks animation, which the `disabled` feature compiles out.
+#[cfg(all(test, not(feature = "disabled")))]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use ratatui::backend::TestBackend;
+    use ratatui::buffer::Buffer;
+    use ratatui::widgets::Paragraph;
+
+    use crate::backend::MorphConfig;
+    use crate::export::FrameSink;
+
+    use super::*;
+
+    type Seen = Arc<Mutex<Vec<(f32, String)>>>;
+
+    struct Record(Seen);
+
+    impl FrameSink for Record {
+        fn frame(&mut self, t: f32, buf: &Buffer, _: Duration) -> io::Result<()> {
+            let row: String = (0..buf.area.width).map(|x| buf[(x, 0)].symbol()).collect();
+            self.0.lock().unwrap().push((t, row));
+            Ok(())
+        }
+
+        fn finish(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn flush_returns_and_the_thread_animates() {
+        let seen = Seen::default();
+        let sink = Arc::clone(&seen);
+        let config = || MorphConfig {
+            duration: Duration::from_millis(100),
+            ..MorphConfig::default()
+        };
+
+        let mut terminal = ThreadedMorphBackend::wrap(TestBackend::new(4, 1), move |inner| {
+            let mut morph = MorphBackend::new(inner, config())?;
+            morph.set_sink(Box::new(Record(sink)));
+            Ok(morph)
+        })
+        .unwrap();
+
+        for text in ["ab", "ba"] {
+            terminal
+                .draw(|f| f.render_widget(Paragraph::new(text), f.area()))
+                .unwrap();
+        }
+
+        terminal.backend_mut().settle().unwrap();
+
+        let seen = seen.lock().unwrap();
+        assert!(seen.len() > 2);
+        assert_eq!(seen.last(), Some(&(1.0, "ba  ".to_string())));
+    }
+}
@@ -0,0 +1,541 @@
+use std::io;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use ratatui::buffer::Buffer;
+
+use crate::config::MorphConfig;
+use crate::easing::{self, CategoryEasing};
+use crate::export::{self, FrameSink};
+use crate::interpolate::{self, RenderOptions};
+use crate::motion::{self, MotionLimit};
+use crate::plan::InterpolationPlan;
+use crate::safety::{self, FlashLimit};
+use crate::solver::{self, SolveOptions, SolveScratch};
+use crate::weights::MorphWeights;
+
+/// How a transition's duration is chosen.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Pacing {
+    /// Always `MorphConfig::duration`.
+    #[default]
+    Fixed,
+    /// Duration follows the longest displacement at a constant speed, so
+    /// short moves finish quickly and long ones take proportionally longer.
+    /// Transitions without displacement use `MorphConfig::duration`.
+    Speed {
+        cells_per_second: f32,
+        min: Duration,
+        max: Duration,
+    },
+    /// Duration grows linearly with the number of changed cells from `min`,
+    /// reaching `max` at `full_at` cells, so small updates stay snappy and
+    /// scene changes get the full animation.
+    Magnitude {
+        full_at: usize,
+        min: Duration,
+        max: Duration,
+    },
+}
+
+impl Pacing {
+    pub fn duration_for(&self, plan: &InterpolationPlan, fixed: Duration) -> Duration {
+        match *self {
+            Self::Fixed => fixed,
+            Self::Speed {
+                cells_per_second,
+                min,
+                max,
+            } => {
+                let distance = plan.max_displacement();
+
+                if distance <= 0.0 || cells_per_second <= 0.0 {
+                    return fixed;
+                }
+
+                Duration::from_secs_f32(distance / cells_per_second).clamp(min, max.max(min))
+            }
+            Self::Magnitude { full_at, min, max } => {
+                let changed = plan.counts().changed();
+                let share = changed.min(full_at) as f64 / full_at.max(1) as f64;
+
+                min + max.saturating_sub(min).mul_f64(share)
+            }
+        }
+    }
+}
+
+/// A solved morph between two frames, independent of any backend.
+///
+/// Owns both endpoint buffers and the plan between them. `MorphBackend`
+/// plays these in real time; exporters and tests sample them directly,
+/// and custom render loops can drive one with `advance`:
+///
+/// ```ignore
+/// let mut transition = Transition::new(&before, &after, &config);
+/// while !transition.is_done() {
+///     draw(&transition.advance(frame_time));
+/// }
+/// ```
+pub struct Transition {
+    src: Buffer,
+    dst: Buffer,
+    plan: InterpolationPlan,
+    weights: MorphWeights,
+    solver: SolveOptions,
+    easing: fn(f32) -> f32,
+    category_easing: CategoryEasing,
+    render: RenderOptions,
+    fps: u32,
+    flash_limit: Option<FlashLimit>,
+    motion_limit: MotionLimit,
+    duration: Duration,
+    /// Playback position for `advance`.
+    elapsed: Duration,
+}
+
+impl Transition {
+    pub fn new(src: &Buffer, dst: &Buffer, config: &MorphConfig) -> Self {
+        Self::new_in(src, dst, config, &mut SolveScratch::default())
+    }
+
+    /// `new`, reusing `scratch`'s solver allocations.
+    pub fn new_in(
+        src: &Buffer,
+        dst: &Buffer,
+        config: &MorphConfig,
+        scratch: &mut SolveScratch,
+    ) -> Self {
+        let plan = solver::diff_in(src, dst, &config.weights, &config.solver, scratch);
+        Self::from_plan(src, dst, plan, config)
+    }
+
+    /// Start solving on a worker thread, so the solve can overlap playback
+    /// of an earlier transition. Only the solver inputs (the buffers, the
+    /// weights and `config.solver`) are snapshotted here; the rest of
+    /// `config` is read by `PendingTransition::join`.
+    pub fn spawn(src: Buffer, dst: Buffer, config: &MorphConfig) -> PendingTransition {
+        let weights = config.weights;
+        let options = config.solver.clone();
+
+        PendingTransition {
+            handle: thread::spawn(move || {
+                let plan = solver::diff_with(&src, &dst, &weights, &options);
+                (src, dst, plan)
+            }),
+        }
+    }
+
+    /// Wrap a plan produced elsewhere (e.g. `ThemeMap::plan`) so it can be
+    /// played like a solved one. `plan` must describe `src` → `dst`.
+    pub fn from_plan(
+        src: &Buffer,
+        dst: &Buffer,
+        plan: InterpolationPlan,
+        config: &MorphConfig,
+    ) -> Self {
+        // A differently sized source is stored as the solver saw it.
+        let src = if src.area == dst.area {
+            src.clone()
+        } else {
+            solver::refit(src, dst.area, config.solver.anchor)
+        };
+
+        let mut transition = Self {
+            src,
+            dst: dst.clone(),
+            plan,
+            weights: config.weights,
+            solver: config.solver.clone(),
+            easing: config.easing,
+            category_easing: config.category_easing,
+            render: RenderOptions {
+                modifiers: config.modifiers.clone(),
+                hue: config.hue,
+                animate: config.animate,
+                color: config.color.resolve(),
+                bg_sweep: config.bg_sweep.filter(|_| config.quality.bg_sweep()),
+                text_direction: config.text_direction,
+            },
+            fps: config.fps,
+            flash_limit: config.flash_limit,
+            motion_limit: config.motion_limit,
+            duration: config.duration,
+            elapsed: Duration::ZERO,
+        };
+
+        let requested = config
+            .pacing
+            .duration_for(&transition.plan, config.duration);
+        transition.retime(requested);
+        transition
+    }
+
+    /// Replace the requested duration. Safety and motion limits still apply.
+    pub fn with_duration(mut self, requested: Duration) -> Self {
+        self.retime(requested);
+        self
+    }
+
+    /// Moves too fast for the new duration are demoted to fades; demotion
+    /// is not undone if the duration later grows.
+    fn retime(&mut self, requested: Duration) {
+        self.duration = self.safe_duration(requested);
+
+        motion::apply(
+            &mut self.plan,
+            &self.motion_limit,
+            self.duration,
+            self.category_easing.displaced.unwrap_or(self.easing),
+            &self.src,
+            &self.dst,
+        );
+    }
+
+    fn safe_duration(&self, requested: Duration) -> Duration {
+        let Some(limit) = &self.flash_limit else {
+            return requested;
+        };
+
+        // Colors change on every curve; the steepest sets the pace.
+        let curves = &self.category_easing;
+        let steepest = [curves.mutating, curves.displaced, curves.orphans]
+            .into_iter()
+            .map(|curve| curve.unwrap_or(self.easing))
+            .max_by(|a, b| easing::peak_slope(a).total_cmp(&easing::peak_slope(b)))
+            .unwrap_or(self.easing);
+
+        safety::safe_duration(&self.plan, limit, requested, steepest, self.fps)
+    }
+
+    pub fn plan(&self) -> &InterpolationPlan {
+        &self.plan
+    }
+
+    pub fn into_plan(self) -> InterpolationPlan {
+        self.plan
+    }
+
+    pub fn source(&self) -> &Buffer {
+        &self.src
+    }
+
+    pub fn target(&self) -> &Buffer {
+        &self.dst
+    }
+
+    /// Wall-clock length, after any safety lengthening.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Eased progress at linear progress `t`, clamped to `[0, 1]`.
+    pub fn progress(&self, t: f32) -> f32 {
+        (self.easing)(t.clamp(0.0, 1.0))
+    }
+
+    /// Frame at linear progress `t`; easing is applied here, per category
+    /// where `CategoryEasing` says so. Clamped to `[0, 1]`.
+    pub fn frame_at(&self, t: f32) -> Buffer {
+        let t = t.clamp(0.0, 1.0);
+        let progress = self.category_easing.progress(self.easing, t);
+        interpolate::render_at(&self.plan, progress, &self.render)
+    }
+
+    /// Linear progress of `advance` playback, `1.0` once done.
+    pub fn t(&self) -> f32 {
+        if self.duration.is_zero() {
+            1.0
+        } else {
+            (self.elapsed.as_secs_f32() / self.duration.as_secs_f32()).min(1.0)
+        }
+    }
+
+    /// Move playback on by `dt` and return the frame for the new position.
+    pub fn advance(&mut self, dt: Duration) -> Buffer {
+        self.elapsed = self.elapsed.saturating_add(dt).min(self.duration);
+        self.frame_at(self.t())
+    }
+
+    /// `advance` has reached the target.
+    pub fn is_done(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+
+    /// Every frame of the transition sampled at `fps`, source and target
+    /// included, for exporters or loops that schedule flushes themselves.
+    pub fn frames(&self, fps: u32) -> impl Iterator<Item = Buffer> + '_ {
+        let n = export::frame_count(self.duration, fps);
+        let last = (n - 1) as f32;
+        (0..n).map(move |i| self.frame_at(i as f32 / last))
+    }
+
+    /// Feed frames sampled at `fps` into `sink`, timestamped in transition
+    /// time. Does not call `finish`, so several transitions can be recorded
+    /// into one sink.
+    pub fn record(&self, sink: &mut dyn FrameSink, fps: u32) -> io::Result<()> {
+        let last = (export::frame_count(self.duration, fps) - 1) as f32;
+
+        for (i, frame) in self.frames(fps).enumerate() {
+            let t = i as f32 / last;
+            sink.frame(t, &frame, self.duration.mul_f32(t))?;
+        }
+
+        Ok(())
+    }
+
+    /// The same morph played backwards — target to source, re-solved.
+    pub fn reverse(&self) -> Self {
+        let mut reversed = Self {
+            src: self.dst.clone(),
+            dst: self.src.clone(),
+            plan: solver::diff_with(&self.dst, &self.src, &self.weights, &self.solver),
+            weights: self.weights,
+            solver: self.solver.clone(),
+            easing: self.easing,
+            category_easing: self.category_easing,
+            render: self.render.clone(),
+            fps: self.fps,
+            flash_limit: self.flash_limit,
+            motion_limit: self.motion_limit,
+            duration: self.duration,
+            elapsed: Duration::ZERO,
+        };
+
+        reversed.retime(self.duration);
+        reversed
+    }
+}
+
+/// A transition being solved in the background; see `Transition::spawn`.
+pub struct PendingTransition {
+    handle: JoinHandle<(Buffer, Buffer, InterpolationPlan)>,
+}
+
+impl PendingTransition {
+    /// The solve is done and `join` will not block.
+    pub fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+
+    /// Wait for the solve and finish building the transition with
+    /// `config`'s timing and render settings. A panic in the solver is
+    /// resumed on the calling thread.
+    pub fn join(self, config: &MorphConfig) -> Transition {
+        let (src, dst, plan) = self
+            .handle
+            .join()
+            .unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+
+        Transition::from_plan(&src, &dst, plan, config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::layout::Rect;
+    use ratatui::style::{Color, Style};
+
+    use super::*;
+
+    fn make_buffer(cells: &[((u16, u16), &str, Color)]) -> Buffer {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 4, 1));
+
+        for &((x, y), sym, fg) in cells {
+            let cell = &mut buf[(x, y)];
+            cell.set_symbol(sym);
+            cell.set_style(Style::default().fg(fg));
+        }
+
+        buf
+    }
+
+    #[test]
+    fn frames_hit_both_endpoints() {
+        let a = make_buffer(&[((0, 0), "A", Color::Red)]);
+        let b = make_buffer(&[((3, 0), "B", Color::Blue)]);
+        let config = MorphConfig {
+            duration: Duration::from_millis(100),
+            ..MorphConfig::default()
+        };
+        let transition = Transition::new(&a, &b, &config);
+        let frames: Vec<Buffer> = transition.frames(40).collect();
+
+        assert_eq!(frames.len(), 5);
+        assert_eq!(frames[0][(0, 0)].symbol(), "A");
+        assert_eq!(frames[4][(3, 0)].symbol(), "B");
+    }
+
+    #[test]
+    fn speed_pacing_scales_with_distance() {
+        let config = MorphConfig {
+            weights: MorphWeights::LIQUID,
+            pacing: Pacing::Speed {
+                cells_per_second: 10.0,
+                min: Duration::from_millis(50),
+                max: Duration::from_secs(1),
+            },
+            ..MorphConfig::default()
+        };
+        let a = make_buffer(&[((0, 0), "M", Color::Red)]);
+        let near = make_buffer(&[((1, 0), "M", Color::Red)]);
+        let far = make_buffer(&[((3, 0), "M", Color::Red)]);
+
+        let short = Transition::new(&a, &near, &config).duration();
+        let long = Transition::new(&a, &far, &config).duration();
+
+        assert_eq!(short.as_millis(), 100);
+        assert_eq!(long.as_millis(), 300);
+    }
+
+    #[test]
+    fn magnitude_pacing_scales_with_changes() {
+        let config = MorphConfig {
+            pacing: Pacing::Magnitude {
+                full_at: 4,
+                min: Duration::from_millis(100),
+                max: Duration::from_millis(500),
+            },
+            ..MorphConfig::default()
+        };
+        let blank = make_buffer(&[]);
+        let one = make_buffer(&[((0, 0), "a", Color::Red)]);
+        let many = make_buffer(&[
+            ((0, 0), "a", Color::Red),
+            ((1, 0), "b", Color::Red),
+            ((2, 0), "c", Color::Red),
+            ((3, 0), "d", Color::Red),
+        ]);
+
+        let small = Transition::new(&blank, &one, &config).duration();
+        let large = Transition::new(&blank, &many, &config).duration();
+
+        assert!(small > Duration::from_millis(100) && small < large);
+        assert_eq!(large, Duration::from_millis(500));
+    }
+
+    #[test]
+    fn categories_ease_separately() {
+        let area = Rect::new(0, 0, 12, 1);
+        let (mut a, mut b) = (Buffer::empty(area), Buffer::empty(area));
+        a.set_string(0, 0, "M", Style::default());
+        b.set_string(8, 0, "M", Style::default());
+        b.set_string(11, 0, "+", Style::default());
+        let config = MorphConfig {
+            weights: MorphWeights::LIQUID,
+            easing: easing::linear,
+            category_easing: CategoryEasing {
+                displaced: Some(easing::ease_out),
+                orphans: Some(easing::delayed),
+                ..CategoryEasing::NONE
+            },
+            ..MorphConfig::default()
+        };
+        let transition = Transition::new(&a, &b, &config);
+        let frame = transition.frame_at(0.5);
+
+        // Motion is 3/4 done; the fade-in hasn't started.
+        assert_eq!(frame[(6, 0)].symbol(), "M");
+        assert_eq!(frame[(11, 0)].symbol(), " ");
+        assert_eq!(transition.frame_at(1.0), b);
+    }
+
+    #[test]
+    fn offset_area_renders_in_place() {
+        let area = Rect::new(2, 3, 4, 1);
+        let (mut a, mut b) = (Buffer::empty(area), Buffer::empty(area));
+        a[(2, 3)].set_symbol("M");
+        b[(5, 3)].set_symbol("M");
+
+        let config = MorphConfig {
+            weights: MorphWeights::LIQUID,
+            ..MorphConfig::default()
+        };
+        let transition = Transition::new(&a, &b, &config);
+
+        assert_eq!(transition.plan().area(), area);
+        assert_eq!(transition.frame_at(0.5).area, area);
+        assert_eq!(transition.frame_at(1.0), b);
+    }
+
+    #[test]
+    fn advance_plays_to_the_target() {
+        let a = make_buffer(&[((0, 0), "M", Color::Red)]);
+        let b = make_buffer(&[((0, 0), "M", Color::Blue)]);
+        let config = MorphConfig {
+            duration: Duration::from_millis(100),
+            easing: crate::easing::linear,
+            ..MorphConfig::default()
+        };
+        let mut transition = Transition::new(&a, &b, &config);
+
+        assert_eq!(transition.advance(Duration::ZERO), transition.frame_at(0.0));
+        assert_eq!(
+            transition.advance(Duration::from_millis(50)),
+            transition.frame_at(0.5)
+        );
+        assert!(!transition.is_done());
+
+        assert_eq!(
+            transition.advance(Duration::from_millis(80)),
+            transition.frame_at(1.0)
+        );
+        assert!(transition.is_done());
+        assert_eq!(transition.t(), 1.0);
+    }
+
+    #[test]
+    fn spawned_solve_matches_inline() {
+        let config = MorphConfig {
+            weights: MorphWeights::LIQUID,
+            ..MorphConfig::default()
+        };
+        let a = make_buffer(&[((0, 0), "M", Color::Red)]);
+        let b = make_buffer(&[((3, 0), "M", Color::Red)]);
+
+        let inline = Transition::new(&a, &b, &config);
+        let spawned = Transition::spawn(a, b, &config).join(&config);
+
+        assert!(inline.plan().diff(spawned.plan()).is_empty());
+        assert_eq!(inline.duration(), spawned.duration());
+    }
+
+    #[test]
+    fn reused_scratch_solves_the_same() {
+        let config = MorphConfig {
+            weights: MorphWeights::LIQUID,
+            ..MorphConfig::default()
+        };
+        let a = make_buffer(&[((0, 0), "M", Color::Red), ((1, 0), "N", Color::Red)]);
+        let b = make_buffer(&[((3, 0), "M", Color::Red)]);
+        let c = make_buffer(&[((2, 0), "N", Color::Blue), ((0, 0), "M", Color::Red)]);
+
+        let mut scratch = SolveScratch::default();
+        Transition::new_in(&a, &b, &config, &mut scratch);
+        let reused = Transition::new_in(&b, &c, &config, &mut scratch);
+        let fresh = Transition::new(&b, &c, &config);
+
+        assert!(reused.plan().diff(fresh.plan()).is_empty());
+    }
+
+    #[test]
+    fn differently_sized_source_is_refit() {
+        let a = Buffer::with_lines(["ab"]);
+        let b = make_buffer(&[((3, 0), "B", Color::Blue)]);
+        let transition = Transition::new(&a, &b, &MorphConfig::default());
+
+        assert_eq!(transition.source().area, b.area);
+        assert_eq!(transition.frame_at(0.0)[(0, 0)].symbol(), "a");
+        assert_eq!(transition.frame_at(1.0), b);
+    }
+
+    #[test]
+    fn reverse_swaps_endpoints() {
+        let a = make_buffer(&[((0, 0), "A", Color::Red)]);
+        let b = make_buffer(&[((1, 0), "B", Color::Blue)]);
+        let reversed = Transition::new(&a, &b, &MorphConfig::default()).reverse();
+
+        assert_eq!(reversed.frame_at(0.0)[(1, 0)].symbol(), "B");
+        assert_eq!(reversed.frame_at(1.0)[(0, 0)].symbol(), "A");
+    }
+}
@@ -0,0 +1,137 @@
+use ratatui::buffer::{Buffer, Cell};
+
+/// Widest run of unchanged cells worth re-emitting to avoid a cursor jump.
+/// A jump costs 6–10 bytes (`ESC [ row ; col H`); a plain cell costs one.
+pub const MAX_BRIDGE: u16 = 4;
+
+/// Typical cursor jump, as above.
+const JUMP_BYTES: usize = 8;
+/// Typical SGR sequence for a style change: a truecolor foreground or
+/// background plus modifiers.
+const SGR_BYTES: usize = 24;
+
+/// Cells to draw to turn `prev` into `next`, ordered for a compact
+/// escape stream.
+///
+/// Row-major like `Buffer::diff`, but short runs of unchanged cells
+/// between two updates on the same row are included when they share the
+/// preceding cell's style, so the terminal writes straight through instead
+/// of repositioning the cursor and re-sending SGR.
+pub fn coalesced<'a>(prev: &Buffer, next: &'a Buffer) -> Vec<(u16, u16, &'a Cell)> {
+    let updates = prev.diff(next);
+    let mut out = Vec::with_capacity(updates.len());
+
+    for (x, y, cell) in updates {
+        if let Some(&(px, py, last)) = out.last() {
+            bridge(next, (px, py, last), (x, y), &mut out);
+        }
+
+        out.push((x, y, cell));
+    }
+
+    out
+}
+
+/// Rough size of the escape stream for `updates`: each cell's text, a
+/// cursor jump wherever a run breaks and an SGR sequence wherever the
+/// style changes.
+pub fn estimated_bytes(updates: &[(u16, u16, &Cell)]) -> usize {
+    let mut bytes = 0;
+    let mut last: Option<(u16, u16, &Cell)> = None;
+
+    for &(x, y, cell) in updates {
+        bytes += cell.symbol().len();
+
+        if !last.is_some_and(|(px, py, prev)| py == y && px + 1 == x && is_narrow(prev)) {
+            bytes += JUMP_BYTES;
+        }
+
+        if last.is_none_or(|(_, _, prev)| prev.style() != cell.style()) {
+            bytes += SGR_BYTES;
+        }
+
+        last = Some((x, y, cell));
+    }
+
+    bytes
+}
+
+fn bridge<'a>(
+    next: &'a Buffer,
+    (px, py, last): (u16, u16, &'a Cell),
+    (x, y): (u16, u16),
+    out: &mut Vec<(u16, u16, &'a Cell)>,
+) {
+    let start = px + 1;
+
+    if !is_narrow(last) || py != y || x <= start || x - start > MAX_BRIDGE {
+        return;
+    }
+
+    let gap: Vec<&Cell> = (start..x).map(|gx| &next[(gx, y)]).collect();
+    let plain = gap
+        .iter()
+        .all(|cell| !cell.skip && is_narrow(cell) && cell.style() == last.style());
+
+    if plain {
+        out.extend((start..x).zip(gap).map(|(gx, cell)| (gx, y, cell)));
+    }
+}
+
+/// Single printable ASCII byte: known to advance the cursor by exactly one.
+fn is_narrow(cell: &Cell) -> bool {
+    matches!(cell.symbol().as_bytes(), [b' '..=b'~'])
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::layout::Rect;
+    use ratatui::style::{Color, Style};
+
+    use super::*;
+
+    fn positions(updates: &[(u16, u16, &Cell)]) -> Vec<(u16, u16)> {
+        updates.iter().map(|&(x, y, _)| (x, y)).collect()
+    }
+
+    #[test]
+    fn bridges_short_same_style_gaps() {
+        let prev = Buffer::with_lines(["abcdefghij"]);
+        let mut next = Buffer::with_lines(["Xbcdefghij"]);
+        next[(3, 0)].set_symbol("Y");
+        next[(9, 0)].set_symbol("Z");
+
+        let updates = coalesced(&prev, &next);
+        assert_eq!(
+            positions(&updates),
+            vec![(0, 0), (1, 0), (2, 0), (3, 0), (9, 0)]
+        );
+    }
+
+    #[test]
+    fn estimates_jumps_and_style_changes() {
+        let prev = Buffer::with_lines(["abcdefgh"]);
+        let mut next = Buffer::with_lines(["XYcdefgZ"]);
+        next.set_style(Rect::new(7, 0, 1, 1), Style::new().fg(Color::Red));
+
+        // "XY" in one run and style, then a jump and a restyle for "Z".
+        let updates = coalesced(&prev, &next);
+        assert_eq!(
+            estimated_bytes(&updates),
+            3 + 2 * JUMP_BYTES + 2 * SGR_BYTES
+        );
+    }
+
+    #[test]
+    fn keeps_gaps_with_different_style() {
+        let red = Rect::new(1, 0, 1, 1);
+        let mut prev = Buffer::with_lines(["abcd"]);
+        let mut next = Buffer::with_lines(["Xbcd"]);
+        next[(3, 0)].set_symbol("Y");
+        prev.set_style(red, Style::new().fg(Color::Red));
+        next.set_style(red, Style::new().fg(Color::Red));
+
+        let updates = coalesced(&prev, &next);
+        assert_eq!(positions(&updates), vec![(0, 0), (3, 0)]);
+    }
+}
@@ -5,6 +5,9 @@ pub struct MorphWeights {
     pub spatial: f32,
     pub glyph: f32,
     pub color: f32,
+    /// Background Oklch distance; keeps glyphs from flying across panels
+    /// of a different background.
+    pub background: f32,
     pub glyph_mismatch: f32,
 }
 
@@ -14,6 +17,7 @@ impl MorphWeights {
         spatial: 1.0,
         glyph: 0.1,
         color: 0.2,
+        background: 0.2,
         glyph_mismatch: 5.0,
     };
 
@@ -22,6 +26,7 @@ impl MorphWeights {
         spatial: 0.2,
         glyph: 1.0,
         color: 0.3,
+        background: 0.3,
         glyph_mismatch: 20.0,
     };
 
@@ -30,6 +35,7 @@ impl MorphWeights {
         spatial: 0.1,
         glyph: 0.1,
         color: 1.0,
+        background: 1.0,
         glyph_mismatch: 2.0,
     };
 }